@@ -1,3 +1,4 @@
+use aoc::{Answer, Solution};
 use std::collections::HashSet;
 
 fn parse(s: &str) -> Vec<i64> {
@@ -34,11 +35,27 @@ fn part2(input: &[i64]) -> i64 {
     }
 }
 
-pub fn run(filename: &str) {
-    let content = std::fs::read_to_string(filename).expect("Cannot read file");
-    let input = parse(&content);
-    println!("{}", part1(&input));
-    println!("{}", part2(&input));
+pub struct Day;
+
+impl Solution for Day {
+    type Input = Vec<i64>;
+
+    fn parse(&self, input: &str) -> Result<Vec<i64>, String> {
+        Ok(parse(input))
+    }
+
+    fn part1(&self, input: &Vec<i64>) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(&self, input: &Vec<i64>) -> Answer {
+        part2(input).into()
+    }
+
+    aoc::examples! {
+        "+1\n-2\n+3\n+1" => part1: 3, part2: 2;
+        "+3\n+3\n+4\n-2\n-4" => part2: 10;
+    }
 }
 
 #[cfg(test)]
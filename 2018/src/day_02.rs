@@ -1,3 +1,4 @@
+use aoc::{Answer, Solution};
 use std::collections::HashMap;
 
 struct Scan {
@@ -70,8 +71,20 @@ fn part2(s: &str) -> String {
     panic!("Invalid input")
 }
 
-pub fn run(filename: &str) {
-    let content = std::fs::read_to_string(filename).unwrap();
-    println!("{}", part1(&content));
-    println!("{}", part2(&content));
+pub struct Day;
+
+impl Solution for Day {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String, String> {
+        Ok(input.to_string())
+    }
+
+    fn part1(&self, input: &String) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(&self, input: &String) -> Answer {
+        part2(input).into()
+    }
 }
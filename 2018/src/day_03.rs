@@ -1,4 +1,5 @@
-use crate::util::vector2::Vector2;
+use aoc::{Answer, Solution};
+use geometry::Vector2;
 use scan_fmt::scan_fmt;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -56,7 +57,7 @@ fn part1(s: &str) -> usize {
     claims.values().filter(|&&c| c > 1).count()
 }
 
-fn part2(s: &str) {
+fn part2(s: &str) -> u32 {
     // We want to find the (unique) claim that does not overlap with other
     // claims.
     let mut claims: HashMap<Vector2<i32>, HashSet<u32>> = HashMap::new();
@@ -77,16 +78,25 @@ fn part2(s: &str) {
     if ids.len() != 1 {
         panic!("Invalid instance {}", ids.len())
     };
-    for id in ids.iter() {
-        println!("{}", id)
-    }
+    ids.into_iter().next().unwrap()
 }
 
-pub fn run(filename: &str) {
-    let content = std::fs::read_to_string(filename)
-        .unwrap_or_else(|_| panic!("Could not read file '{}'", filename));
-    println!("{}", part1(&content));
-    part2(&content);
+pub struct Day;
+
+impl Solution for Day {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String, String> {
+        Ok(input.to_string())
+    }
+
+    fn part1(&self, input: &String) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(&self, input: &String) -> Answer {
+        part2(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -1,3 +1,4 @@
+use aoc::{Answer, Solution};
 use std::collections::HashMap;
 
 #[derive(Debug, PartialEq, PartialOrd, Ord, Eq)]
@@ -8,7 +9,7 @@ enum Event {
 }
 
 #[derive(Debug, PartialEq, PartialOrd, Ord, Eq)]
-struct LogEntry {
+pub struct LogEntry {
     date: chrono::NaiveDate,
     timestamp: u32,
     event: Event,
@@ -151,12 +152,24 @@ fn part2(log: &[LogEntry]) -> u32 {
     id * (min as u32)
 }
 
-pub fn run(filename: &str) {
-    let content = std::fs::read_to_string(filename).unwrap();
-    let mut log: Vec<_> = content.split('\n').filter_map(parse_log_entry).collect();
-    log.sort();
-    println!("{}", part1(&log));
-    println!("{}", part2(&log));
+pub struct Day;
+
+impl Solution for Day {
+    type Input = Vec<LogEntry>;
+
+    fn parse(&self, input: &str) -> Result<Vec<LogEntry>, String> {
+        let mut log: Vec<_> = input.split('\n').filter_map(parse_log_entry).collect();
+        log.sort();
+        Ok(log)
+    }
+
+    fn part1(&self, log: &Vec<LogEntry>) -> Answer {
+        part1(log).into()
+    }
+
+    fn part2(&self, log: &Vec<LogEntry>) -> Answer {
+        part2(log).into()
+    }
 }
 
 #[cfg(test)]
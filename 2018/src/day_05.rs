@@ -1,3 +1,5 @@
+use aoc::{Answer, Solution};
+
 fn matching(a: char, b: char) -> bool {
     if a.to_ascii_uppercase() == b.to_ascii_uppercase() {
         (a.is_ascii_lowercase() && b.is_ascii_uppercase())
@@ -97,10 +99,22 @@ fn part2(s: &str) -> usize {
     len
 }
 
-pub fn run(filename: &str) {
-    let content = std::fs::read_to_string(filename).unwrap();
-    println!("{}", part1(&content));
-    println!("{}", part2(&content));
+pub struct Day;
+
+impl Solution for Day {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String, String> {
+        Ok(input.to_string())
+    }
+
+    fn part1(&self, input: &String) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(&self, input: &String) -> Answer {
+        part2(input).into()
+    }
 }
 
 #[cfg(test)]
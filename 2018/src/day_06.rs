@@ -1,4 +1,5 @@
-use crate::util::vector2::Vector2;
+use aoc::{Answer, Solution};
+use geometry::Vector2;
 use scan_fmt::scan_fmt;
 use std::collections::HashMap;
 
@@ -117,8 +118,20 @@ fn part2(s: &str) -> usize {
     count
 }
 
-pub fn run(filename: &str) {
-    let content = std::fs::read_to_string(filename).unwrap();
-    println!("{}", part1(&content));
-    println!("{}", part2(&content));
+pub struct Day;
+
+impl Solution for Day {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String, String> {
+        Ok(input.to_string())
+    }
+
+    fn part1(&self, input: &String) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(&self, input: &String) -> Answer {
+        part2(input).into()
+    }
 }
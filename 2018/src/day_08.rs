@@ -1,4 +1,6 @@
-struct T {
+use aoc::{Answer, Solution};
+
+pub struct T {
     tree: Vec<usize>,
 }
 
@@ -75,14 +77,22 @@ fn part2(t: &T) -> usize {
     v
 }
 
-pub fn run(filename: &str) {
-    let mut contents = std::fs::read_to_string(&filename).unwrap();
-    if contents.ends_with('\n') {
-        contents.pop();
-    };
-    let t = parse(&contents);
-    println!("{}", part1(&t));
-    println!("{}", part2(&t));
+pub struct Day;
+
+impl Solution for Day {
+    type Input = T;
+
+    fn parse(&self, input: &str) -> Result<T, String> {
+        Ok(parse(input.strip_suffix('\n').unwrap_or(input)))
+    }
+
+    fn part1(&self, t: &T) -> Answer {
+        part1(t).into()
+    }
+
+    fn part2(&self, t: &T) -> Answer {
+        part2(t).into()
+    }
 }
 
 #[cfg(test)]
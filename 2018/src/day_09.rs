@@ -1,3 +1,5 @@
+use aoc::{Answer, Solution};
+use scan_fmt::scan_fmt;
 use std::collections::VecDeque;
 
 // The `front` of the Deque corresponds to the current marble, and indicates the
@@ -67,14 +69,34 @@ fn part1(players: usize, last_marble: usize) -> usize {
     t.score.into_iter().max().unwrap()
 }
 
-pub fn run(filename: &str) {
-    // 428 players; last marble is worth 72061 points
-    let content = std::fs::read_to_string(filename).unwrap();
-    let words: Vec<_> = content.split(' ').collect();
-    let players: usize = words[0].parse().unwrap();
-    let last_marble: usize = words[6].parse().unwrap();
-    println!("{}", part1(players, last_marble));
-    println!("{}", part1(players, 100 * last_marble))
+pub struct Day;
+
+impl Solution for Day {
+    // The number of players, and the value of the last marble.
+    type Input = (usize, usize);
+
+    fn parse(&self, input: &str) -> Result<(usize, usize), String> {
+        scan_fmt!(
+            input.trim(),
+            "{d} players; last marble is worth {d} points",
+            usize,
+            usize
+        )
+        .map_err(|e| format!("{}: {}", input.trim(), e))
+    }
+
+    fn part1(&self, &(players, last_marble): &(usize, usize)) -> Answer {
+        part1(players, last_marble).into()
+    }
+
+    fn part2(&self, &(players, last_marble): &(usize, usize)) -> Answer {
+        part1(players, 100 * last_marble).into()
+    }
+
+    aoc::examples! {
+        "9 players; last marble is worth 25 points" => part1: 32;
+        "10 players; last marble is worth 1618 points" => part1: 8317;
+    }
 }
 
 #[cfg(test)]
@@ -1,9 +1,10 @@
-use crate::util::vector2::Vector2;
+use aoc::{Answer, Solution};
+use geometry::{ocr, Grid, SparseGrid, Vector2};
 use scan_fmt::scan_fmt;
 use std::collections::HashSet;
 
 #[derive(Clone, Eq, PartialEq, Hash)]
-struct Star {
+pub struct Star {
     position: Vector2<i32>,
     velocity: Vector2<i32>,
 }
@@ -30,7 +31,7 @@ fn parse(s: &str) -> Vec<Star> {
 
 fn step(stars: &mut Vec<Star>) {
     for star in stars.iter_mut() {
-        star.position = star.position + star.velocity
+        star.position += star.velocity
     }
 }
 
@@ -61,39 +62,134 @@ fn proximity(state: &[Star]) -> usize {
     count
 }
 
+fn to_image(state: &[Star]) -> Grid<bool> {
+    let mut sky = SparseGrid::new(false);
+    for star in state {
+        sky.insert(star.position, true);
+    }
+    sky.to_grid().1
+}
+
+// The width plus the height of the smallest box containing all the stars.
+fn size(state: &[Star]) -> i64 {
+    let xs = state.iter().map(|s| s.position.x as i64);
+    let ys = state.iter().map(|s| s.position.y as i64);
+    let width = xs.clone().max().unwrap_or(0) - xs.min().unwrap_or(0);
+    let height = ys.clone().max().unwrap_or(0) - ys.min().unwrap_or(0);
+    width + height
+}
+
 // For part 1, we need to find a magic number of steps after which the stars
 // display a message. We could do this step by step, but we can also infer from
 // the example that this corresponds to the step at which the
-fn part1(mut state: Vec<Star>) {
+// stars are the closest to each other. They get closer until then, and drift
+// apart afterwards, so we step until the box containing them stops shrinking.
+// Part 2 is the number of steps. When its letters are not recognized, the
+// message is given as a picture.
+fn solve(mut state: Vec<Star>) -> (Answer, usize) {
     let mut best_state = state.clone();
+    let mut best_time = 0;
     let mut best_proximity = proximity(&best_state);
-    for _i in 0..10_036 {
+    let mut previous_size = size(&state);
+    for time in 1.. {
         step(&mut state);
         let proximity = proximity(&state);
 
         if proximity > best_proximity {
             best_state = state.clone();
+            best_time = time;
             best_proximity = proximity;
         }
+        // The size cannot shrink forever, even when the stars are aligned.
+        let next_size = size(&state);
+        if next_size >= previous_size {
+            break;
+        }
+        previous_size = next_size;
     }
-    let positions: Vec<_> = best_state.iter().map(|s| s.position).collect();
-    let (min, max) = crate::util::vector2::bounding_box(positions.iter()).unwrap();
-    let positions: HashSet<_> = best_state.iter().map(|s| s.position).collect();
-    for y in (min.y - 1)..(max.y + 2) {
-        for x in (min.x - 1)..(max.x + 2) {
-            let p = Vector2::new(x, y);
-            if positions.contains(&p) {
-                print!("#")
-            } else {
-                print!(" ")
-            }
+    let image = to_image(&best_state);
+    match ocr::recognize(&image) {
+        Ok(message) => (message.into(), best_time),
+        Err(_) => (
+            Answer::grid(&image.render(|&b| if b { '#' } else { ' ' })),
+            best_time,
+        ),
+    }
+}
+
+const EXAMPLE: &str = "position=< 9,  1> velocity=< 0,  2>
+position=< 7,  0> velocity=<-1,  0>
+position=< 3, -2> velocity=<-1,  1>
+position=< 6, 10> velocity=<-2, -1>
+position=< 2, -4> velocity=< 2,  2>
+position=<-6, 10> velocity=< 2, -2>
+position=< 1,  8> velocity=< 1, -1>
+position=< 1,  7> velocity=< 1,  0>
+position=<-3, 11> velocity=< 1, -2>
+position=< 7,  6> velocity=<-1, -1>
+position=<-2,  3> velocity=< 1,  0>
+position=<-4,  3> velocity=< 2,  0>
+position=<10, -3> velocity=<-1,  1>
+position=< 5, 11> velocity=< 1, -2>
+position=< 4,  7> velocity=< 0, -1>
+position=< 8, -2> velocity=< 0,  1>
+position=<15,  0> velocity=<-2,  0>
+position=< 1,  6> velocity=< 1,  0>
+position=< 8,  9> velocity=< 0, -1>
+position=< 3,  3> velocity=<-1,  1>
+position=< 0,  5> velocity=< 0, -1>
+position=<-2,  2> velocity=< 2,  0>
+position=< 5, -2> velocity=< 1,  2>
+position=< 1,  4> velocity=< 2,  1>
+position=<-2,  7> velocity=< 2, -2>
+position=< 3,  6> velocity=<-1, -1>
+position=< 5,  0> velocity=< 1,  0>
+position=<-6,  0> velocity=< 2,  0>
+position=< 5,  9> velocity=< 1, -2>
+position=<14,  7> velocity=<-2,  0>
+position=<-3,  6> velocity=< 2, -1>";
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input = Vec<Star>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Star>, String> {
+        let stars = parse(input);
+        if stars.is_empty() {
+            return Err("No stars".to_string());
         }
-        println!()
+        Ok(stars)
+    }
+
+    fn part1(&self, stars: &Vec<Star>) -> Answer {
+        let (message, _) = solve(stars.clone());
+        message
+    }
+
+    fn part2(&self, stars: &Vec<Star>) -> Answer {
+        let (_, time) = solve(stars.clone());
+        time.into()
+    }
+
+    aoc::examples! {
+        EXAMPLE => part2: 3;
     }
 }
 
-pub fn run(filename: &str) {
-    let content = std::fs::read_to_string(&filename).unwrap();
-    let stars = parse(&content);
-    part1(stars)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The search ends when the stars never get closer.
+    #[test]
+    fn test_aligned() {
+        let stars = Day
+            .parse("position=<0, 0> velocity=<1, 0>\nposition=<3, 0> velocity=<1, 0>")
+            .unwrap();
+        assert_eq!(Day.part2(&stars), Answer::Int(0));
+        let star = Day.parse("position=<1, 2> velocity=<-1, 1>").unwrap();
+        assert_eq!(Day.part2(&star), Answer::Int(0));
+        assert!(Day.parse("").is_err());
+    }
 }
@@ -1,3 +1,4 @@
+use aoc::{Answer, Solution};
 use std::collections::HashMap;
 
 fn power_level(x: i32, y: i32, grid_serial_number: i32) -> i32 {
@@ -96,29 +97,28 @@ fn part2(grid_serial_number: i32) -> (i32, i32, i32) {
     best_position.unwrap()
 }
 
-fn average(grid_serial_number: i32) -> f64 {
-    let mut sum = 0.0;
-    let mut count = 0;
-    for x in 1..=300 {
-        for y in 1..=300 {
-            sum += power_level(x, y, grid_serial_number) as f64;
-            count += 1
-        }
+pub struct Day;
+
+impl Solution for Day {
+    type Input = i32;
+
+    fn parse(&self, input: &str) -> Result<i32, String> {
+        input.trim().parse().map_err(|e| format!("{:?}", e))
     }
-    sum / count as f64
-}
 
-pub fn run(filename: &str) {
-    let mut contents = std::fs::read_to_string(filename).unwrap();
-    if contents.ends_with('\n') {
-        contents.pop();
-    };
-    let grid_serial_number: i32 = contents.parse().unwrap();
-    println!("Average power level: {}", average(grid_serial_number));
-    let (x, y) = part1(grid_serial_number);
-    println!("{},{}", x, y);
-    let (x, y, s) = part2(grid_serial_number);
-    println!("{},{},{}", x, y, s);
+    fn part1(&self, &grid_serial_number: &i32) -> Answer {
+        let (x, y) = part1(grid_serial_number);
+        format!("{},{}", x, y).into()
+    }
+
+    fn part2(&self, &grid_serial_number: &i32) -> Answer {
+        let (x, y, s) = part2(grid_serial_number);
+        format!("{},{},{}", x, y, s).into()
+    }
+
+    aoc::examples! {
+        "18" => part1: "33,45", part2: "90,269,16";
+    }
 }
 
 #[cfg(test)]
@@ -1,3 +1,5 @@
+use aoc::{Answer, Solution};
+use graph::Cycle;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
@@ -96,44 +98,50 @@ fn part1(initial_state: &[bool], rules: &HashMap<u8, bool>) -> i64 {
 // one time step to the next). We can simply compute the value of this offset at
 // the step 50 * 10 ^ 9, and be done with it.
 fn part2(initial_state: &[bool], rules: &HashMap<u8, bool>) -> i64 {
-    let mut states: HashMap<Vec<i64>, (i64, usize)> = HashMap::new();
-    let mut state = T::initialize(initial_state);
-    let mut count = 0;
-
-    let ((offset1, step1), (offset2, step2)) = loop {
-        if states.contains_key(&state.plants) {
-            break (states.get(&state.plants).unwrap(), (state.offset, count));
-        };
-        states.insert(state.plants.clone(), (state.offset, count));
-        state = step(&state, rules);
-        count += 1;
-    };
-    if step2 == step1 + 1 {
-        let final_offset = (offset2 - offset1) * (50_000_000_000_i64 - step2 as i64) + offset2;
-        state.plants.iter().map(|i| i + final_offset).sum()
+    let init = T::initialize(initial_state);
+    let history = graph::find_cycle_by_key(init, |t| step(t, rules), |t| t.plants.clone());
+    let Cycle { start, period } = history.cycle;
+    if period == 1 {
+        let (first, next) = (&history.states[start], &history.states[start + 1]);
+        let final_offset =
+            (next.offset - first.offset) * (50_000_000_000_i64 - start as i64) + first.offset;
+        first.plants.iter().map(|i| i + final_offset).sum()
     } else {
         panic!()
     }
 }
 
-pub fn run(filename: &str) {
-    let contents = std::fs::read_to_string(filename).unwrap();
-    let contents: Vec<_> = contents.split("\n\n").collect();
-    let initial_state = contents[0].strip_prefix("initial state: ").unwrap();
-    let initial_state: Vec<bool> = initial_state.chars().map(|c| c == '#').collect();
-    let rules: HashMap<_, _> = contents[1]
-        .split('\n')
-        .filter_map(|r| {
-            if r.is_empty() {
-                None
-            } else {
-                let Rule { lhs, rhs } = parse_rule(r);
-                Some((lhs, rhs))
-            }
-        })
-        .collect();
-    println!("{}", part1(&initial_state, &rules));
-    println!("{}", part2(&initial_state, &rules));
+pub struct Day;
+
+impl Solution for Day {
+    // The initial state, and the rules.
+    type Input = (Vec<bool>, HashMap<u8, bool>);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, String> {
+        let contents: Vec<_> = input.split("\n\n").collect();
+        let initial_state = contents[0].strip_prefix("initial state: ").unwrap();
+        let initial_state: Vec<bool> = initial_state.chars().map(|c| c == '#').collect();
+        let rules: HashMap<_, _> = contents[1]
+            .split('\n')
+            .filter_map(|r| {
+                if r.is_empty() {
+                    None
+                } else {
+                    let Rule { lhs, rhs } = parse_rule(r);
+                    Some((lhs, rhs))
+                }
+            })
+            .collect();
+        Ok((initial_state, rules))
+    }
+
+    fn part1(&self, (initial_state, rules): &Self::Input) -> Answer {
+        part1(initial_state, rules).into()
+    }
+
+    fn part2(&self, (initial_state, rules): &Self::Input) -> Answer {
+        part2(initial_state, rules).into()
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,6 @@
-use crate::util::direction::Direction;
-use crate::util::vector2::Vector2;
+use aoc::{Answer, Solution};
+use geometry::Direction;
+use geometry::Vector2;
 use std::collections::HashMap;
 
 #[derive(Debug, Copy, Clone)]
@@ -11,8 +12,8 @@ struct Cart {
     crashed: bool,
 }
 
-#[derive(Debug)]
-struct T {
+#[derive(Debug, Clone)]
+pub struct T {
     rails: HashMap<Vector2<i32>, char>,
     carts: Vec<Cart>,
     collisions: Vec<Vector2<i32>>,
@@ -71,36 +72,8 @@ fn parse(s: &str) -> T {
     }
 }
 
-fn vel(d: Direction) -> Vector2<i32> {
-    match d {
-        Direction::North => Vector2::new(0, -1),
-        Direction::South => Vector2::new(0, 1),
-        Direction::East => Vector2::new(1, 0),
-        Direction::West => Vector2::new(-1, 0),
-    }
-}
-
-fn left(d: Direction) -> Direction {
-    match d {
-        Direction::North => Direction::West,
-        Direction::South => Direction::East,
-        Direction::East => Direction::North,
-        Direction::West => Direction::South,
-    }
-}
-
-fn right(d: Direction) -> Direction {
-    match d {
-        Direction::North => Direction::East,
-        Direction::South => Direction::West,
-        Direction::East => Direction::South,
-        Direction::West => Direction::North,
-    }
-}
-
 fn step(t: &T, c: Cart) -> Cart {
-    let vel = vel(c.dir);
-    let next = c.pos + vel;
+    let next = c.pos + c.dir.to_vector();
     let dir = match (c.dir, t.rails.get(&next).unwrap()) {
         (Direction::North, '|') => Direction::North,
         (Direction::South, '|') => Direction::South,
@@ -116,11 +89,11 @@ fn step(t: &T, c: Cart) -> Cart {
         (Direction::West, '-') => Direction::West,
         (dir, '+') => {
             if c.turns % 3 == 0 {
-                left(dir)
+                dir.turn_left()
             } else if c.turns % 3 == 1 {
                 dir
             } else {
-                right(dir)
+                dir.turn_right()
             }
         }
         (dir, c) => {
@@ -180,8 +153,22 @@ fn part2(t: &mut T) -> Vector2<i32> {
     t.carts[0].pos
 }
 
-pub fn run(filename: &str) {
-    let contents = std::fs::read_to_string(filename).unwrap();
-    println!("{:?}", part1(&mut parse(&contents)));
-    println!("{:?}", part2(&mut parse(&contents)))
+pub struct Day;
+
+impl Solution for Day {
+    type Input = T;
+
+    fn parse(&self, input: &str) -> Result<T, String> {
+        Ok(parse(input))
+    }
+
+    fn part1(&self, t: &T) -> Answer {
+        let pos = part1(&mut t.clone());
+        format!("{},{}", pos.x, pos.y).into()
+    }
+
+    fn part2(&self, t: &T) -> Answer {
+        let pos = part2(&mut t.clone());
+        format!("{},{}", pos.x, pos.y).into()
+    }
 }
@@ -1,3 +1,5 @@
+use aoc::{Answer, Solution};
+
 fn part1(n: usize) -> Vec<u8> {
     let mut scores: Vec<u8> = vec![3, 7];
     let mut pos1 = 0;
@@ -68,12 +70,29 @@ fn part2(mut n: usize) -> usize {
     scores.len() - target.len()
 }
 
-pub fn run(s: &str) {
-    let content = std::fs::read_to_string(s).unwrap();
-    let n = content.strip_suffix("\n").unwrap().parse().unwrap();
+pub struct Day;
+
+impl Solution for Day {
+    type Input = usize;
 
-    println!("{:?}", part1(n));
-    println!("{:?}", part2(n));
+    fn parse(&self, input: &str) -> Result<usize, String> {
+        input.trim().parse().map_err(|e| format!("{:?}", e))
+    }
+
+    fn part1(&self, &n: &usize) -> Answer {
+        let digits: String = part1(n).iter().map(|d| d.to_string()).collect();
+        digits.into()
+    }
+
+    fn part2(&self, &n: &usize) -> Answer {
+        part2(n).into()
+    }
+
+    aoc::examples! {
+        "9" => part1: "5158916779";
+        "51589" => part2: 9;
+        "59414" => part2: 2018;
+    }
 }
 
 #[cfg(test)]
@@ -1,8 +1,8 @@
-use core::panic;
-use std::collections::HashMap;
+use aoc::{Answer, Solution};
 use std::collections::HashSet;
 
-use crate::util::vector2::Vector2;
+use geometry::image::{Frames, Rgb};
+use geometry::{Grid, Vector2};
 
 // -> x
 // |
@@ -47,11 +47,16 @@ impl Unit {
     }
 }
 
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum Tile {
+    Wall,
+    Empty,
+    Unit(Unit),
+}
+
 #[derive(Debug, Clone)]
-struct T {
-    graph: HashMap<Position, Option<Unit>>,
-    width: i32,
-    height: i32,
+pub struct T {
+    graph: Grid<Tile>,
     rounds: i32,
     score: Option<(i32, i32)>,
     elf_attack_power: i32,
@@ -60,10 +65,10 @@ struct T {
 impl T {
     fn elves(&self) -> usize {
         self.graph
-            .values()
-            .filter(|u| match u {
-                None => false,
-                Some(u) => u.species == Species::Elf,
+            .iter()
+            .filter(|t| match t {
+                Tile::Unit(u) => u.species == Species::Elf,
+                _ => false,
             })
             .count()
     }
@@ -72,32 +77,32 @@ impl T {
         let mut b = String::new();
 
         b.push_str("    ");
-        for x in 0..=self.width {
+        for x in 0..self.graph.width() {
             b.push_str(&format!("{}", x % 10));
         }
 
         b.push('\n');
 
-        for y in 0..=self.height {
+        for (y, row) in self.graph.rows().enumerate() {
             let mut hps = Vec::new();
             b.push_str(&format!("{:3} ", y));
-            for x in 0..=self.width {
-                match self.graph.get(&Vector2::new(x, y)) {
-                    None => b.push('#'),
-                    Some(None) => b.push('.'),
-                    Some(Some(Unit {
+            for tile in row {
+                match tile {
+                    Tile::Wall => b.push('#'),
+                    Tile::Empty => b.push('.'),
+                    Tile::Unit(Unit {
                         species: Species::Elf,
                         hit_points,
                         ..
-                    })) => {
+                    }) => {
                         b.push('E');
                         hps.push(format!("E({})", hit_points))
                     }
-                    Some(Some(Unit {
+                    Tile::Unit(Unit {
                         species: Species::Goblin,
                         hit_points,
                         ..
-                    })) => {
+                    }) => {
                         b.push('G');
                         hps.push(format!("G({})", hit_points))
                     }
@@ -111,25 +116,25 @@ impl T {
     }
 
     fn is_empty_tile(&self, pos: &Position) -> bool {
-        matches!(self.graph.get(pos), Some(None))
+        matches!(self.graph.get(*pos), Some(Tile::Empty))
     }
 
     fn units(&self) -> Vec<Unit> {
-        self.graph.values().filter_map(|&x| x).collect()
+        self.graph
+            .iter()
+            .filter_map(|t| match t {
+                Tile::Unit(u) => Some(*u),
+                _ => None,
+            })
+            .collect()
     }
 
     fn targets(&self, from: Species) -> Vec<Position> {
         self.graph
-            .iter()
-            .filter_map(|(&p, v)| match v {
-                None => None,
-                Some(u) => {
-                    if u.species != from {
-                        Some(p)
-                    } else {
-                        None
-                    }
-                }
+            .enumerate()
+            .filter_map(|(p, t)| match t {
+                Tile::Unit(u) if u.species != from => Some(p),
+                _ => None,
             })
             .collect()
     }
@@ -190,16 +195,9 @@ impl T {
         let order = vec![pos + U, pos + L, pos + R, pos + D];
         let mut other_units: Vec<_> = order
             .into_iter()
-            .filter_map(|p| match self.graph.get(&p) {
-                None => None,
-                Some(None) => None,
-                Some(Some(other)) => {
-                    if other.species != unit.species {
-                        Some((p, *other))
-                    } else {
-                        None
-                    }
-                }
+            .filter_map(|p| match self.graph.get(p) {
+                Some(Tile::Unit(other)) if other.species != unit.species => Some((p, *other)),
+                _ => None,
             })
             .collect();
 
@@ -214,28 +212,23 @@ impl T {
 
         if other.hit_points > attack_power {
             other.hit_points -= attack_power;
-            self.graph.insert(other_pos, Some(other));
+            self.graph[other_pos] = Tile::Unit(other);
         } else {
-            self.graph.insert(other_pos, None);
+            self.graph[other_pos] = Tile::Empty;
         }
     }
 
     fn step(&mut self) {
-        for y in 0..=self.height {
-            for x in 0..=self.width {
+        for y in 0..self.graph.height() as i32 {
+            for x in 0..self.graph.width() as i32 {
                 let p1 = Vector2::new(x, y);
                 // Get a copy of the unit to process, if any.
-                let unit_to_process = match self.graph.get_mut(&p1) {
-                    None => None,
-                    Some(None) => None,
-                    Some(Some(u1)) => {
-                        if u1.last_move < self.rounds {
-                            u1.last_move = self.rounds;
-                            Some(*u1)
-                        } else {
-                            None
-                        }
+                let unit_to_process = match self.graph.get_mut(p1) {
+                    Some(Tile::Unit(u1)) if u1.last_move < self.rounds => {
+                        u1.last_move = self.rounds;
+                        Some(*u1)
                     }
+                    _ => None,
                 };
                 match unit_to_process {
                     None => {}
@@ -259,9 +252,9 @@ impl T {
                         } else {
                             // Find path toward closest square (breaking ties by chosing the first square in reading order) and move in that direction, then attack, if possible.
                             let dir = self.find_path_to_closest_target(p1, &targets);
-                            self.graph.insert(p1, None);
+                            self.graph[p1] = Tile::Empty;
                             assert!(self.is_empty_tile(&(p1 + dir)), "{:?} {:?}", p1, dir);
-                            self.graph.insert(p1 + dir, Some(u1));
+                            self.graph[p1 + dir] = Tile::Unit(u1);
                             if in_range_of_targets.contains(&(p1 + dir)) {
                                 self.attack(p1 + dir, u1)
                             }
@@ -275,44 +268,45 @@ impl T {
 }
 
 fn parse(s: &str) -> T {
-    let s = s.replace(" ", "");
-    let lines = s.split('\n');
-    let mut graph = HashMap::new();
-    let mut width = 0;
-    let mut height = 0;
-    for (y, line) in lines.enumerate() {
-        let y = y as i32;
-        for (x, c) in line.chars().enumerate() {
-            let x = x as i32;
-            let pos = Vector2::new(x, y);
-            match c {
-                '.' => {
-                    graph.insert(pos, None);
-                }
-                'G' => {
-                    graph.insert(pos, Some(Unit::g()));
-                }
-                'E' => {
-                    graph.insert(pos, Some(Unit::e()));
-                }
-                '#' => {}
-                _ => panic!("Unexpected puzzle input {}", c),
-            };
-            width = std::cmp::max(width, x);
-        }
-        height = std::cmp::max(height, y);
-    }
+    let s = s.replace(' ', "");
+    let graph = Grid::parse(&s, |c| match c {
+        '.' => Some(Tile::Empty),
+        'G' => Some(Tile::Unit(Unit::g())),
+        'E' => Some(Tile::Unit(Unit::e())),
+        '#' => Some(Tile::Wall),
+        _ => None,
+    })
+    .unwrap();
     T {
         rounds: 0,
         graph,
-        height,
-        width,
         score: None,
         elf_attack_power: 3,
     }
 }
 
+// Units fade to black as they lose hit points.
+fn tile_to_rgb(tile: &Tile) -> Rgb {
+    let shade = |c: u8, hp: i32| (c as i32 * hp.max(0) / 200) as u8;
+    match tile {
+        Tile::Wall => Rgb::GREY,
+        Tile::Empty => Rgb::BLACK,
+        Tile::Unit(u) => {
+            let Rgb(r, g, b) = match u.species {
+                Species::Elf => Rgb::GREEN,
+                Species::Goblin => Rgb::RED,
+            };
+            Rgb(
+                shade(r, u.hit_points),
+                shade(g, u.hit_points),
+                shade(b, u.hit_points),
+            )
+        }
+    }
+}
+
 fn part1(mut t: T, debug: bool) -> (i32, i32) {
+    let mut frames = Frames::from_env("2018_day_15", 16);
     let score = loop {
         match t.score {
             None => {
@@ -321,12 +315,17 @@ fn part1(mut t: T, debug: bool) -> (i32, i32) {
                     t.print();
                     println!();
                 }
+                if let Some(frames) = &mut frames {
+                    frames.write(&t.graph, tile_to_rgb).unwrap();
+                }
                 t.step()
             }
             Some(score) => break score,
         }
     };
-    t.print();
+    if debug {
+        t.print();
+    }
     score
 }
 
@@ -348,24 +347,24 @@ fn part2(t0: T) -> (i32, i32, i32) {
     }
 }
 
-pub fn run(s: &str) {
-    let contents = std::fs::read_to_string(s).unwrap();
-    let t = parse(&contents);
-    let (rounds, hps) = part1(t.clone(), false);
-    println!(
-        "rounds: {}, hitpoints: {}, score: {}",
-        rounds,
-        hps,
-        rounds * hps
-    );
-    let (ap, rounds, hps) = part2(t);
-    println!(
-        "ap: {}, rounds: {}, hitpoints: {}, score: {}",
-        ap,
-        rounds,
-        hps,
-        rounds * hps
-    );
+pub struct Day;
+
+impl Solution for Day {
+    type Input = T;
+
+    fn parse(&self, input: &str) -> Result<T, String> {
+        Ok(parse(input))
+    }
+
+    fn part1(&self, t: &T) -> Answer {
+        let (rounds, hps) = part1(t.clone(), false);
+        (rounds * hps).into()
+    }
+
+    fn part2(&self, t: &T) -> Answer {
+        let (_ap, rounds, hps) = part2(t.clone());
+        (rounds * hps).into()
+    }
 }
 
 #[cfg(test)]
@@ -483,10 +482,7 @@ Outcome: 20 * 937 = 18740";
                       #...#   
                       #####";
         let t = parse(s);
-        assert_eq!(
-            t.graph.get(&Vector2::new(2, 1)).copied().unwrap(),
-            Some(Unit::g())
-        );
+        assert_eq!(t.graph[Vector2::new(2, 1)], Tile::Unit(Unit::g()));
     }
     #[test]
     fn test_example_1() {
@@ -1,9 +1,10 @@
 use crate::asm::*;
+use aoc::{Answer, Solution};
 use std::collections::HashMap;
 use std::collections::HashSet;
 
 #[derive(PartialEq, Debug)]
-struct Sample {
+pub struct Sample {
     before: [RegType; 4],
     after: [RegType; 4],
     opcode: RegType,
@@ -178,21 +179,31 @@ fn execute(program: &[DInstr]) -> [RegType; 4] {
     regs
 }
 
-pub fn run(s: &str) {
-    let contents = std::fs::read_to_string(s).unwrap();
-    let parts: Vec<_> = contents.split("\n\n\n\n").collect();
+pub struct Day;
 
-    let samples: Vec<_> = parts[0].split("\n\n").map(|s| parse_sample(s)).collect();
-    println!("{}", part1(&samples));
-    let cypher_to_plain = mapping(&samples);
+impl Solution for Day {
+    // The samples, and the program.
+    type Input = (Vec<Sample>, String);
 
-    let program: Vec<_> = parts[1]
-        .lines()
-        .map(|l| decode_instruction(l, &cypher_to_plain))
-        .collect();
+    fn parse(&self, input: &str) -> Result<Self::Input, String> {
+        let parts: Vec<_> = input.split("\n\n\n\n").collect();
+        let samples: Vec<_> = parts[0].split("\n\n").map(parse_sample).collect();
+        Ok((samples, parts[1].to_string()))
+    }
 
-    let regs = execute(&program);
-    println!("{:?}", regs);
+    fn part1(&self, (samples, _): &Self::Input) -> Answer {
+        part1(samples).into()
+    }
+
+    fn part2(&self, (samples, program): &Self::Input) -> Answer {
+        let cypher_to_plain = mapping(samples);
+        let program: Vec<_> = program
+            .lines()
+            .map(|l| decode_instruction(l, &cypher_to_plain))
+            .collect();
+        let regs = execute(&program);
+        regs[0].into()
+    }
 }
 
 #[cfg(test)]
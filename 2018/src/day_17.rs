@@ -1,6 +1,7 @@
-use crate::util::vector2::Vector2;
+use aoc::{Answer, Solution};
+use geometry::image::{Frames, Rgb};
+use geometry::{Aabb, SparseGrid, Vector2};
 use scan_fmt::scan_fmt;
-use std::collections::HashMap;
 
 type Position = Vector2<i32>;
 
@@ -12,10 +13,11 @@ enum Type {
 }
 
 #[derive(Debug, Clone)]
-struct T {
-    scan: HashMap<Position, Type>,
-    upper_left: Position,
-    bottom_right: Position,
+pub struct T {
+    scan: SparseGrid<Option<Type>, i32>,
+    // Only the rows of the initial scan matter.
+    ymin: i32,
+    ymax: i32,
 }
 
 fn parse_line(s: &str) -> Vec<Position> {
@@ -30,54 +32,60 @@ fn parse_line(s: &str) -> Vec<Position> {
     }
 }
 fn parse(s: &str) -> T {
-    let mut t = HashMap::new();
+    let mut scan = SparseGrid::new(None);
     for line in s.lines() {
         for pos in parse_line(line) {
-            t.insert(pos, Type::Clay);
+            scan.insert(pos, Some(Type::Clay));
         }
     }
-    let (upper_left, bottom_right) = crate::util::vector2::bounding_box(t.keys()).unwrap();
+    let bounds = scan.bounds().unwrap();
 
     T {
-        scan: t,
-        upper_left,
-        bottom_right,
+        scan,
+        ymin: bounds.low[1],
+        ymax: bounds.high[1],
     }
 }
 
 // --> x
 // |
 // v y
-fn print(t: &T) {
-    let mut b = String::new();
-    b.push_str("   ");
-    for x in t.upper_left.x..=t.bottom_right.x {
-        b.push_str(&format!("{}", (x / 100) % 10));
-    }
-    b.push('\n');
-    b.push_str("   ");
-    for x in t.upper_left.x..=t.bottom_right.x {
-        b.push_str(&format!("{}", (x / 10) % 10));
-    }
-    b.push('\n');
-    b.push_str("   ");
-    for x in t.upper_left.x..=t.bottom_right.x {
-        b.push_str(&format!("{}", (x % 10)));
+// The part of the scan that matters.
+fn viewport(t: &T) -> Aabb<i32, 2> {
+    let bounds = t.scan.bounds().unwrap();
+    Aabb::new([bounds.low[0], t.ymin], [bounds.high[0], t.ymax])
+}
+
+fn type_to_rgb(ty: &Option<Type>) -> Rgb {
+    match ty {
+        None => Rgb::WHITE,
+        Some(Type::Clay) => Rgb::BROWN,
+        Some(Type::Water) => Rgb::BLUE,
+        Some(Type::Transient) => Rgb(150, 200, 255),
     }
-    b.push('\n');
+}
 
-    for y in t.upper_left.y..=t.bottom_right.y {
-        b.push_str(&format!("{:3}", y));
-        for x in t.upper_left.x..=t.bottom_right.x {
-            match t.scan.get(&Vector2::new(x, y)) {
-                None => b.push('.'),
-                Some(Type::Clay) => b.push('#'),
-                Some(Type::Water) => b.push('~'),
-                Some(Type::Transient) => b.push('|'),
-            }
+#[allow(dead_code)]
+fn print(t: &T) {
+    let viewport = viewport(t);
+    let mut b = String::new();
+    for scale in [100, 10, 1] {
+        b.push_str("   ");
+        for x in viewport.low[0]..=viewport.high[0] {
+            b.push_str(&format!("{}", (x / scale) % 10));
         }
         b.push('\n');
     }
+
+    let rendered = t.scan.render_viewport(&viewport, |ty| match ty {
+        None => '.',
+        Some(Type::Clay) => '#',
+        Some(Type::Water) => '~',
+        Some(Type::Transient) => '|',
+    });
+    for (y, line) in (t.ymin..).zip(rendered.lines()) {
+        b.push_str(&format!("{:3}{}\n", y, line));
+    }
     println!("{}", b);
 }
 
@@ -87,21 +95,15 @@ const R: Vector2<i32> = Vector2 { x: 1, y: 0 };
 
 impl T {
     fn solid(&self, pos: Position) -> bool {
-        matches!(self.scan.get(&(pos)), Some(Type::Water) | Some(Type::Clay))
+        matches!(self.scan[pos], Some(Type::Water) | Some(Type::Clay))
     }
 
     fn transient(&self, pos: Position) -> bool {
-        matches!(self.scan.get(&(pos)), Some(Type::Transient))
+        matches!(self.scan[pos], Some(Type::Transient))
     }
 
     fn insert(&mut self, pos: Position, ty: Type) {
-        self.scan.insert(pos, ty);
-        if pos.x < self.upper_left.x {
-            self.upper_left.x = pos.x
-        };
-        if self.bottom_right.x < pos.x {
-            self.bottom_right.x = pos.x
-        }
+        self.scan.insert(pos, Some(ty));
     }
 }
 
@@ -119,7 +121,7 @@ fn left(t: &T, mut pos: Position) -> Border {
         } else if t.solid(pos + L) {
             return Border::Wall(pos + L);
         } else {
-            pos = pos + L;
+            pos += L;
         }
     }
 }
@@ -131,7 +133,7 @@ fn right(t: &T, mut pos: Position) -> Border {
         } else if t.solid(pos + R) {
             return Border::Wall(pos + R);
         } else {
-            pos = pos + R;
+            pos += R;
         }
     }
 }
@@ -142,11 +144,20 @@ fn pour(t: &mut T, spring: Position) {
     t.insert(spring, Type::Transient);
 
     let mut y = spring.y;
+    let mut frames = Frames::from_env("2018_day_17", 1);
 
-    while y <= t.bottom_right.y {
+    while y <= t.ymax {
+        if let Some(frames) = &mut frames {
+            // Follow the current row, since the whole scan is very tall.
+            let mut window = viewport(t);
+            window.low[1] = y - 100;
+            window.high[1] = y + 50;
+            frames.write(&t.scan.crop(&window), type_to_rgb).unwrap();
+        }
         let mut candidates = vec![];
 
-        for x in t.upper_left.x..=t.bottom_right.x {
+        let bounds = t.scan.bounds().unwrap();
+        for x in bounds.low[0]..=bounds.high[0] {
             let p = Vector2::new(x, y);
             if t.transient(p) && !t.solid(p + D) {
                 candidates.push(p + D)
@@ -225,9 +236,9 @@ fn count_wet(t: &T) -> usize {
     t.scan
         .iter()
         .filter(|&(pos, ty)| {
-            t.upper_left.y <= pos.y
-                && pos.y <= t.bottom_right.y
-                && matches!(ty, Type::Water | Type::Transient)
+            t.ymin <= pos.y
+                && pos.y <= t.ymax
+                && matches!(ty, Some(Type::Water) | Some(Type::Transient))
         })
         .count()
 }
@@ -235,21 +246,30 @@ fn count_wet(t: &T) -> usize {
 fn count_dry(t: &T) -> usize {
     t.scan
         .iter()
-        .filter(|&(pos, ty)| {
-            t.upper_left.y <= pos.y && pos.y <= t.bottom_right.y && matches!(ty, Type::Water)
-        })
+        .filter(|&(pos, ty)| t.ymin <= pos.y && pos.y <= t.ymax && matches!(ty, Some(Type::Water)))
         .count()
 }
 
-pub fn run(filename: &str) {
-    let contents = std::fs::read_to_string(filename).unwrap();
-    let mut t = parse(&contents);
-    pour(&mut t, Vector2::new(500, 0));
-    println!("{}", count_wet(&t));
-    println!("{}", count_dry(&t));
-    if false {
-        print(&t)
-    };
+pub struct Day;
+
+impl Solution for Day {
+    type Input = T;
+
+    fn parse(&self, input: &str) -> Result<T, String> {
+        Ok(parse(input))
+    }
+
+    fn part1(&self, t: &T) -> Answer {
+        let mut t = t.clone();
+        pour(&mut t, Vector2::new(500, 0));
+        count_wet(&t).into()
+    }
+
+    fn part2(&self, t: &T) -> Answer {
+        let mut t = t.clone();
+        pour(&mut t, Vector2::new(500, 0));
+        count_dry(&t).into()
+    }
 }
 
 #[cfg(test)]
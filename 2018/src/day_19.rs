@@ -1,4 +1,5 @@
 use crate::asm::{parse, Program, T};
+use aoc::{Answer, Solution};
 
 fn part1(text: &Program, ip: usize) -> u64 {
     let mut t = T::new(text, ip);
@@ -30,11 +31,25 @@ fn part2(_text: &Program, _ip: usize) -> u64 {
     return 15864120;
 }
 
-pub fn run(filename: &str) {
-    let contents = std::fs::read_to_string(filename).unwrap();
-    let (ip, text) = parse(&contents);
-    println!("{:?}", part1(&text, ip.unwrap()));
-    println!("{:?}", part2(&text, ip.unwrap()));
+pub struct Day;
+
+impl Solution for Day {
+    // The register bound to the instruction pointer, and the program.
+    type Input = (usize, Program);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, String> {
+        let (ip, text) = parse(input);
+        let ip = ip.ok_or("Missing #ip directive")?;
+        Ok((ip, text))
+    }
+
+    fn part1(&self, (ip, text): &Self::Input) -> Answer {
+        part1(text, *ip).into()
+    }
+
+    fn part2(&self, (ip, text): &Self::Input) -> Answer {
+        part2(text, *ip).into()
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,7 @@
-use crate::util::direction::Direction;
-use crate::util::vector2::Vector2;
+use aoc::{Answer, Solution};
+use geometry::Direction;
+use geometry::SparseGrid;
+use geometry::Vector2;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
@@ -69,17 +71,10 @@ fn parse(input: &str) -> T {
         .unwrap();
     result
 }
-fn step(d: Direction) -> Vector2<i32> {
-    match d {
-        Direction::East => Vector2::new(1, 0),
-        Direction::West => Vector2::new(-1, 0),
-        Direction::North => Vector2::new(0, 1),
-        Direction::South => Vector2::new(0, -1),
-    }
-}
+
 struct State {
-    rooms: HashMap<Position, i32>,
-    graph: HashMap<Position, HashSet<Position>>,
+    // The rooms reachable through the doors of each room.
+    graph: SparseGrid<HashSet<Position>, i32>,
 }
 
 #[derive(Clone)]
@@ -97,16 +92,15 @@ impl Positions {
 impl State {
     fn new() -> State {
         State {
-            rooms: HashMap::new(),
-            graph: HashMap::new(),
+            graph: SparseGrid::new(HashSet::new()),
         }
     }
 
     fn step(&mut self, pos: &Positions, d: Direction) -> Positions {
         let mut next = Positions::new();
         for &p in pos.0.iter() {
-            let neighbors = self.graph.entry(p).or_insert(HashSet::new());
-            let d = step(d);
+            let neighbors = self.graph.entry(p);
+            let d = d.to_vector();
             neighbors.insert(p + d);
             next.insert(p + d)
         }
@@ -119,9 +113,7 @@ impl State {
         let mut lpar: Vec<Positions> = Vec::new();
         let mut rpar = Vec::new();
 
-        for (i, c) in s.chars().enumerate() {
-            println!("{}", i);
-
+        for c in s.chars() {
             match c {
                 'N' => pos = self.step(&pos, Direction::North),
                 'S' => pos = self.step(&pos, Direction::South),
@@ -155,13 +147,8 @@ impl State {
         while let Some((p, d)) = todo.pop_front() {
             if !visited.contains_key(&p) {
                 visited.insert(p, d);
-                match self.graph.get(&p) {
-                    None => {}
-                    Some(hs) => {
-                        for &n in hs.iter() {
-                            todo.push_back((n, d + 1))
-                        }
-                    }
+                for &n in self.graph[p].iter() {
+                    todo.push_back((n, d + 1))
                 }
             }
         }
@@ -177,14 +164,31 @@ fn distances(t: &str) -> HashMap<Position, i32> {
     state.distances()
 }
 
-pub fn run(filename: &str) {
-    let content = std::fs::read_to_string(filename).unwrap();
-    // let t = parse(&content[1..content.len() - 2]);
-    let distances = distances(&content[1..content.len() - 2]);
-    let &part1 = distances.values().max().unwrap();
-    let part2 = distances.values().filter(|&&d| d >= 1000).count();
-    println!("{}", part1);
-    println!("{}", part2);
+pub struct Day;
+
+impl Solution for Day {
+    // The regular expression, without its anchors.
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String, String> {
+        let regex = input.trim_end();
+        let regex = regex
+            .strip_prefix('^')
+            .and_then(|r| r.strip_suffix('$'))
+            .ok_or("Expected a regular expression between ^ and $")?;
+        Ok(regex.to_string())
+    }
+
+    fn part1(&self, regex: &String) -> Answer {
+        let distances = distances(regex);
+        let &max = distances.values().max().unwrap();
+        max.into()
+    }
+
+    fn part2(&self, regex: &String) -> Answer {
+        let distances = distances(regex);
+        distances.values().filter(|&&d| d >= 1000).count().into()
+    }
 }
 
 #[cfg(test)]
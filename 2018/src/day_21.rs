@@ -1,3 +1,5 @@
+use crate::asm::{parse, MULI, ORI, SETI};
+use aoc::{Answer, Solution};
 use std::collections::HashSet;
 
 enum State {
@@ -12,8 +14,15 @@ enum Mode {
     Max,
 }
 
+// The constants of the program that differ between inputs: the value that `r4` is reset to, and
+// the multiplier of the hash.
+pub struct Constants {
+    start: u64,
+    multiplier: u64,
+}
+
 // The program that we are given implement the following construct
-fn secret(seed: u64, mode: Mode) -> u64 {
+fn secret(constants: &Constants, seed: u64, mode: Mode) -> u64 {
     let mut r1 = 0;
     let mut r2 = 0;
     let mut r4 = 0;
@@ -26,13 +35,13 @@ fn secret(seed: u64, mode: Mode) -> u64 {
         match state {
             State::L6 => {
                 r2 = r4 | 0b10000000000000000;
-                r4 = 6152285;
+                r4 = constants.start;
                 state = State::L8
             }
             State::L8 => {
                 r1 = r2 & 0b11111111;
                 r4 = (r4 + r1) & 0b111111111111111111111111;
-                r4 = (r4 * 65899) & 0b111111111111111111111111;
+                r4 = (r4 * constants.multiplier) & 0b111111111111111111111111;
                 if 256 > r2 {
                     state = State::L28;
                 } else {
@@ -93,11 +102,33 @@ fn secret(seed: u64, mode: Mode) -> u64 {
 // else l6 ()
 // ;;
 
-pub fn run(s: &str) {
-    // let contents = std::fs::read_to_string(s).unwrap();
-    // let (ipr, text) = crate::asm::parse(&contents);
-    // let mut t = crate::asm::T::new(&text, ipr.unwrap());
+pub struct Day;
+
+impl Solution for Day {
+    // `secret` is a hand-decompiled version of the program: only its constants are read. The
+    // start value is set right after `r2 = r4 | 65536`.
+    type Input = Constants;
+
+    fn parse(&self, input: &str) -> Result<Constants, String> {
+        let (_, program) = parse(input);
+        let start = program
+            .windows(2)
+            .find(|w| *w[0].0 == ORI && w[0].2 == 65536 && *w[1].0 == SETI)
+            .map(|w| w[1].1)
+            .ok_or("Cannot find the start value of the hash")?;
+        let multiplier = program
+            .iter()
+            .find(|instr| *instr.0 == MULI)
+            .map(|instr| instr.2)
+            .ok_or("Cannot find the multiplier of the hash")?;
+        Ok(Constants { start, multiplier })
+    }
+
+    fn part1(&self, constants: &Constants) -> Answer {
+        secret(constants, 0, Mode::Min).into()
+    }
 
-    println!("{}", secret(0, Mode::Min));
-    println!("{}", secret(0, Mode::Max));
+    fn part2(&self, constants: &Constants) -> Answer {
+        secret(constants, 0, Mode::Max).into()
+    }
 }
@@ -1,12 +1,11 @@
-use std::{
-    collections::{BinaryHeap, HashMap},
-    hash::Hash,
-};
-struct T {
+use aoc::{Answer, Solution};
+use std::{cell::RefCell, collections::HashMap};
+pub struct T {
     depth: i32,
     target_x: i32,
     target_y: i32,
-    memo_erosion_level: HashMap<(i32, i32), i32>,
+    // Filled lazily while the search explores the cave.
+    memo_erosion_level: RefCell<HashMap<(i32, i32), i32>>,
 }
 
 #[derive(Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash, Debug)]
@@ -18,7 +17,7 @@ struct N {
 }
 
 impl T {
-    fn geologic_index(&mut self, x: i32, y: i32) -> i32 {
+    fn geologic_index(&self, x: i32, y: i32) -> i32 {
         if (x == 0 && y == 0) || (x == self.target_x && y == self.target_y) {
             0
         } else if y == 0 {
@@ -30,42 +29,60 @@ impl T {
         }
     }
 
-    fn erosion_level(&mut self, x: i32, y: i32) -> i32 {
-        if self.memo_erosion_level.contains_key(&(x, y)) {
-            *self.memo_erosion_level.get(&(x, y)).unwrap()
-        } else {
-            let r = (self.geologic_index(x, y) + self.depth) % 20183;
-            self.memo_erosion_level.insert((x, y), r);
-            r
+    fn erosion_level(&self, x: i32, y: i32) -> i32 {
+        if let Some(&r) = self.memo_erosion_level.borrow().get(&(x, y)) {
+            return r;
         }
+        let r = (self.geologic_index(x, y) + self.depth) % 20183;
+        self.memo_erosion_level.borrow_mut().insert((x, y), r);
+        r
     }
 
     // type: 0 rocky, 1 wet, 2 narrow
     // risk level: 0 for rocky, 1 for wet, 2 for narrow
-    fn soil(&mut self, x: i32, y: i32) -> i32 {
+    fn soil(&self, x: i32, y: i32) -> i32 {
         self.erosion_level(x, y) % 3
     }
 
-    fn is_valid(&mut self, n: &N) -> bool {
+    fn is_valid(&self, n: &N) -> bool {
         let soil = self.soil(n.x, n.y);
         (soil == 0 && (n.climbing_gear || n.torch))
             || (soil == 1 && (!n.torch))
             || (soil == 2 && (!n.climbing_gear))
     }
+}
+
+fn risk_level(t: &T) -> i64 {
+    let mut level = 0i64;
+    for x in 0..=t.target_x {
+        for y in 0..=t.target_y {
+            level += t.soil(x, y) as i64;
+        }
+    }
+
+    level
+}
 
-    fn neighbors(&mut self, n: N) -> Vec<(i64, N)> {
+impl graph::Neighbours<N, u64> for T {
+    fn neighbours<K>(&self, n: &K) -> Vec<(N, u64)>
+    where
+        N: std::borrow::Borrow<K>,
+        K: ToOwned<Owned = N> + Clone,
+    {
+        let n = n.to_owned();
         let mut acc = Vec::new();
         for (dx, dy) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
             let x = n.x + dx;
             let y = n.y + dy;
 
+            // The other directions may still be in the cave.
             if x < 0 || y < 0 {
-                break;
+                continue;
             }
 
             let m = N { x, y, ..n };
             if self.is_valid(&m) {
-                acc.push((1, m));
+                acc.push((m, 1));
             }
         }
 
@@ -75,90 +92,69 @@ impl T {
                     && (torch != n.torch || climbing_gear != n.climbing_gear)
                 {
                     acc.push((
-                        7,
                         N {
                             torch,
                             climbing_gear,
                             ..n
                         },
+                        7,
                     ))
                 }
             }
         }
 
-        acc.into_iter().filter(|(_, n)| self.is_valid(n)).collect()
+        acc.into_iter().filter(|(n, _)| self.is_valid(n)).collect()
     }
-}
 
-fn risk_level(t: &mut T) -> i64 {
-    let mut level = 0i64;
-    for x in 0..=t.target_x {
-        for y in 0..=t.target_y {
-            level += t.soil(x, y) as i64;
-        }
+    fn weight(&self, dt: &u64) -> u64 {
+        *dt
     }
-
-    level
 }
 
 // Part 2 boils down to computing the path of minimum weight in the graph where
 // nodes are a pair of a position and a tool, and edges are transitionning tools
 // or moving positions.
-fn distance(t: &mut T) -> i64 {
-    let mut distance = HashMap::new();
-    let mut heap = BinaryHeap::new();
-
+fn distance(t: &T) -> u64 {
     let source = N {
         x: 0,
         y: 0,
         torch: true,
         climbing_gear: false,
     };
+    let is_target = |u: &N| u.x == t.target_x && u.y == t.target_y && u.torch;
+    graph::dijkstra(t, &source, is_target).unwrap().cost
+}
 
-    use std::cmp::Reverse;
-
-    heap.push(Reverse((0, source)));
-    distance.insert(source, 0);
-
-    while let Some(Reverse((time, u))) = heap.pop() {
-        if u.x == t.target_x && u.y == t.target_y && u.torch {
-            return time;
-        };
-
-        // We have found a better way
-        if time > distance[&u] {
-            continue;
-        };
+pub struct Day;
+
+impl Solution for Day {
+    type Input = T;
+
+    fn parse(&self, input: &str) -> Result<T, String> {
+        let lines: Vec<_> = input.lines().collect();
+        let depth = lines[0].strip_prefix("depth: ").unwrap().parse().unwrap();
+        let target_pos: Vec<_> = lines[1]
+            .strip_prefix("target: ")
+            .unwrap()
+            .split(',')
+            .collect();
+        let target_x = target_pos[0].parse().unwrap();
+        let target_y = target_pos[1].parse().unwrap();
+        Ok(T {
+            depth,
+            target_x,
+            target_y,
+            memo_erosion_level: RefCell::new(HashMap::new()),
+        })
+    }
 
-        for (dt, v) in t.neighbors(u) {
-            if time + dt < *distance.get(&v).unwrap_or(&i64::MAX) {
-                heap.push(Reverse((time + dt, v)));
-                distance.insert(v, time + dt);
-            }
-        }
+    fn part1(&self, t: &T) -> Answer {
+        risk_level(t).into()
     }
-    panic!()
-}
 
-pub fn run(filename: &str) {
-    let contents = std::fs::read_to_string(filename).unwrap();
-    let lines: Vec<_> = contents.lines().collect();
-    let depth = lines[0].strip_prefix("depth: ").unwrap().parse().unwrap();
-    let target_pos: Vec<_> = lines[1]
-        .strip_prefix("target: ")
-        .unwrap()
-        .split(',')
-        .collect();
-    let target_x = target_pos[0].parse().unwrap();
-    let target_y = target_pos[1].parse().unwrap();
-    let mut t = T {
-        depth,
-        target_x,
-        target_y,
-        memo_erosion_level: HashMap::new(),
-    };
-    println!("{}", risk_level(&mut t));
-    println!("{}", distance(&mut t))
+    fn part2(&self, t: &T) -> Answer {
+        distance(t).into()
+    }
 }
 
 #[cfg(test)]
@@ -167,11 +163,11 @@ mod tests {
 
     #[test]
     fn examples() {
-        let mut E = T {
+        let E = T {
             depth: 510,
             target_x: 10,
             target_y: 10,
-            memo_erosion_level: HashMap::new(),
+            memo_erosion_level: RefCell::new(HashMap::new()),
         };
         assert_eq!(E.geologic_index(0, 0), 0);
         assert_eq!(E.erosion_level(0, 0), 510);
@@ -185,14 +181,39 @@ mod tests {
         assert_eq!(E.erosion_level(10, 10), 510);
     }
 
+    #[test]
+    fn test_neighbours() {
+        use graph::Neighbours;
+        let t = T {
+            depth: 510,
+            target_x: 10,
+            target_y: 10,
+            memo_erosion_level: RefCell::new(HashMap::new()),
+        };
+        let n = N {
+            x: 0,
+            y: 0,
+            torch: false,
+            climbing_gear: true,
+        };
+        let mut moves: Vec<(i32, i32)> = t
+            .neighbours(&n)
+            .iter()
+            .filter(|(_, weight)| *weight == 1)
+            .map(|(m, _)| (m.x, m.y))
+            .collect();
+        moves.sort();
+        assert_eq!(moves, vec![(0, 1), (1, 0)]);
+    }
+
     #[test]
     fn part2() {
-        let mut E = T {
+        let E = T {
             depth: 510,
             target_x: 10,
             target_y: 10,
-            memo_erosion_level: HashMap::new(),
+            memo_erosion_level: RefCell::new(HashMap::new()),
         };
-        assert_eq!(distance(&mut E), 45);
+        assert_eq!(distance(&E), 45);
     }
 }
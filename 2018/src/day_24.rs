@@ -1,3 +1,4 @@
+use aoc::{Answer, Solution};
 use std::{
     cell::Ref,
     collections::{HashMap, HashSet},
@@ -48,7 +49,7 @@ impl Group {
 }
 
 #[derive(Debug, Clone)]
-struct T {
+pub struct T {
     immune_system: Vec<Group>,
     infection: Vec<Group>,
 }
@@ -294,12 +295,22 @@ fn part2(t: &T) -> i32 {
     panic!()
 }
 
-pub fn run(filename: &str) {
-    let contents = std::fs::read_to_string(filename).unwrap();
-    let t = parse(&contents);
-    println!("{:?}", t);
-    println!("{}", t.clone().remaining_army_size());
-    println!("{}", part2(&t));
+pub struct Day;
+
+impl Solution for Day {
+    type Input = T;
+
+    fn parse(&self, input: &str) -> Result<T, String> {
+        Ok(parse(input))
+    }
+
+    fn part1(&self, t: &T) -> Answer {
+        t.clone().remaining_army_size().into()
+    }
+
+    fn part2(&self, t: &T) -> Answer {
+        part2(t).into()
+    }
 }
 
 #[cfg(test)]
@@ -1,65 +1,14 @@
-use std::collections::{HashMap, HashSet};
+use aoc::{Answer, Solution};
+use graph::UnionFind;
 
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
-struct Position {
+pub struct Position {
     x: i32,
     y: i32,
     z: i32,
     t: i32,
 }
 
-struct UnionFind<T> {
-    parent: HashMap<T, T>,
-    rank: HashMap<T, u32>,
-}
-
-impl<T> UnionFind<T>
-where
-    T: std::hash::Hash + std::cmp::Eq + Clone,
-{
-    fn new() -> Self {
-        Self {
-            parent: HashMap::new(),
-            rank: HashMap::new(),
-        }
-    }
-
-    fn add(&mut self, x: T) {
-        if !self.parent.contains_key(&x) {
-            self.parent.insert(x.clone(), x.clone());
-            self.rank.insert(x.clone(), 0);
-        }
-    }
-
-    fn find(&mut self, x: T) -> T {
-        let y = self.parent.get(&x).cloned().unwrap();
-
-        if y != x {
-            let z = self.find(y);
-            self.parent.insert(x, z.clone());
-            z
-        } else {
-            x
-        }
-    }
-
-    fn merge(&mut self, x: T, y: T) {
-        let x = self.find(x);
-        let y = self.find(y);
-
-        if x == y {
-        } else {
-            let rx = self.rank.get(&x).unwrap();
-            let ry = self.rank.get(&y).unwrap();
-            let (x, y) = if rx < ry { (y, x) } else { (x, y) };
-            self.parent.insert(y.clone(), x.clone());
-            if rx == ry {
-                self.rank.insert(x, rx + 1);
-            }
-        }
-    }
-}
-
 fn parse(s: &str) -> Vec<Position> {
     s.lines()
         .map(|line| {
@@ -79,29 +28,34 @@ fn distance(a: &Position, b: &Position) -> i32 {
 }
 
 fn part1(positions: &[Position]) -> usize {
-    let mut constellations: UnionFind<Position> = UnionFind::new();
-
-    'outer: for i in 0..positions.len() {
-        constellations.add(positions[i]);
+    let mut constellations = UnionFind::new(positions.len());
+    for i in 0..positions.len() {
         for j in 0..i {
             if distance(&positions[i], &positions[j]) <= 3 {
-                constellations.merge(positions[i], positions[j]);
+                constellations.union(i, j);
             }
         }
     }
+    constellations.count()
+}
 
-    let mut classes = HashSet::new();
-    for i in 0..positions.len() {
-        let root = constellations.find(positions[i]);
-        classes.insert(root);
+pub struct Day;
+
+impl Solution for Day {
+    type Input = Vec<Position>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Position>, String> {
+        Ok(parse(input))
     }
-    classes.len()
-}
 
-pub fn run(filename: &str) {
-    let content = std::fs::read_to_string(filename).unwrap();
-    let points = parse(&content);
-    println!("{}", part1(&points));
+    fn part1(&self, points: &Vec<Position>) -> Answer {
+        part1(points).into()
+    }
+
+    // There is no second part on the last day.
+    fn part2(&self, _: &Vec<Position>) -> Answer {
+        Answer::Nothing
+    }
 }
 
 #[cfg(test)]
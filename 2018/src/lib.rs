@@ -0,0 +1,65 @@
+// The solutions of Advent of Code 2018, run through the `aoc` command line.
+
+#[macro_use]
+extern crate prettytable;
+extern crate nom;
+use aoc::Registry;
+
+mod asm;
+
+mod day_01;
+mod day_02;
+mod day_03;
+mod day_04;
+mod day_05;
+mod day_06;
+mod day_07;
+mod day_08;
+mod day_09;
+mod day_10;
+mod day_11;
+mod day_12;
+mod day_13;
+mod day_14;
+mod day_15;
+mod day_16;
+mod day_17;
+mod day_18;
+mod day_19;
+mod day_20;
+mod day_21;
+mod day_22;
+mod day_23;
+mod day_24;
+mod day_25;
+
+const YEAR: u32 = 2018;
+
+// All the solutions of this year.
+pub fn register(registry: &mut Registry) {
+    registry.add(YEAR, 1, day_01::Day);
+    registry.add(YEAR, 2, day_02::Day);
+    registry.add(YEAR, 3, day_03::Day);
+    registry.add(YEAR, 4, day_04::Day);
+    registry.add(YEAR, 5, day_05::Day);
+    registry.add(YEAR, 6, day_06::Day);
+    registry.add(YEAR, 7, day_07::Day);
+    registry.add(YEAR, 8, day_08::Day);
+    registry.add(YEAR, 9, day_09::Day);
+    registry.add(YEAR, 10, day_10::Day);
+    registry.add(YEAR, 11, day_11::Day);
+    registry.add(YEAR, 12, day_12::Day);
+    registry.add(YEAR, 13, day_13::Day);
+    registry.add(YEAR, 14, day_14::Day);
+    registry.add(YEAR, 15, day_15::Day);
+    registry.add(YEAR, 16, day_16::Day);
+    registry.add(YEAR, 17, day_17::Day);
+    registry.add(YEAR, 18, day_18::Day);
+    registry.add(YEAR, 19, day_19::Day);
+    registry.add(YEAR, 20, day_20::Day);
+    registry.add(YEAR, 21, day_21::Day);
+    registry.add(YEAR, 22, day_22::Day);
+    registry.add(YEAR, 23, day_23::Day);
+    registry.add(YEAR, 24, day_24::Day);
+    registry.add(YEAR, 25, day_25::Day);
+}
@@ -1,3 +0,0 @@
-pub mod direction;
-pub mod vector2;
-pub mod vector3;
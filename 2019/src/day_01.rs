@@ -1,3 +1,5 @@
+use aoc::{Answer, Solution};
+
 fn fuel(mass: u64) -> u64 {
     if mass <= 6 {
         0
@@ -7,25 +9,40 @@ fn fuel(mass: u64) -> u64 {
     }
 }
 
-pub fn run(filename: &str) {
-    let contents = std::fs::read_to_string(filename).unwrap();
-    let modules: Vec<_> = contents
-        .split('\n')
-        .map(|s| s.parse::<u64>().unwrap())
-        .collect();
-
-    let mut part1 = 0;
-    for mass in modules.iter() {
-        let fuel = mass / 3 - 2;
-        part1 += fuel;
+pub struct Day;
+
+impl Solution for Day {
+    // The masses of the modules.
+    type Input = Vec<u64>;
+
+    fn parse(&self, input: &str) -> Result<Vec<u64>, String> {
+        input
+            .lines()
+            .map(|s| s.parse::<u64>().map_err(|e| format!("{}: {}", s, e)))
+            .collect()
+    }
+
+    fn part1(&self, modules: &Vec<u64>) -> Answer {
+        let mut part1 = 0;
+        for mass in modules.iter() {
+            let fuel = mass / 3 - 2;
+            part1 += fuel;
+        }
+        part1.into()
+    }
+
+    fn part2(&self, modules: &Vec<u64>) -> Answer {
+        let mut part2 = 0;
+        for &mass in modules.iter() {
+            part2 += fuel(mass);
+        }
+        part2.into()
     }
-    println!("{}", part1);
 
-    let mut part2 = 0;
-    for &mass in modules.iter() {
-        part2 += fuel(mass);
+    aoc::examples! {
+        "1969" => part1: 654, part2: 966;
+        "100756" => part1: 33583, part2: 50346;
     }
-    println!("{}", part2)
 }
 
 #[cfg(test)]
@@ -1,26 +1,36 @@
 use super::intcode;
+use aoc::{Answer, Solution};
 
-pub fn run(filename: &str) {
-    let program = intcode::read_intcode_program(&filename);
-    // part 1
-
-    let mut vm = intcode::T::new(&program);
-    vm.program[1] = 12;
-    vm.program[2] = 2;
-    intcode::execute(&mut vm);
-    println!("{}", vm.program[0]);
-
-    // part 2
-    for noun in 0..100 {
-        for verb in 0..100 {
-            let mut vm = intcode::T::new(&program);
-            vm.program[1] = noun;
-            vm.program[2] = verb;
-            intcode::execute(&mut vm);
-            if vm.program[0] == 19690720 {
-                println!("{}", 100 * noun + verb)
+pub struct Day;
+
+impl Solution for Day {
+    type Input = intcode::Program;
+
+    fn parse(&self, input: &str) -> Result<intcode::Program, String> {
+        intcode::parse(input)
+    }
+
+    fn part1(&self, program: &intcode::Program) -> Answer {
+        let mut vm = intcode::T::new(program);
+        vm.program[1] = 12;
+        vm.program[2] = 2;
+        intcode::execute(&mut vm);
+        vm.program[0].into()
+    }
+
+    fn part2(&self, program: &intcode::Program) -> Answer {
+        for noun in 0..100 {
+            for verb in 0..100 {
+                let mut vm = intcode::T::new(program);
+                vm.program[1] = noun;
+                vm.program[2] = verb;
+                intcode::execute(&mut vm);
+                if vm.program[0] == 19690720 {
+                    return (100 * noun + verb).into();
+                }
             }
         }
+        panic!("No noun and verb produce 19690720")
     }
 }
 
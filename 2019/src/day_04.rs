@@ -1,4 +1,4 @@
-use std::time::Instant;
+use aoc::{Answer, Solution};
 
 // In this problem, we consider passwords which are 6 digits decimal numbers.
 // We need to do two things with those numbers: iter over a range, and select the digits of an individual number. We could use two different representations: a) represent the numbers as vectors of digits (which makes the indexing operation trivial, at the expanse of having to implement the range iteration) or iter over an integer range, and convert integers in the given range to vectors of digits. We chose the later here.
@@ -133,48 +133,35 @@ fn check_digit_groups(n: &[u8]) -> bool {
     digits.iter().any(|&tally| tally == 2)
 }
 
-pub fn run(filename: &str) {
-    let contents = std::fs::read_to_string(filename).unwrap();
+pub struct Day;
 
-    let range: Vec<_> = contents.split('-').collect();
-    let low: u32 = range[0].parse().unwrap();
-    let high: u32 = range[1].parse().unwrap();
+impl Solution for Day {
+    // The range of passwords, inclusive.
+    type Input = (u32, u32);
 
-    let now = Instant::now();
-
-    let mut part1 = 0;
-    let mut part2 = 0;
-    for i in low..high + 1 {
-        let n = v1::create(i);
-        if check_password1(&n) {
-            part1 += 1;
-
-            if check_digit_groups(&n) {
-                part2 += 1;
-            }
-        }
+    fn parse(&self, input: &str) -> Result<(u32, u32), String> {
+        let (low, high) = input
+            .trim()
+            .split_once('-')
+            .ok_or(format!("Expected a range LOW-HIGH, got '{}'", input.trim()))?;
+        let low = low.parse().map_err(|e| format!("{}: {}", low, e))?;
+        let high = high.parse().map_err(|e| format!("{}: {}", high, e))?;
+        Ok((low, high))
     }
-    println!("{}", part1);
-    println!("{}", part2);
-
-    println!("v1 : {:?}", now.elapsed());
-
-    let now = Instant::now();
-    let mut part1 = 0;
-    let mut part2 = 0;
-    for n in v2::range(&v1::create(low), &v1::create(high)) {
-        if check_password1(&n) {
-            part1 += 1;
 
-            if check_digit_groups(&n) {
-                part2 += 1;
-            }
-        }
+    fn part1(&self, &(low, high): &(u32, u32)) -> Answer {
+        v2::range(&v1::create(low), &v1::create(high))
+            .filter(|n| check_password1(n))
+            .count()
+            .into()
     }
-    println!("{}", part1);
-    println!("{}", part2);
 
-    println!("v2 : {:?}", now.elapsed());
+    fn part2(&self, &(low, high): &(u32, u32)) -> Answer {
+        v2::range(&v1::create(low), &v1::create(high))
+            .filter(|n| check_password1(n) && check_digit_groups(n))
+            .count()
+            .into()
+    }
 }
 
 #[cfg(test)]
@@ -188,4 +175,11 @@ mod tests {
         let n = v1::create(123444);
         assert_eq!(check_digit_groups(&n), false)
     }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Day.parse("123456-654321\n"), Ok((123456, 654321)));
+        assert!(Day.parse("123456").is_err());
+        assert!(Day.parse("123456-x").is_err());
+    }
 }
@@ -1,21 +1,29 @@
 use super::intcode;
+use aoc::{Answer, Solution};
 
-pub fn run(filename: &str) {
-    let program = intcode::read_intcode_program(&filename);
+pub struct Day;
 
-    // part 1
-    let mut vm = intcode::T::new(&program);
-    vm.push(1);
-    intcode::execute(&mut vm);
-    for o in vm.get_outputs().iter() {
-        println!("OUTPUT: {}", o)
+impl Solution for Day {
+    type Input = intcode::Program;
+
+    fn parse(&self, input: &str) -> Result<intcode::Program, String> {
+        intcode::parse(input)
+    }
+
+    // The outputs are the results of the tests, which should be 0, followed by the diagnostic
+    // code.
+    fn part1(&self, program: &intcode::Program) -> Answer {
+        let mut vm = intcode::T::new(program);
+        vm.push(1);
+        let outputs = vm.get_outputs();
+        let (code, tests) = outputs.split_last().unwrap();
+        assert!(tests.iter().all(|&t| t == 0), "Failed tests {:?}", tests);
+        (*code).into()
     }
 
-    // part 2
-    let mut vm = intcode::T::new(&program);
-    vm.push(5);
-    intcode::execute(&mut vm);
-    for o in vm.get_output().iter() {
-        println!("OUTPUT: {}", o)
+    fn part2(&self, program: &intcode::Program) -> Answer {
+        let mut vm = intcode::T::new(program);
+        vm.push(5);
+        vm.get_output().unwrap().into()
     }
 }
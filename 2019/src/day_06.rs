@@ -1,3 +1,4 @@
+use aoc::{Answer, Solution};
 use std::collections::HashMap;
 
 type T = HashMap<String, Vec<String>>;
@@ -84,11 +85,23 @@ fn parse(s: &str) -> (T, HashMap<String, String>) {
     (orbits, parents)
 }
 
-pub fn run(filename: &str) {
-    let contents = std::fs::read_to_string(filename).unwrap();
-    let (orbits, parents) = parse(&contents);
-    println!("{}", indirect(&orbits, "COM", 1));
-    println!("{}", distance(&parents, "YOU", "SAN") - 2);
+pub struct Day;
+
+impl Solution for Day {
+    // The objects in orbit around each object, and the object that each object orbits.
+    type Input = (T, HashMap<String, String>);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, String> {
+        Ok(parse(input.trim_end()))
+    }
+
+    fn part1(&self, (orbits, _): &Self::Input) -> Answer {
+        indirect(orbits, "COM", 1).into()
+    }
+
+    fn part2(&self, (_, parents): &Self::Input) -> Answer {
+        (distance(parents, "YOU", "SAN") - 2).into()
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,5 @@
 use crate::intcode;
+use aoc::{Answer, Solution};
 
 fn compute_output_signal(program: &Vec<i64>, phase_settings: &Vec<i64>) -> i64 {
     let mut amp_a = intcode::T::new(program);
@@ -89,7 +90,6 @@ where
     output
 }
 
-#[allow(dead_code)]
 fn best_signal_without_feedback(program: &Vec<i64>) -> Option<i64> {
     let permutations = generate_permutations(&vec![0, 1, 2, 3, 4]);
 
@@ -101,7 +101,6 @@ fn best_signal_without_feedback(program: &Vec<i64>) -> Option<i64> {
     best_signal
 }
 
-#[allow(dead_code)]
 fn best_signal_with_feedback(program: &Vec<i64>) -> Option<i64> {
     let permutations = generate_permutations(&vec![5, 6, 7, 8, 9]);
 
@@ -113,24 +112,22 @@ fn best_signal_with_feedback(program: &Vec<i64>) -> Option<i64> {
     best_signal
 }
 
-pub fn run(filename: &str) {
-    let program = intcode::read_intcode_program(&filename);
-    let permutations = generate_permutations(&vec![0, 1, 2, 3, 4]);
+pub struct Day;
 
-    let best_signal = permutations
-        .iter()
-        .map(|perm| compute_output_signal(&program, &perm))
-        .max();
-    println!("{:?}", best_signal);
+impl Solution for Day {
+    type Input = intcode::Program;
 
-    let permutations = generate_permutations(&vec![5, 6, 7, 8, 9]);
+    fn parse(&self, input: &str) -> Result<intcode::Program, String> {
+        intcode::parse(input)
+    }
 
-    let best_signal = permutations
-        .iter()
-        .map(|perm| compute_output_signal_with_feedback(&program, &perm))
-        .max();
+    fn part1(&self, program: &intcode::Program) -> Answer {
+        best_signal_without_feedback(program).unwrap().into()
+    }
 
-    println!("{:?}", best_signal);
+    fn part2(&self, program: &intcode::Program) -> Answer {
+        best_signal_with_feedback(program).unwrap().into()
+    }
 }
 
 #[cfg(test)]
@@ -138,9 +135,10 @@ mod tests {
     use super::*;
     #[test]
     fn part2_example1() {
-        let program = intcode::from_string(
+        let program = intcode::parse(
             "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5",
-        );
+        )
+        .unwrap();
         assert_eq!(best_signal_with_feedback(&program), Some(139629729));
     }
 }
@@ -1,3 +1,6 @@
+use aoc::{Answer, Solution};
+use geometry::{ocr, Grid, Vector2};
+
 const WIDTH: usize = 25;
 const HEIGHT: usize = 6;
 const LAYERS: usize = 100; //   img.len() / (WIDTH * HEIGHT);
@@ -37,25 +40,34 @@ fn render_pixel(img: &[u8], x: usize, y: usize, layer: usize) -> char {
     }
 }
 
-fn print(img: &[u8]) {
-    let mut acc = String::new();
-    for y in 0..HEIGHT {
-        for x in 0..WIDTH {
-            acc.push(render_pixel(img, x, y, 0))
-        }
-        acc.push('\n');
-    }
-    println!("{}", acc);
+fn decode(img: &[u8]) -> Grid<bool> {
+    Grid::from_fn(WIDTH, HEIGHT, |p: Vector2<usize>| {
+        render_pixel(img, p.x, p.y, 0) == 'X'
+    })
 }
 
-pub fn run(filename: &str) {
-    let content: String = std::fs::read_to_string(filename).unwrap();
-    let img = content.as_bytes();
-    let layer = (0..LAYERS)
-        .min_by_key(|&layer| digits(img, layer, 0))
-        .unwrap();
+pub struct Day;
 
-    println!("{}", digits(img, layer, 1) * digits(img, layer, 2));
+impl Solution for Day {
+    type Input = Vec<u8>;
+
+    fn parse(&self, input: &str) -> Result<Vec<u8>, String> {
+        Ok(input.trim_end().as_bytes().to_vec())
+    }
 
-    print(img);
+    fn part1(&self, img: &Vec<u8>) -> Answer {
+        let layer = (0..LAYERS)
+            .min_by_key(|&layer| digits(img, layer, 0))
+            .unwrap();
+        (digits(img, layer, 1) * digits(img, layer, 2)).into()
+    }
+
+    fn part2(&self, img: &Vec<u8>) -> Answer {
+        let image = decode(img);
+        match ocr::recognize(&image) {
+            Ok(message) => message.into(),
+            // The picture is given as is, to be read by a human.
+            Err(_) => Answer::grid(&image.render(|&b| if b { 'X' } else { ' ' })),
+        }
+    }
 }
@@ -1,16 +1,28 @@
 use crate::intcode;
+use aoc::{Answer, Solution};
 
-pub fn run(filename: &str) {
-    let program = intcode::read_intcode_program(&filename);
-    let mut vm = intcode::T::new(&program);
-    vm.push(1);
-    let output = vm.get_outputs();
+pub struct Day;
 
-    println!("{:?}", output);
+impl Solution for Day {
+    type Input = intcode::Program;
 
-    let mut vm = intcode::T::new(&program);
-    vm.push(2);
-    let output = vm.get_outputs();
+    fn parse(&self, input: &str) -> Result<intcode::Program, String> {
+        intcode::parse(input)
+    }
 
-    println!("{:?}", output);
+    // In test mode, the program outputs the opcodes that are not implemented correctly, and
+    // then the keycode.
+    fn part1(&self, program: &intcode::Program) -> Answer {
+        let mut vm = intcode::T::new(program);
+        vm.push(1);
+        let output = vm.get_outputs();
+        assert_eq!(output.len(), 1, "Faulty opcodes: {:?}", output);
+        output[0].into()
+    }
+
+    fn part2(&self, program: &intcode::Program) -> Answer {
+        let mut vm = intcode::T::new(program);
+        vm.push(2);
+        vm.get_outputs()[0].into()
+    }
 }
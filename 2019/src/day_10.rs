@@ -1,5 +1,7 @@
+use aoc::{Answer, Solution};
+
 #[derive(Debug, PartialEq, Copy, Clone)]
-struct Point {
+pub struct Point {
     x: i64,
     y: i64,
 }
@@ -150,21 +152,34 @@ fn part2(asteroids: &[Point], center: &Point, nth: usize) -> Point {
     }
 }
 
-pub fn run(filename: &str) {
-    let contents = std::fs::read_to_string(filename).unwrap();
+pub struct Day;
 
-    let (asteroids, _) = parse(&contents);
-    println!("{}", asteroids.len());
+impl Solution for Day {
+    type Input = Vec<Point>;
 
-    let (location, max) = asteroids
-        .iter()
-        .map(|location| (location, number_of_asteroids_in_sight(&asteroids, location)))
-        .max_by_key(|&(_, c)| c)
-        .unwrap();
-    println!("{:?}", max);
+    fn parse(&self, input: &str) -> Result<Vec<Point>, String> {
+        let (asteroids, _) = parse(input);
+        Ok(asteroids)
+    }
 
-    let last = part2(&asteroids, location, 200);
-    println!("{:?}", last);
+    fn part1(&self, asteroids: &Vec<Point>) -> Answer {
+        asteroids
+            .iter()
+            .map(|location| number_of_asteroids_in_sight(asteroids, location))
+            .max()
+            .unwrap()
+            .into()
+    }
+
+    // The 200th asteroid to be vaporized from the best location.
+    fn part2(&self, asteroids: &Vec<Point>) -> Answer {
+        let location = asteroids
+            .iter()
+            .max_by_key(|location| number_of_asteroids_in_sight(asteroids, location))
+            .unwrap();
+        let last = part2(asteroids, location, 200);
+        (last.x * 100 + last.y).into()
+    }
 }
 
 #[cfg(test)]
@@ -1,40 +1,32 @@
 use crate::intcode;
-use std::collections::HashSet;
+use aoc::{Answer, Solution};
+use geometry::{ocr, Direction, SparseGrid, Vector2};
 
 #[derive(Clone)]
 struct T {
-    x: i32,
-    y: i32,
-    dir: i32,
-    white: HashSet<(i32, i32)>,
-    painted: HashSet<(i32, i32)>,
+    pos: Vector2<i32>,
+    dir: Direction,
+    // Painted panels, `true` for white ones.
+    hull: SparseGrid<bool, i32>,
 }
 
 // Execute the painting instructions
 fn paint(t: &T, program: &intcode::Program) -> T {
     let mut vm = intcode::T::new(program);
     let mut t = t.clone();
-    let dirs = vec![(0, 1), (1, 0), (0, -1), (-1, 0)];
 
     while !vm.is_halted() {
-        let input = if t.white.contains(&(t.x, t.y)) { 1 } else { 0 };
+        let input = if t.hull[t.pos] { 1 } else { 0 };
         vm.push(input);
         let paint = vm.get_output().unwrap(); // 0 means paint black, 1 means paint white
         let turn = vm.get_output().unwrap(); // 0 means turn left 90 degrees, 1 means turn right 90 degrees.
-        t.painted.insert((t.x, t.y));
-        if paint == 0 {
-            t.white.remove(&(t.x, t.y));
+        t.hull.insert(t.pos, paint != 0);
+        t.dir = if turn == 0 {
+            t.dir.turn_left()
         } else {
-            t.white.insert((t.x, t.y));
+            t.dir.turn_right()
         };
-        if turn == 0 {
-            t.dir -= 1
-        } else {
-            t.dir += 1
-        };
-        let (dx, dy) = dirs[t.dir.rem_euclid(4) as usize];
-        t.x += dx;
-        t.y += dy;
+        t.pos += t.dir.to_vector();
     }
     t
 }
@@ -42,68 +34,38 @@ fn paint(t: &T, program: &intcode::Program) -> T {
 impl T {
     fn new() -> T {
         T {
-            x: 0,
-            y: 0,
-            dir: 0,
-            white: HashSet::new(),
-            painted: HashSet::new(),
+            pos: Vector2::new(0, 0),
+            dir: Direction::North,
+            hull: SparseGrid::new(false),
         }
     }
 }
 
-// Column major matrix
-fn matrix<T>(width: usize, height: usize, elt: T) -> Vec<Vec<T>>
-where
-    T: Copy,
-{
-    let mut t = vec![];
-    for _i in 0..width + 1 {
-        let mut v = vec![];
-        v.resize(height + 1, elt);
-        t.push(v)
-    }
-    t
-}
+pub struct Day;
 
-// TODO: move to mini bitmap library
-fn raster(pts: HashSet<(i32, i32)>) -> Vec<Vec<bool>> {
-    let minx = pts.iter().map(|(x, _)| x).min().unwrap();
-    let maxx = pts.iter().map(|(x, _)| x).max().unwrap();
-    let miny = pts.iter().map(|(_, y)| y).min().unwrap();
-    let maxy = pts.iter().map(|(_, y)| y).max().unwrap();
+impl Solution for Day {
+    type Input = intcode::Program;
 
-    let width = (maxx - minx) as usize;
-    let height = (maxy - miny) as usize;
-    let mut m = matrix(width, height, false);
+    fn parse(&self, input: &str) -> Result<intcode::Program, String> {
+        intcode::parse(input)
+    }
 
-    for (x, y) in pts.iter() {
-        m[(x - minx) as usize][(y - miny) as usize] = true
+    // The number of panels painted at least once.
+    fn part1(&self, program: &intcode::Program) -> Answer {
+        let t = paint(&T::new(), program);
+        t.hull.len().into()
     }
-    m
-}
 
-// TODO: move to mini bitmap library
-fn print(m: &[Vec<bool>]) {
-    for line in m.iter() {
-        for &c in line.iter() {
-            if c {
-                print!("X")
-            } else {
-                print!(" ")
-            }
+    // The registration identifier, painted when starting on a white panel.
+    fn part2(&self, program: &intcode::Program) -> Answer {
+        let mut t = T::new();
+        t.hull.insert(Vector2::new(0, 0), true);
+        let t = paint(&t, program);
+        let (_, image) = t.hull.to_grid();
+        match ocr::recognize(&image) {
+            Ok(message) => message.into(),
+            // The picture is given as is, to be read by a human.
+            Err(_) => Answer::grid(&image.render(|&b| if b { 'X' } else { ' ' })),
         }
-        println!()
     }
 }
-
-pub fn run(filename: &str) {
-    let program = intcode::read_intcode_program(&filename);
-    let t = T::new();
-    let part1 = paint(&t, &program);
-    println!("{}", part1.painted.len());
-
-    let mut t = T::new();
-    t.white.insert((0, 0));
-    let part2 = paint(&t, &program);
-    print(&raster(part2.white))
-}
@@ -1,4 +1,6 @@
-use crate::vector3::*;
+use aoc::{Answer, Solution};
+use geometry::Vector3;
+use graph::{cycle, Cycle};
 
 #[derive(Debug)]
 struct T {
@@ -46,21 +48,18 @@ fn apply_gravity(state: &mut [T]) {
     }
 
     for i in 0..delta.len() {
-        state[i].velocity = state[i].velocity + delta[i]
+        state[i].velocity += delta[i]
     }
 }
 
 fn apply_velocity(state: &mut [T]) {
     for t in state.iter_mut() {
-        t.position = t.position + t.velocity
+        t.position += t.velocity
     }
 }
 
-fn norm1(t: &Vector3<i64>) -> i64 {
-    t.x.abs() + t.y.abs() + t.z.abs()
-}
 fn energy(t: &T) -> i64 {
-    norm1(&t.position) * norm1(&t.velocity)
+    t.position.norm1() * t.velocity.norm1()
 }
 
 fn simulate(positions: &[Vector3<i64>], steps: usize) -> Vec<T> {
@@ -105,19 +104,8 @@ fn step_1d(state: &[T1; 4]) -> [T1; 4] {
 
 // Returns (n,m) where m is the index of the first state that matches a previous state (n)
 fn find_period_1d(state: &[T1; 4]) -> (usize, usize) {
-    use std::collections::HashMap;
-
-    let mut state = *state;
-    let mut i: usize = 0;
-    let mut seen = HashMap::new();
-    loop {
-        match seen.get(&state) {
-            None => seen.insert(state, i),
-            Some(&previous) => return (previous, i),
-        };
-        state = step_1d(&state);
-        i += 1;
-    }
+    let Cycle { start, period } = cycle::brent(state, step_1d);
+    (start, start + period)
 }
 
 fn project(state: &[T], dimension: usize) -> [T1; 4] {
@@ -142,44 +130,83 @@ fn find_period_3d(state: &[T]) -> Vec<(usize, usize)> {
     periods
 }
 
-pub fn run() {
-    let positions: Vec<Vector3<i64>> = vec![
-        Vector3::new(13, -13, -2),
-        Vector3::new(16, 2, -15),
-        Vector3::new(7, -18, -12),
-        Vector3::new(-3, -8, -8),
-    ];
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
 
-    let state = simulate(&positions, 1000);
+fn lcm(a: usize, b: usize) -> usize {
+    a / gcd(a, b) * b
+}
 
-    println!("{}", total_energy(&state));
+const EXAMPLE: &str = "<x=-1, y=0, z=2>
+<x=2, y=-10, z=-7>
+<x=4, y=-8, z=8>
+<x=3, y=5, z=-1>";
+
+pub struct Day;
+
+impl Solution for Day {
+    // The initial positions of the moons.
+    type Input = Vec<Vector3<i64>>;
+
+    // One moon per line, e.g. `<x=-1, y=0, z=2>`.
+    fn parse(&self, input: &str) -> Result<Vec<Vector3<i64>>, String> {
+        let positions = input
+            .lines()
+            .map(|line| {
+                scan_fmt::scan_fmt!(line, "<x={d}, y={d}, z={d}>", i64, i64, i64)
+                    .map(|(x, y, z)| Vector3::new(x, y, z))
+                    .map_err(|e| format!("{}: {}", line, e))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        if positions.len() != 4 {
+            return Err(format!("Expected 4 moons, got {}", positions.len()));
+        }
+        Ok(positions)
+    }
 
-    // Part 2: we can make the observation that the accelation depends only on
-    // the ordering on 3 axis of the 4 bodies. Also, there is no interaction
-    // between the three axis: we could compute the periodicity on each axis,
-    // and find the lowest common multiple of the periodicity on each axis.
+    fn part1(&self, positions: &Vec<Vector3<i64>>) -> Answer {
+        let state = simulate(positions, 1000);
+        total_energy(&state).into()
+    }
 
-    let periods = find_period_3d(&state);
+    // We can make the observation that the accelation depends only on the ordering on 3 axis
+    // of the 4 bodies. Also, there is no interaction between the three axis: we could compute
+    // the periodicity on each axis, and find the lowest common multiple of the periodicity on
+    // each axis.
+    //
+    // It turns out that the first repeating state on a given axis is the projection of the
+    // initial state along this axis. This simplifies the problem quite a bit, since we "just"
+    // need to compute the lcm of the periods involved to figure out the period of the whole
+    // system.
+    fn part2(&self, positions: &Vec<Vector3<i64>>) -> Answer {
+        let state = simulate(positions, 0);
+        let periods = find_period_3d(&state);
+        assert!(periods.iter().all(|&(start, _)| start == 0));
+        periods
+            .iter()
+            .map(|&(start, end)| end - start)
+            .fold(1, lcm)
+            .into()
+    }
 
-    // It turns out that the first repeating state on a given axis is the
-    // projection of the initial state along this axis. This simplifies the
-    // problem quite a bit, since we "just" need to compute the lcm of the
-    // periods involved to figure out the period of the whole system. Here, I
-    // simply used wolfram alpha to compute the lcm
-    println!("{:?}", periods);
+    aoc::examples! {
+        EXAMPLE => part2: 2772;
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+
     #[test]
     fn test_example_part1() {
-        let positions = vec![
-            Vector3::new(-1, 0, 2),
-            Vector3::new(2, -10, -7),
-            Vector3::new(4, -8, 8),
-            Vector3::new(3, 5, -1),
-        ];
+        let positions = Day.parse(EXAMPLE).unwrap();
+        assert_eq!(positions[1], Vector3::new(2, -10, -7));
         let state = simulate(&positions, 10);
         assert_eq!(total_energy(&state), 179);
     }
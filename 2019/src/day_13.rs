@@ -1,4 +1,5 @@
 use crate::intcode;
+use aoc::{Answer, Solution};
 use crossterm::event::{read, Event};
 use crossterm::queue;
 use crossterm::{
@@ -6,11 +7,12 @@ use crossterm::{
     style::{self, Stylize},
     // terminal, ExecutableCommand, QueueableCommand, Result,
 };
-use std::collections::HashMap;
+use geometry::image::{Frames, Rgb};
+use geometry::{SparseGrid, Vector2};
 use std::io::{stdout, Write};
 
-fn part1(program: &[i64]) {
-    let mut vm = intcode::T::new(&program);
+fn part1(program: &[i64]) -> usize {
+    let mut vm = intcode::T::new(program);
     intcode::execute(&mut vm);
     let mut part1 = 0;
     let mut o = vm.get_outputs();
@@ -23,10 +25,10 @@ fn part1(program: &[i64]) {
             part1 += 1
         };
     }
-    println!("{} {}", part1, vm.steps())
+    part1
 }
 
-fn draw(state: &HashMap<(i64, i64), i64>, score: i64) {
+fn draw(state: &SparseGrid<i64>, score: i64) {
     let mut stdout = stdout();
     queue!(
         stdout,
@@ -40,8 +42,8 @@ fn draw(state: &HashMap<(i64, i64), i64>, score: i64) {
         style::PrintStyledContent((format!("{}", score)).red())
     )
     .unwrap();
-    for (&(x, y), tile) in state.iter() {
-        queue!(stdout, cursor::MoveTo(x as u16, y as u16)).unwrap();
+    for (pos, tile) in state.iter() {
+        queue!(stdout, cursor::MoveTo(pos.x as u16, pos.y as u16)).unwrap();
         match tile {
             0 => queue!(stdout, style::PrintStyledContent("█".black())).unwrap(),
             1 => queue!(stdout, style::PrintStyledContent("█".red())).unwrap(),
@@ -54,6 +56,17 @@ fn draw(state: &HashMap<(i64, i64), i64>, score: i64) {
     stdout.flush().unwrap();
 }
 
+fn tile_to_rgb(tile: &i64) -> Rgb {
+    match tile {
+        0 => Rgb::BLACK,
+        1 => Rgb::RED,
+        2 => Rgb::BLUE,
+        3 => Rgb::GREEN,
+        4 => Rgb::YELLOW,
+        _ => panic!("Unknown tile {}", tile),
+    }
+}
+
 // output instructions specify the x position (distance from the left), y
 // position (distance from the top), and tile id. The tile id is interpreted
 // as follows: 0 is an empty tile. No game object appears in this tile. 1 is
@@ -61,20 +74,30 @@ fn draw(state: &HashMap<(i64, i64), i64>, score: i64) {
 // can be broken by the ball. 3 is a horizontal paddle tile. The paddle is
 // indestructible. 4 is a ball tile. The ball moves diagonally and bounces
 // off objects.
-fn part2(program: &[i64]) {
+//
+// The paddle follows the ball, unless `interactive` is set: then the game is drawn in the
+// terminal and the paddle is moved with the arrow keys (`z` saves the game, `x` restores it, and
+// `a` switches back to autoplay).
+fn part2(program: &[i64], interactive: bool) -> i64 {
     // Enable free play mode
     let mut program = program.to_vec();
     program[0] = 2;
     let mut vm = intcode::T::new(&program);
-    let mut state = HashMap::new();
+    let mut state = SparseGrid::new(0);
+    // One frame each time the ball moves.
+    let mut frames = Frames::from_env("2019_day_13", 8);
     let mut score = 0;
     let mut save = Box::new(None);
     let mut paddle_x = 0;
     let mut ball_x = 0;
-    let mut auto = false;
-    crossterm::terminal::enable_raw_mode().unwrap();
+    let mut auto = !interactive;
+    if interactive {
+        crossterm::terminal::enable_raw_mode().unwrap();
+    }
     while !vm.is_halted() || 0 < vm.outputs() {
-        draw(&state, score);
+        if interactive {
+            draw(&state, score);
+        }
         if 3 <= vm.outputs() {
             let x = vm.get_output().unwrap();
             let y = vm.get_output().unwrap();
@@ -82,12 +105,16 @@ fn part2(program: &[i64]) {
             if x == -1 && y == 0 {
                 score = tile;
             } else {
-                state.insert((x, y), tile);
+                state.insert(Vector2::new(x, y), tile);
                 if tile == 3 {
                     paddle_x = x
                 };
                 if tile == 4 {
-                    ball_x = x
+                    ball_x = x;
+                    if let Some(frames) = &mut frames {
+                        let (_, grid) = state.to_grid();
+                        frames.write(&grid, tile_to_rgb).unwrap();
+                    }
                 };
             };
         } else if vm.is_blocked_on_input() {
@@ -128,12 +155,29 @@ fn part2(program: &[i64]) {
             }
         }
     }
-    println!("Score: {} (steps: {})", score, vm.steps())
+    if interactive {
+        crossterm::terminal::disable_raw_mode().unwrap();
+    }
+    score
 }
 
-pub fn run(filename: &str) {
-    let program = intcode::read_intcode_program(&filename);
+pub struct Day;
+
+impl Solution for Day {
+    type Input = intcode::Program;
+
+    fn parse(&self, input: &str) -> Result<intcode::Program, String> {
+        intcode::parse(input)
+    }
 
-    part1(&program);
-    part2(&program);
+    // The number of block tiles.
+    fn part1(&self, program: &intcode::Program) -> Answer {
+        part1(program).into()
+    }
+
+    // The score once all the blocks are broken. Set `AOC_INTERACTIVE` to play the game.
+    fn part2(&self, program: &intcode::Program) -> Answer {
+        let interactive = std::env::var_os("AOC_INTERACTIVE").is_some();
+        part2(program, interactive).into()
+    }
 }
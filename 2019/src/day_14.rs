@@ -1,3 +1,4 @@
+use aoc::{Answer, Solution};
 use nom::IResult;
 use std::collections::HashMap;
 
@@ -28,7 +29,7 @@ use std::collections::HashMap;
 // Are there cycles in the reactions?
 
 #[derive(Debug, Clone)]
-struct Reaction {
+pub struct Reaction {
     lhs: Vec<(i64, String)>,
     rhs: (i64, String),
 }
@@ -114,7 +115,6 @@ fn fuel_produced(index: &HashMap<String, Reaction>, ore: i64) -> i64 {
     let mut ocl = ore_cost(&index, l);
     let mut ocr = ore_cost(&index, r);
     loop {
-        println!("{} {} -- {} {} ({})", l, ocl, r, ocr, (r - l));
         assert!(ocl < ore);
         assert!(ore <= ocr);
         if l == r - 1 {
@@ -134,12 +134,23 @@ fn fuel_produced(index: &HashMap<String, Reaction>, ore: i64) -> i64 {
     }
 }
 
-pub fn run(filename: &str) {
-    let contents = std::fs::read_to_string(filename).unwrap();
-    let (_, reactions) = parse(&contents).unwrap();
-    let index = index(&reactions);
-    println!("{}", ore_cost(&index, 1));
-    println!("{}", fuel_produced(&index, 1_000_000_000_000));
+pub struct Day;
+
+impl Solution for Day {
+    type Input = HashMap<String, Reaction>;
+
+    fn parse(&self, input: &str) -> Result<HashMap<String, Reaction>, String> {
+        let (_, reactions) = parse(input).map_err(|e| e.to_string())?;
+        Ok(index(&reactions))
+    }
+
+    fn part1(&self, index: &HashMap<String, Reaction>) -> Answer {
+        ore_cost(index, 1).into()
+    }
+
+    fn part2(&self, index: &HashMap<String, Reaction>) -> Answer {
+        fuel_produced(index, 1_000_000_000_000).into()
+    }
 }
 
 #[cfg(test)]
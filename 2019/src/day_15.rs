@@ -7,7 +7,8 @@
 //    "small". We could decide to change the exploration strategy once we have
 //    found the oxygen generator, but this feels more complicated.
 use crate::intcode;
-use crate::vector2::Vector2;
+use aoc::{Answer, Solution};
+use geometry::{SparseGrid, Vector2};
 use lazy_static::lazy_static;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -49,7 +50,8 @@ enum Cell {
 
 #[derive(Debug)]
 struct T {
-    world: HashMap<Vector2<i64>, Cell>,
+    // `None` for cells that have not been explored yet.
+    world: SparseGrid<Option<Cell>>,
     to_visit: Vec<Vector2<i64>>,
     pos: Vector2<i64>,
     oxygen: Option<Vector2<i64>>,
@@ -59,7 +61,7 @@ struct T {
 impl T {
     fn new() -> T {
         T {
-            world: HashMap::new(),
+            world: SparseGrid::new(None),
             to_visit: DIRS.iter().map(|(_, d)| d.clone()).collect(),
             pos: Vector2::new(0, 0),
             oxygen: None,
@@ -69,10 +71,10 @@ impl T {
 
     // It might be the case that cells are present in to_visit multiple times.
     fn move_to(&mut self, pos: Vector2<i64>) {
-        if self.world.insert(pos, Cell::Tile).is_none() {
+        if self.world.insert(pos, Some(Cell::Tile)).is_none() {
             for (_, dir) in DIRS.iter() {
                 let next = pos + *dir;
-                if !self.world.contains_key(&next) {
+                if !self.world.contains(next) {
                     self.to_visit.push(next)
                 }
             }
@@ -81,7 +83,7 @@ impl T {
     }
 
     fn wall(&mut self, pos: Vector2<i64>) {
-        self.world.insert(pos, Cell::Wall);
+        self.world.insert(pos, Some(Cell::Wall));
         self.instructions.clear()
     }
 
@@ -90,9 +92,12 @@ impl T {
         // Filter out the elements that we have visited in the meantime if any
         // (this is not needed, with the current exploration strategy)
         let world = &self.world;
-        self.to_visit.retain(|p| !world.contains_key(p));
+        self.to_visit.retain(|&p| !world.contains(p));
         if self.instructions.is_empty() {
-            let tgt = self.to_visit.iter().min_by_key(|p| p.norm1(&self.pos))?;
+            let tgt = self
+                .to_visit
+                .iter()
+                .min_by_key(|p| p.manhattan(&self.pos))?;
             self.instructions = self.shortest_path(&self.pos, tgt).unwrap()
         };
         self.instructions.pop()
@@ -116,14 +121,9 @@ impl T {
                 return Some(path);
             };
             for (dir, dirv) in DIRS.iter() {
-                match self.world.get(&(v + *dirv)) {
-                    None | Some(Cell::Tile) => {
-                        if visited.insert(v + *dirv) {
-                            q.push_back(v + *dirv);
-                            parent.insert(v + *dirv, (v, *dir));
-                        }
-                    }
-                    _ => {}
+                if self.world[v + *dirv] != Some(Cell::Wall) && visited.insert(v + *dirv) {
+                    q.push_back(v + *dirv);
+                    parent.insert(v + *dirv, (v, *dir));
                 }
             }
         }
@@ -142,7 +142,7 @@ fn flood_fill(t: &T) -> i64 {
     todo.push_back((t.oxygen.unwrap(), 0));
     while !todo.is_empty() {
         let (e, d) = todo.pop_front().unwrap();
-        if !visited.contains(&e) && *t.world.get(&e).unwrap() == Cell::Tile {
+        if !visited.contains(&e) && t.world[e] == Some(Cell::Tile) {
             visited.insert(e);
             for (_, dir) in DIRS.iter() {
                 todo.push_back((e + *dir, d + 1))
@@ -158,9 +158,8 @@ fn flood_fill(t: &T) -> i64 {
 // 0: The repair droid hit a wall. Its position has not changed.
 // 1: The repair droid has moved one step in the requested direction.
 // 2: The repair droid has moved one step in the requested direction; its new position is the location of the oxygen system.
-pub fn run(filename: &str) {
-    let program = intcode::read_intcode_program(filename);
-    let mut vm = intcode::T::new(&program);
+fn explore(program: &[i64]) -> T {
+    let mut vm = intcode::T::new(program);
     let mut state = T::new();
     while !vm.is_halted() && !state.exploration_complete() {
         if let Some(dir) = state.next() {
@@ -178,42 +177,45 @@ pub fn run(filename: &str) {
             }
         }
     }
+    state
+}
 
-    let minx = state.world.keys().map(|v| v.x).min().unwrap();
-    let maxx = state.world.keys().map(|v| v.x).max().unwrap();
-    let miny = state.world.keys().map(|v| v.y).min().unwrap();
-    let maxy = state.world.keys().map(|v| v.y).max().unwrap();
-
-    let oxygen = state.oxygen.unwrap();
-    for y in miny..(maxy + 1) {
-        print!("{:>4} ", y);
-        for x in minx..(maxx + 1) {
-            let p = Vector2::new(x, y);
-            if p == oxygen {
-                print!("O")
-            } else if p == Vector2::new(0, 0) {
-                print!("S")
-            } else {
-                match state.world.get(&p) {
-                    None => print!("+"),
-                    Some(Cell::Wall) => print!("#"),
-                    Some(Cell::Tile) => print!("."),
-                }
-            }
-        }
-        println!("")
+#[allow(dead_code)]
+fn print(state: &T) {
+    let mut map = state.world.map(|cell| match cell {
+        None => '+',
+        Some(Cell::Wall) => '#',
+        Some(Cell::Tile) => '.',
+    });
+    map.insert(state.oxygen.unwrap(), 'O');
+    map.insert(Vector2::new(0, 0), 'S');
+    let miny = map.bounds().unwrap().low[1];
+    for (y, line) in (miny..).zip(map.render(|&c| c).lines()) {
+        println!("{:>4} {}", y, line)
+    }
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input = intcode::Program;
+
+    fn parse(&self, input: &str) -> Result<intcode::Program, String> {
+        intcode::parse(input)
     }
 
-    let path: Vec<_> = state
-        .shortest_path(&Vector2::new(0, 0), &state.oxygen.unwrap())
-        .unwrap();
-
-    // path.reverse();
-    // let mut pos = Vector2::new(0, 0);
-    // for dir in path.iter() {
-    //     pos = pos + dir.to_vector2();
-    //     println!("{:?}", pos);
-    // }
-    println!("{:?}", path.len());
-    println!("{}", flood_fill(&state));
+    // The length of the shortest path to the oxygen system.
+    fn part1(&self, program: &intcode::Program) -> Answer {
+        let state = explore(program);
+        let path: Vec<_> = state
+            .shortest_path(&Vector2::new(0, 0), &state.oxygen.unwrap())
+            .unwrap();
+        path.len().into()
+    }
+
+    // The time it takes for the oxygen to fill the area.
+    fn part2(&self, program: &intcode::Program) -> Answer {
+        let state = explore(program);
+        flood_fill(&state).into()
+    }
 }
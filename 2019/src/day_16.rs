@@ -1,3 +1,5 @@
+use aoc::{Answer, Solution};
+
 fn parse(s: &str) -> Vec<u8> {
     const RADIX: u32 = 10;
     s.chars()
@@ -67,17 +69,29 @@ fn decode(input: &[u8], repetitions: usize, offset: usize, steps: usize) -> Vec<
     signal[0..8].to_vec()
 }
 
-pub fn run(filename: &str) {
-    let contents = std::fs::read_to_string(filename).unwrap();
-    let signal = parse(&contents);
-    let result: Vec<_> = fft(&signal, 100).iter().take(8).cloned().collect();
-    println!("Part 1: {:?}", result);
-
-    // Part 2
-    let offset: usize = contents[0..7].parse().unwrap();
-    println!("Offset: {}", offset);
-    println!("Signal len: {}", signal.len());
-    println!("{:?}", decode(&signal, 10_000, offset, 100));
+fn to_string(digits: &[u8]) -> String {
+    digits.iter().map(|d| d.to_string()).collect()
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input = Vec<u8>;
+
+    fn parse(&self, input: &str) -> Result<Vec<u8>, String> {
+        Ok(parse(input.trim_end()))
+    }
+
+    // The first eight digits after 100 phases.
+    fn part1(&self, signal: &Vec<u8>) -> Answer {
+        to_string(&fft(signal, 100)[0..8]).into()
+    }
+
+    // The message in the real signal, at the offset given by its first seven digits.
+    fn part2(&self, signal: &Vec<u8>) -> Answer {
+        let offset = signal[0..7].iter().fold(0, |acc, &d| 10 * acc + d as usize);
+        to_string(&decode(signal, 10_000, offset, 100)).into()
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,5 @@
 use crate::intcode;
+use aoc::{Answer, Solution};
 use std::collections::HashSet;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -99,17 +100,21 @@ fn supply_input(vm: &mut intcode::T, input: &Input, feed: u8) {
     vm.push_u8(b'\n')
 }
 
-fn print(vm: &mut intcode::T) {
+// Runs the program until it halts. Returns its ASCII output, and the last value that is not
+// ASCII, if any (the amount of dust collected, in part 2).
+fn output(vm: &mut intcode::T) -> (String, Option<i64>) {
     vm.execute();
+    let mut ascii = String::new();
+    let mut value = None;
     while !vm.is_halted() || vm.outputs() != 0 {
         let c = vm.get_output().unwrap();
         if c > 255 {
-            eprintln!("Non ASCII value: {}", c);
+            value = Some(c)
         } else {
-            let c = (c as u8) as char;
-            print!("{}", c)
+            ascii.push((c as u8) as char)
         }
     }
+    (ascii, value)
 }
 
 struct Move {
@@ -329,40 +334,46 @@ fn compress_input(is: &[Instruction]) -> Option<Input> {
     return None;
 }
 
-pub fn run(filename: &str) {
-    let mut program = intcode::read_intcode_program(filename);
-    let mut vm = intcode::T::new(&program);
-    print(&mut vm);
-    let problem = collect_data(&program);
+pub struct Day;
+
+impl Solution for Day {
+    type Input = intcode::Program;
 
-    // part 1
-    let is_intersection = |x, y| {
-        DIRS.iter()
-            .all(|(dx, dy)| problem.scaffold.contains(&(x + dx, y + dy)))
-    };
-    let intersections: Vec<_> = problem
-        .scaffold
-        .iter()
-        .filter(|(x, y)| is_intersection(x, y))
-        .collect();
+    fn parse(&self, input: &str) -> Result<intcode::Program, String> {
+        intcode::parse(input)
+    }
 
-    println!(
-        "{}",
+    // The sum of the alignment parameters of the intersections of the scaffold.
+    fn part1(&self, program: &intcode::Program) -> Answer {
+        let problem = collect_data(program);
+        let is_intersection = |x, y| {
+            DIRS.iter()
+                .all(|(dx, dy)| problem.scaffold.contains(&(x + dx, y + dy)))
+        };
+        let intersections: Vec<_> = problem
+            .scaffold
+            .iter()
+            .filter(|(x, y)| is_intersection(x, y))
+            .collect();
         intersections
             .iter()
             .fold(0 as i64, |acc, (x, y)| acc + (*x as i64) * (*y as i64))
-    );
-
-    // part 2
+            .into()
+    }
 
-    let path = find_path(&problem).unwrap();
-    assert!(is_path_complete(&problem, &path));
-    let input = compress_input(&path).unwrap();
-    println!("{:?}", input);
-    program[0] = 2;
-    let mut vm = intcode::T::new(&program);
-    supply_input(&mut vm, &input, b'n');
-    print(&mut vm);
+    // The dust collected by the robot, once it has visited the whole scaffold.
+    fn part2(&self, program: &intcode::Program) -> Answer {
+        let problem = collect_data(program);
+        let path = find_path(&problem).unwrap();
+        assert!(is_path_complete(&problem, &path));
+        let input = compress_input(&path).unwrap();
+        let mut program = program.clone();
+        program[0] = 2;
+        let mut vm = intcode::T::new(&program);
+        supply_input(&mut vm, &input, b'n');
+        let (_, dust) = output(&mut vm);
+        dust.unwrap().into()
+    }
 }
 
 #[cfg(test)]
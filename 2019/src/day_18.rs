@@ -11,11 +11,11 @@
 //    key) as the tuple of the number of steps, and the set of doors to
 //    traverse,
 
-use crate::vector2::Vector2;
+use aoc::{Answer, Solution};
+use geometry::{Grid, Vector2};
 use lazy_static::lazy_static;
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::collections::VecDeque;
 
 lazy_static! {
     // N, E, S, W. (0,0) is top left of screen.
@@ -23,7 +23,7 @@ lazy_static! {
 }
 
 #[derive(Clone)]
-struct T {
+pub struct T {
     doors: HashMap<Vector2<i64>, u8>,
     keys: HashMap<Vector2<i64>, u8>,
     entrance: Vec<Vector2<i64>>,
@@ -80,113 +80,92 @@ impl Frame {
 
 // BFS of the set of reachable keys given the current state. Return a list of moves [key, cost]
 fn reachable_keys(t: &T, frame: &Frame, robot: usize) -> Vec<(u8, u32)> {
-    let mut queue: VecDeque<(Vector2<i64>, u32)> = std::collections::VecDeque::new();
-    let mut reachable = HashMap::new();
-    let mut visited = HashSet::new();
-    queue.push_back((frame.pos[robot], 0));
-    while !queue.is_empty() {
-        let (pos, distance) = queue.pop_front().unwrap();
-        if visited.contains(&pos) {
-        } else {
-            visited.insert(pos);
-            for next in neighbours(t, pos).iter() {
-                if let Some(key) = t.keys.get(next) {
-                    if reachable.contains_key(key) {
-                    } else if frame.keys.contains(key) {
-                        queue.push_back((*next, distance + 1))
-                    } else {
-                        reachable.insert(key, distance + 1);
-                        // We stop here, to handover to the Dijkstra part of the
-                        // search.
-                    }
-                } else if let Some(door) = t.doors.get(next) {
-                    if frame.keys.contains(door) {
-                        // This is a door we can open
-                        queue.push_back((*next, distance + 1));
-                    }
-                } else {
-                    // This is a regular tile
-                    queue.push_back((*next, distance + 1))
-                }
-            }
+    let start = frame.pos[robot];
+    let is_new_key = |pos: &Vector2<i64>| match t.keys.get(pos) {
+        Some(key) => !frame.keys.contains(key),
+        None => false,
+    };
+    let moves = graph::FromFn(|&pos: &Vector2<i64>| {
+        if pos != start && is_new_key(&pos) {
+            // We stop here, to handover to the Dijkstra part of the search.
+            return vec![];
         }
+        neighbours(t, pos)
+            .into_iter()
+            .filter(|next| match t.doors.get(next) {
+                // This is a door we can open
+                Some(door) => frame.keys.contains(door),
+                None => true,
+            })
+            .map(|next| (next, ()))
+            .collect()
+    });
+    graph::flood_fill(&moves, &start)
+        .into_iter()
+        .filter(|(pos, _)| is_new_key(pos))
+        .map(|(pos, distance)| (t.keys[&pos], distance as u32))
+        .collect()
+}
+
+// A move of a robot to a key, with its cost.
+type Move = (usize, u8, u32);
+
+impl graph::Neighbours<Frame, Move> for T {
+    fn neighbours<K>(&self, frame: &K) -> Vec<(Frame, Move)>
+    where
+        Frame: std::borrow::Borrow<K>,
+        K: ToOwned<Owned = Frame> + Clone,
+    {
+        let frame = frame.to_owned();
+        (0..frame.pos.len())
+            .flat_map(|robot| {
+                reachable_keys(self, &frame, robot)
+                    .into_iter()
+                    .map(move |(k, cost)| (robot, k, cost))
+            })
+            .map(|(robot, k, cost)| {
+                let pos = self.key_position(k).unwrap();
+                let next_frame = frame.add_key(k).set_position(robot, pos);
+                (next_frame, (robot, k, cost))
+            })
+            .collect()
+    }
+
+    fn weight(&self, &(_, _, cost): &Move) -> u64 {
+        cost as u64
     }
-    reachable.iter().map(|(&k, &v)| (*k, v)).collect()
 }
 
 fn dijkstra(t: &T) -> Option<u32> {
-    let mut cost: HashMap<Frame, _> = HashMap::new(); // Absence from the map means cost = + \infnty
-    let mut queue: VecDeque<Frame> = std::collections::VecDeque::new();
     let init_frame = Frame {
         pos: t.entrance.clone(),
         keys: vec![],
     };
-    queue.push_back(init_frame.clone());
-    cost.insert(init_frame, 0);
-    while !queue.is_empty() {
-        let frame = queue.pop_front().unwrap();
-        let children: Vec<_> = (0..frame.pos.len())
-            .flat_map(|i| {
-                reachable_keys(t, &frame, i)
-                    .iter()
-                    .map(|(pos, cost)| (i, *pos, *cost))
-                    .collect::<Vec<_>>()
-            })
-            .collect();
-        for (robot, k, add) in children.iter() {
-            let pos = t.key_position(*k).unwrap();
-            let next_frame = frame.add_key(*k).set_position(*robot, pos);
-            let &c0 = cost.get(&frame).unwrap();
-            let update = match cost.get(&next_frame) {
-                Some(&c1) => c1 > c0 + add,
-                None => true,
-            };
-            if update {
-                queue.push_back(next_frame.clone());
-                cost.insert(next_frame, c0 + add);
-            }
-        }
-    }
-    // println!(
-    //     "{}",
-    //     cost.iter()
-    //         .filter(|(frame, cost)| frame.keys.len() == t.keys.len())
-    //         .count()
-    // );
-    cost.iter()
-        .filter_map(|(frame, cost)| {
-            if frame.keys.len() == t.keys.len() {
-                Some(*cost)
-            } else {
-                None
-            }
-        })
-        .min()
+    let path = graph::dijkstra(t, &init_frame, |frame| frame.keys.len() == t.keys.len())?;
+    Some(path.cost as u32)
 }
 
 fn parse(content: &str) -> T {
     let mut t = T::new();
-    for (y, line) in content.split('\n').enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            let pos = Vector2::new(x as i64, y as i64);
-            if c.is_ascii_lowercase() {
-                let key = (c as u8) - ('a' as u8);
-                t.keys.insert(pos, key);
-                t.tiles.insert(pos);
-            } else if c.is_ascii_uppercase() {
-                let door = (c as u8) - ('A' as u8);
-                t.doors.insert(pos, door);
-                t.tiles.insert(pos);
-            } else if c == '@' {
-                t.entrance[0] = pos;
-                t.tiles.insert(pos);
-            } else if c == '.' {
-                t.tiles.insert(pos);
-            } else if c == '#' {
-                // wall
-            } else {
-                panic!("Unecpected input {}", c)
-            }
+    let grid = Grid::parse(content, Some).unwrap();
+    for (pos, &c) in grid.enumerate::<i64>() {
+        if c.is_ascii_lowercase() {
+            let key = (c as u8) - b'a';
+            t.keys.insert(pos, key);
+            t.tiles.insert(pos);
+        } else if c.is_ascii_uppercase() {
+            let door = (c as u8) - b'A';
+            t.doors.insert(pos, door);
+            t.tiles.insert(pos);
+        } else if c == '@' {
+            t.entrance[0] = pos;
+            t.tiles.insert(pos);
+        } else if c == '.' {
+            t.tiles.insert(pos);
+        } else if c == '#' {
+            // wall
+        } else {
+            panic!("Unecpected input {}", c)
         }
     }
     t
@@ -212,14 +191,23 @@ fn split_vault(t: &T) -> T {
     t
 }
 
-pub fn run(filename: &str) {
-    let content = std::fs::read_to_string(filename).unwrap();
-    let t = parse(&content);
-    println!("{:?}", dijkstra(&t));
+pub struct Day;
 
-    // part 2
-    let t = split_vault(&t);
-    println!("{:?}", dijkstra(&t));
+impl Solution for Day {
+    type Input = T;
+
+    fn parse(&self, input: &str) -> Result<T, String> {
+        Ok(parse(input))
+    }
+
+    fn part1(&self, t: &T) -> Answer {
+        dijkstra(t).unwrap().into()
+    }
+
+    // The vault is split into four, with one robot in each part.
+    fn part2(&self, t: &T) -> Answer {
+        dijkstra(&split_vault(t)).unwrap().into()
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,5 @@
 use crate::intcode;
+use aoc::{Answer, Solution};
 use std::collections::HashMap;
 
 fn part1(program: &intcode::Program) -> u64 {
@@ -9,16 +10,12 @@ fn part1(program: &intcode::Program) -> u64 {
             vm.push(x);
             vm.push(y);
             let out = vm.get_output().unwrap();
-            if out == 0 {
-                print!(".")
-            } else if out == 1 {
-                print!("#");
+            if out == 1 {
                 count += 1
-            } else {
+            } else if out != 0 {
                 panic!("Unexpected intcode output {}", out)
             }
         }
-        println!("")
     }
     count
 }
@@ -145,13 +142,27 @@ fn square_best_fit(program: &intcode::Program, size: u32) -> (u32, u32) {
     (xmin, y - (size - 1))
 }
 
-pub fn run(filename: &str) {
-    let program = intcode::read_intcode_program(filename);
-    // x ->
-    // y v
-    println!("{}", part1(&program));
-    let (x, y) = square_best_fit(&program, 100);
-    println!("{}", x * 10_000 + y);
+pub struct Day;
+
+impl Solution for Day {
+    type Input = intcode::Program;
+
+    fn parse(&self, input: &str) -> Result<intcode::Program, String> {
+        intcode::parse(input)
+    }
+
+    // The number of points affected by the beam in the 50x50 area closest to the emitter.
+    fn part1(&self, program: &intcode::Program) -> Answer {
+        part1(program).into()
+    }
+
+    // The position of the closest 100x100 square that fits in the beam.
+    fn part2(&self, program: &intcode::Program) -> Answer {
+        // x ->
+        // y v
+        let (x, y) = square_best_fit(program, 100);
+        (x * 10_000 + y).into()
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,5 @@
-use crate::vector2::Vector2;
+use aoc::{Answer, Solution};
+use geometry::{Grid, Vector2};
 use lazy_static::lazy_static;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -12,7 +13,7 @@ enum Side {
 }
 
 #[derive(Debug)]
-struct Parsed {
+pub struct Parsed {
     tiles: HashSet<Pos>,
     warps_pl: HashMap<Pos, (String, Side)>,
     warps_lp: HashMap<String, Vec<(Pos, Side)>>,
@@ -76,47 +77,33 @@ fn neighbours_rec(parsed: &Parsed, (pos, level): (Pos, u32)) -> Vec<(Pos, u32)>
     acc
 }
 
-fn bfs<T, F>(neighbours: F, src: T, dst: T) -> Option<u32>
+// Both mazes are undirected (warps can be taken both ways), so that we can search from both ends.
+fn distance<T, F>(neighbours: F, src: T, dst: T) -> Option<u32>
 where
-    F: Fn(T) -> Vec<T>,
-    T: Eq + std::hash::Hash + Copy,
+    F: Fn(&T) -> Vec<T>,
+    T: Eq + std::hash::Hash + Clone,
 {
-    let mut todo = std::collections::VecDeque::new();
-    let mut visited = HashSet::new();
-    todo.push_back((src, 0));
-    while let Some((next, dist)) = todo.pop_front() {
-        if visited.contains(&next) {
-        } else {
-            visited.insert(next);
-            if next == dst {
-                return Some(dist);
-            } else {
-                for n in neighbours(next).iter() {
-                    todo.push_back((*n, dist + 1))
-                }
-            }
-        }
-    }
-    None
+    let maze = graph::FromFn(|pos: &T| neighbours(pos).into_iter().map(|n| (n, ())).collect());
+    let path = graph::bidirectional_bfs(&maze, &src, &dst)?;
+    Some(path.len() as u32 - 1)
 }
 
 // x ->
 // v y
+// Parsing here is a bit more complex than previous problems. Let's do this in three steps:
+// first, process the file line by line, looking for labels and recording the position of the
+// (unique) tile before or after them; second, process the file column by column and do the
+// same; then construct the map.
 fn parse(content: &str) -> Parsed {
     // First, let's transform the string into a 2d matrix.
-    let lines = content.split('\n');
-    let mut image = vec![];
-    for line in lines {
-        image.push(line.chars().collect::<Vec<_>>())
-    }
-    let height = image.len();
-    let width = image[0].len();
+    let image = Grid::parse(content, Some).unwrap();
+    let height = image.height();
+    let width = image.width();
     let mut warps: Vec<(Pos, String, Side)> = vec![];
     for y in 0..height {
         for x in 0..(width - 2) {
-            let a = image[y][x];
-            let b = image[y][x + 1];
-            let c = image[y][x + 2];
+            let row = image.row(y);
+            let (a, b, c) = (row[x], row[x + 1], row[x + 2]);
             let side = if x == 0 || x == width - 3 {
                 Side::Outer
             } else {
@@ -139,9 +126,9 @@ fn parse(content: &str) -> Parsed {
     }
     for y in 0..(height - 2) {
         for x in 0..width {
-            let a = image[y][x];
-            let b = image[y + 1][x];
-            let c = image[y + 2][x];
+            let a = image[Vector2::new(x, y)];
+            let b = image[Vector2::new(x, y + 1)];
+            let c = image[Vector2::new(x, y + 2)];
             let side = if y == 0 || y == height - 3 {
                 Side::Outer
             } else {
@@ -162,14 +149,11 @@ fn parse(content: &str) -> Parsed {
             }
         }
     }
-    let mut tiles = HashSet::new();
-    for y in 0..height {
-        for x in 0..width {
-            if image[y][x] == '.' {
-                tiles.insert(Vector2::new(x as i32, y as i32));
-            }
-        }
-    }
+    let tiles: HashSet<Pos> = image
+        .enumerate()
+        .filter(|&(_, &c)| c == '.')
+        .map(|(pos, _)| pos)
+        .collect();
     let warps_pl: HashMap<Pos, (String, Side)> = warps
         .iter()
         .cloned()
@@ -188,21 +172,29 @@ fn parse(content: &str) -> Parsed {
         warps_lp,
     }
 }
-pub fn run(filename: &str) {
-    // Parsing here is a bit more complex than previous problems. Let's do this
-    // in three steps: first, process the file line by line, looking for labels
-    // and recording the position of the (unique) tile before or after them;
-    // second, process the file column by column and do the same; then construct
-    // the map.
+pub struct Day;
 
-    let contents = std::fs::read_to_string(filename).unwrap();
-    let parsed = parse(&contents);
-    // let t = build(&parsed);
-    let src = parsed.warps_lp.get("AA").unwrap()[0].0;
-    let tgt = parsed.warps_lp.get("ZZ").unwrap()[0].0;
-    println!("{:?}", bfs(|pos| neighbours(&parsed, pos), src, tgt));
-    println!(
-        "{:?}",
-        bfs(|pos| neighbours_rec(&parsed, pos), (src, 0), (tgt, 0))
-    );
+impl Solution for Day {
+    type Input = Parsed;
+
+    fn parse(&self, input: &str) -> Result<Parsed, String> {
+        Ok(parse(input))
+    }
+
+    fn part1(&self, parsed: &Parsed) -> Answer {
+        let src = parsed.warps_lp.get("AA").unwrap()[0].0;
+        let tgt = parsed.warps_lp.get("ZZ").unwrap()[0].0;
+        distance(|&pos| neighbours(parsed, pos), src, tgt)
+            .unwrap()
+            .into()
+    }
+
+    // The maze is recursive: the inner warps go one level down, and the outer ones one level up.
+    fn part2(&self, parsed: &Parsed) -> Answer {
+        let src = parsed.warps_lp.get("AA").unwrap()[0].0;
+        let tgt = parsed.warps_lp.get("ZZ").unwrap()[0].0;
+        distance(|&pos| neighbours_rec(parsed, pos), (src, 0), (tgt, 0))
+            .unwrap()
+            .into()
+    }
 }
@@ -1,6 +1,8 @@
+use aoc::{Answer, Solution};
+
 //
 #[derive(Debug, Clone, Copy)]
-enum Technique {
+pub enum Technique {
     Deal,
     Cut(isize),
     DealWithIncrement(usize),
@@ -247,13 +249,13 @@ impl Transform {
     }
 }
 
-fn part1(techniques: &[Technique]) {
+fn part1(techniques: &[Technique]) -> Option<usize> {
     let mut deck = T::new(10007);
     techniques.iter().for_each(|&t| deck.apply(t));
-    println!("{:?}", deck.find_card(2019));
+    deck.find_card(2019)
 }
 
-fn part1_bis(techniques: &[Technique]) {
+fn part1_bis(techniques: &[Technique]) -> Option<i128> {
     const DECK_SIZE: i128 = 10_007;
     let mut aff = Transform::ident(DECK_SIZE);
     for &t in techniques.iter() {
@@ -263,9 +265,10 @@ fn part1_bis(techniques: &[Technique]) {
             Technique::DealWithIncrement(i) => aff.deal_with_increment(i),
         };
     }
-    println!("{:?}", aff.find_card(2019));
+    aff.find_card(2019)
 }
-fn part2(techniques: &[Technique]) {
+
+fn part2(techniques: &[Technique]) -> i128 {
     const DECK_SIZE: i128 = 119_315_717_514_047;
     const REPETITION_COUNT: i128 = 101_741_582_076_661;
 
@@ -278,15 +281,29 @@ fn part2(techniques: &[Technique]) {
         };
     }
     let acc = acc.pow(REPETITION_COUNT);
-    println!("{}", acc.get(2020))
+    acc.get(2020)
 }
 
-pub fn run(filename: &str) {
-    let content = std::fs::read_to_string(filename).unwrap();
-    let techniques = parse(&content);
-    part1(&techniques);
-    part1_bis(&techniques);
-    part2(&techniques)
+pub struct Day;
+
+impl Solution for Day {
+    type Input = Vec<Technique>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Technique>, String> {
+        Ok(parse(input.trim_end()))
+    }
+
+    // The position of card 2019, computed both by shuffling the deck and with affine
+    // transformations.
+    fn part1(&self, techniques: &Vec<Technique>) -> Answer {
+        let position = part1(techniques).unwrap();
+        assert_eq!(part1_bis(techniques), Some(position as i128));
+        position.into()
+    }
+
+    fn part2(&self, techniques: &Vec<Technique>) -> Answer {
+        part2(techniques).into()
+    }
 }
 
 #[cfg(test)]
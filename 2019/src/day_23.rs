@@ -1,4 +1,5 @@
 use crate::intcode;
+use aoc::{Answer, Solution};
 use std::collections::VecDeque;
 
 struct T {
@@ -93,13 +94,8 @@ fn part1(program: &[i64]) -> i64 {
 fn part2(program: &[i64]) -> i64 {
     let mut state = T::initialize(&program);
     let mut pre = None;
-    let mut cycles = 0;
     loop {
-        cycles += 1;
-        if cycles % 100 == 0 {
-            eprintln!("{}: {:?} {:?}", cycles, pre, state.nat);
-        }
-        // We check for an idle state after trying to send packets, if any. 
+        // We check for an idle state after trying to send packets, if any.
         state.rx();
         state.execute();
         state.tx();
@@ -123,8 +119,22 @@ fn part2(program: &[i64]) -> i64 {
     pre.unwrap()
 }
 
-pub fn run(filename: &str) {
-    let program = intcode::read_intcode_program(filename);
-    println!("{}", part1(&program));
-    println!("{}", part2(&program));
+pub struct Day;
+
+impl Solution for Day {
+    type Input = intcode::Program;
+
+    fn parse(&self, input: &str) -> Result<intcode::Program, String> {
+        intcode::parse(input)
+    }
+
+    // The Y value of the first packet sent to address 255.
+    fn part1(&self, program: &intcode::Program) -> Answer {
+        part1(program).into()
+    }
+
+    // The first Y value delivered twice in a row by the NAT.
+    fn part2(&self, program: &intcode::Program) -> Answer {
+        part2(program).into()
+    }
 }
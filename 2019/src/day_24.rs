@@ -1,9 +1,8 @@
-use lazy_static::lazy_static;
+use aoc::{Answer, Solution};
+use geometry::automaton::{self, Bounded, LiveCells, Recursive, VonNeumann};
+use geometry::Aabb;
 use std::collections::HashSet;
 const N: i8 = 5;
-lazy_static! {
-    static ref DIRS2: Vec<(i8, i8)> = vec![(-1, 0), (1, 0), (0, -1), (0, 1)];
-}
 
 fn parse(s: &str) -> Vec<(i8, i8)> {
     let mut result = Vec::new();
@@ -17,82 +16,49 @@ fn parse(s: &str) -> Vec<(i8, i8)> {
     result
 }
 
-trait Positions<'a> {
-    type Position;
-    fn new() -> Self;
-    fn get(&self, pos: &Self::Position) -> bool;
-    fn add(&mut self, pos: Self::Position);
-    fn neighbours(&self, pos: &Self::Position) -> Vec<Self::Position>;
-    fn positions(&self) -> Box<dyn Iterator<Item = Self::Position> + '_>;
-    fn len(&self) -> usize;
-}
-
-impl<'a> Positions<'a> for u64 {
-    type Position = (i8, i8);
-
-    fn new() -> Self {
-        0
-    }
+// The bugs of a single 5x5 grid, as a bitset.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash)]
+struct Bugs(u64);
 
-    fn len(&self) -> usize {
-        let mut count = 0;
-        for i in 0..N {
-            for j in 0..N {
-                if self.get(&(i, j)) {
-                    count += 1
-                }
-            }
-        }
-        count
+impl LiveCells<[i32; 2]> for Bugs {
+    fn count(&self) -> usize {
+        self.0.count_ones() as usize
     }
 
-    fn get(&self, pos: &Self::Position) -> bool {
-        let (i, j) = *pos;
-        if i < 0 || N <= i || j < 0 || N <= j {
+    fn contains(&self, &[i, j]: &[i32; 2]) -> bool {
+        let n = N as i32;
+        if i < 0 || n <= i || j < 0 || n <= j {
             false
         } else {
-            (*self & (1 << (i * N + j))) != 0
+            (self.0 & (1 << (i * n + j))) != 0
         }
     }
 
-    fn add(&mut self, pos: Self::Position) {
-        let (i, j) = pos;
-        if i < 0 || N <= i || j < 0 || N <= j {
+    fn insert(&mut self, [i, j]: [i32; 2]) {
+        let n = N as i32;
+        if i < 0 || n <= i || j < 0 || n <= j {
             panic!("Invalid bitset operation {} {}", i, j)
         } else {
-            let mask = 1 << (i * N + j);
-            *self |= mask
+            let mask = 1 << (i * n + j);
+            self.0 |= mask
         }
     }
 
-    fn neighbours(&self, pos: &Self::Position) -> Vec<Self::Position> {
-        let mut result = Vec::new();
-        let (i, j) = *pos;
-        for (di, dj) in DIRS2.iter() {
-            let i = i + di;
-            let j = j + dj;
-            if 0 <= i && i < N && 0 <= j && j < N {
-                result.push((i, j))
-            }
-        }
-        result
-    }
-    fn positions(&self) -> Box<dyn Iterator<Item = Self::Position> + '_> {
-        let mut result: Vec<(i8, i8)> = Vec::new();
-        for i in 0..N {
-            for j in 0..N {
-                result.push((i, j))
-            }
-        }
-        Box::new(result.into_iter())
+    fn cells(&self) -> Box<dyn Iterator<Item = [i32; 2]> + '_> {
+        let n = N as i32;
+        Box::new(
+            (0..n)
+                .flat_map(move |i| (0..n).map(move |j| [i, j]))
+                .filter(move |pos| self.contains(pos)),
+        )
     }
 }
 
-fn biodiversity_rating(s: u64) -> u64 {
+fn biodiversity_rating(s: Bugs) -> u64 {
     let mut result = 0;
     for i in 0..N {
         for j in 0..N {
-            if s.get(&(i, j)) {
+            if s.contains(&[i as i32, j as i32]) {
                 result += 1 << (i * N + j)
             }
         }
@@ -100,163 +66,62 @@ fn biodiversity_rating(s: u64) -> u64 {
     result
 }
 
-impl<'a> Positions<'a> for HashSet<(i8, i8, i64)> {
-    type Position = (i8, i8, i64);
-
-    fn positions(&self) -> Box<dyn Iterator<Item = Self::Position> + '_> {
-        Box::new(self.iter().copied())
-    }
-
-    fn new() -> Self {
-        HashSet::new()
-    }
-
-    fn len(&self) -> usize {
-        self.len()
-    }
-
-    fn get(&self, pos: &Self::Position) -> bool {
-        self.contains(pos)
-    }
-
-    fn add(&mut self, pos: Self::Position) {
-        self.insert(pos);
-    }
-
-    fn neighbours(&self, pos: &Self::Position) -> Vec<Self::Position> {
-        // There are many cases to consider. If we are on the outer border of
-        // the grid at a given level, we need to consider the extra neighbours
-        // at level -1. If we are on the next to the center, we need to consider
-        // the extra neighbours at level + 1.
-        let mut result = Vec::new();
-        let mut push = |i: i8, j: i8, l: i64| {
-            if i == 2 && j == 2 {
-            } else {
-                result.push((i, j, l))
-            }
-        };
-        let (i, j, level) = *pos;
-        if i == 0 {
-            push(1, 2, level - 1)
-        };
-        if i == N - 1 {
-            push(3, 2, level - 1)
-        };
-        if j == 0 {
-            push(2, 1, level - 1)
-        };
-        if j == N - 1 {
-            push(2, 3, level - 1)
-        };
-        if i == 1 && j == 2 {
-            // Add top row
-            for x in 0..N {
-                push(0, x, level + 1)
-            }
-        };
-        if i == 3 && j == 2 {
-            // Add bottom row
-            for x in 0..N {
-                push(N - 1, x, level + 1)
-            }
-        };
-        if i == 2 && j == 1 {
-            // Add the left row
-            for x in 0..N {
-                push(x, 0, level + 1)
-            }
-        };
-        if i == 2 && j == 3 {
-            // Add the right row
-            for x in 0..N {
-                push(x, N - 1, level + 1)
-            }
-        };
-        if i + 1 < N {
-            push(i + 1, j, level)
-        };
-        if 0 <= i - 1 {
-            push(i - 1, j, level)
-        };
-        if j + 1 < N {
-            push(i, j + 1, level)
-        };
-        if 0 <= j - 1 {
-            push(i, j - 1, level)
-        };
-
-        result
-    }
+// A bug dies unless there is exactly one bug adjacent to it, and an empty space becomes
+// infested if exactly one or two bugs are adjacent to it.
+fn rule(infested: bool, n: usize) -> bool {
+    n == 1 || (!infested && n == 2)
 }
 
-fn count<'a, T>(state: &T, pos: T::Position) -> usize
-where
-    T: Positions<'a>,
-    T::Position: Eq,
-{
-    state
-        .neighbours(&pos)
+fn part1(positions: &[(i8, i8)]) -> u64 {
+    let mut state = Bugs::default();
+    positions
         .iter()
-        .filter(|n| state.get(n))
-        .count()
+        .for_each(|(i, j)| state.insert([*i as i32, *j as i32]));
+    let grid = Bounded {
+        topology: VonNeumann::<2>,
+        bounds: Aabb::new([0, 0], [N as i32 - 1, N as i32 - 1]),
+    };
+    let history = graph::find_cycle(state, |s| automaton::step(&grid, s, rule));
+    biodiversity_rating(*history.first_repeated())
 }
 
-fn next<'a, T>(state: &T) -> T
-where
-    T: Positions<'a>,
-    T::Position: Eq + std::hash::Hash + Copy,
-{
-    let mut to_consider = HashSet::new();
-    for pos in state.positions() {
-        to_consider.insert(pos);
-        to_consider.extend(state.neighbours(&pos))
-    }
-
-    let mut next = T::new();
-    for &pos in to_consider.iter() {
-        let n = count(state, pos);
-        let infested = state.get(&pos);
-        if n == 1 && infested {
-            next.add(pos)
-        } else if !infested && (n == 1 || n == 2) {
-            next.add(pos)
-        } else {
-        }
+fn part2(positions: &[(i8, i8)], steps: usize) -> usize {
+    let mut state: HashSet<([i32; 2], i32)> = HashSet::new();
+    positions.iter().for_each(|(i, j)| {
+        state.insert(([*i as i32, *j as i32], 0));
+    });
+    let levels = Recursive { size: N as i32 };
+    for _i in 0..steps {
+        state = automaton::step(&levels, &state, rule)
     }
-    next
+    state.len()
 }
 
-fn part1(positions: &[(i8, i8)]) -> u64 {
-    let mut state: u64 = 0;
-    positions.iter().for_each(|(i, j)| state.add((*i, *j)));
-    let mut visited = HashSet::new();
-    loop {
-        if !visited.insert(state) {
-            return biodiversity_rating(state);
-        }
-        state = next(&state);
+pub struct Day;
+
+impl Solution for Day {
+    // The positions of the bugs.
+    type Input = Vec<(i8, i8)>;
+
+    fn parse(&self, input: &str) -> Result<Vec<(i8, i8)>, String> {
+        Ok(parse(input))
     }
-}
 
-fn part2(positions: &[(i8, i8)], steps: usize) -> usize {
-    let mut state: HashSet<(i8, i8, i64)> = HashSet::new();
-    positions.iter().for_each(|(i, j)| state.add((*i, *j, 0)));
-    for _i in 0..steps {
-        state = next(&state)
+    // The biodiversity rating of the first layout that appears twice.
+    fn part1(&self, positions: &Vec<(i8, i8)>) -> Answer {
+        part1(positions).into()
     }
-    state.len()
-}
 
-pub fn run(filename: &str) {
-    let contents = std::fs::read_to_string(filename).unwrap();
-    let positions = parse(&contents);
-    println!("{}", part1(&positions));
-    println!("{}", part2(&positions, 200));
+    // The number of bugs after 200 minutes, on recursive grids.
+    fn part2(&self, positions: &Vec<(i8, i8)>) -> Answer {
+        part2(positions, 200).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use geometry::automaton::Topology;
     #[test]
     fn test_example1() {
         let s = "....#
@@ -281,42 +146,44 @@ mod tests {
 
     #[test]
     fn test_neighbours() {
-        let s = "....#
-#..#.
-#..##
-..#..
-#....";
-        let positions = parse(s);
-        let mut state: HashSet<(i8, i8, i64)> = HashSet::new();
-        positions.iter().for_each(|(i, j)| state.add((*i, *j, 0)));
+        let levels = Recursive { size: N as i32 };
+        let neighbours = |i, j| {
+            let mut result: Vec<_> = levels
+                .neighbours(&([i, j], 0))
+                .into_iter()
+                .map(|([i, j], level)| (i, j, level))
+                .collect();
+            result.sort();
+            result
+        };
         assert_eq!(
-            state.neighbours(&(0, 0, 0)),
-            vec![(1, 2, -1), (2, 1, -1), (1, 0, 0), (0, 1, 0)]
+            neighbours(0, 0),
+            vec![(0, 1, 0), (1, 0, 0), (1, 2, -1), (2, 1, -1)]
         );
         assert_eq!(
-            state.neighbours(&(2, 1, 0)),
+            neighbours(2, 1),
             vec![
                 (0, 0, 1),
                 (1, 0, 1),
+                (1, 1, 0),
+                (2, 0, 0),
                 (2, 0, 1),
                 (3, 0, 1),
-                (4, 0, 1),
                 (3, 1, 0),
-                (1, 1, 0),
-                (2, 0, 0)
+                (4, 0, 1)
             ]
         );
         assert_eq!(
-            state.neighbours(&(2, 3, 0)),
+            neighbours(2, 3),
             vec![
                 (0, 4, 1),
+                (1, 3, 0),
                 (1, 4, 1),
+                (2, 4, 0),
                 (2, 4, 1),
-                (3, 4, 1),
-                (4, 4, 1),
                 (3, 3, 0),
-                (1, 3, 0),
-                (2, 4, 0)
+                (3, 4, 1),
+                (4, 4, 1)
             ]
         );
     }
@@ -1,6 +1,6 @@
-use crate::direction::Direction;
-use crate::graph;
 use crate::intcode;
+use aoc::{Answer, Solution};
+use geometry::Direction;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::io;
@@ -53,7 +53,6 @@ fn parse(s: &str) -> Option<Room> {
 
 fn room(vm: &mut intcode::T) -> Result<Room, String> {
     let buf = vm.get_string();
-    println!("{}", buf);
     match parse(&buf) {
         None => Err(buf),
         Some(room) => Ok(room),
@@ -69,15 +68,10 @@ fn push_direction(vm: &mut intcode::T, dir: Direction) {
     }
 }
 
-fn interactive_input(vm: &mut intcode::T) -> String {
+fn interactive_input() -> Option<String> {
     let stdin = io::stdin();
-    // interactive mode
-    let input = stdin.lock().lines().next();
-    input
-        .expect("No lines in buffer")
-        .expect("Failed to read line")
-        .trim()
-        .to_string()
+    let input = stdin.lock().lines().next()?;
+    Some(input.expect("Failed to read line").trim().to_string())
 }
 
 struct T {
@@ -148,22 +142,14 @@ fn explore(program: &[i64]) -> T {
     while !to_visit.is_empty() {
         let dir = to_visit.pop().unwrap();
         push_direction(&mut vm, dir);
-        match room(&mut vm) {
-            Err(buf) => {
-                println!("{:?}", buf)
-            }
-            Ok(r) => {
-                println!("{:?} -> {:?}", dir, r);
-                if r.name == state.current.name {
-                    println!("ERROR: Could not follow dir {:?}", dir)
-                } else {
-                    if state.rooms.contains_key(&r.name) {
-                    } else {
-                        to_visit.push(dir.opposite());
-                        to_visit.extend(r.directions.iter().filter(|&d| d.opposite() != dir));
-                    }
-                    state.visit(dir, &r)
+        // Rooms that send us back (e.g. the pressure-sensitive floor) are dead ends.
+        if let Ok(r) = room(&mut vm) {
+            if r.name != state.current.name {
+                if !state.rooms.contains_key(&r.name) {
+                    to_visit.push(dir.opposite());
+                    to_visit.extend(r.directions.iter().filter(|&d| d.opposite() != dir));
                 }
+                state.visit(dir, &r)
             }
         }
     }
@@ -195,60 +181,68 @@ where
         .collect()
 }
 
-pub fn run(filename: &str) {
-    let program = intcode::read_intcode_program(filename);
-
-    let mut state = explore(&program);
-    let mut vm = intcode::T::new(&program);
-    let items = vec![
-        ("Navigation", "easter egg"),
-        ("Warp Drive Maintenance", "mug"),
-        ("Storage", "prime number"),
-        ("Stables", "mouse"),
-        ("Corridor", "astronaut ice cream"),
-        ("Sick Bay", "ornament"),
-        ("Gift Wrapping Center", "wreath"),
-        ("Hot Chocolate Fountain", "hypercube"),
-    ];
+// The items that can safely be taken, and where they are.
+const ITEMS: [(&str, &str); 8] = [
+    ("Navigation", "easter egg"),
+    ("Warp Drive Maintenance", "mug"),
+    ("Storage", "prime number"),
+    ("Stables", "mouse"),
+    ("Corridor", "astronaut ice cream"),
+    ("Sick Bay", "ornament"),
+    ("Gift Wrapping Center", "wreath"),
+    ("Hot Chocolate Fountain", "hypercube"),
+];
 
-    for (room_name, item) in items.iter().cloned() {
-        goto(&mut state, &mut vm, &room_name.to_string());
+// Picks up all the items, and tries all the combinations of items on the pressure-sensitive
+// floor until one has the right weight. Returns the password for the main airlock.
+fn password(program: &[i64], interactive: bool) -> i64 {
+    let mut state = explore(program);
+    let mut vm = intcode::T::new(program);
+    for (room_name, item) in ITEMS.iter() {
+        goto(&mut state, &mut vm, room_name);
         vm.push_str(&format!("take {}\n", item));
-        let buf = vm.get_string();
-        println!("{}", buf)
+        vm.get_string();
     }
-    goto(&mut state, &mut vm, &"Security Checkpoint");
-    for (_, item) in items.iter() {
+    goto(&mut state, &mut vm, "Security Checkpoint");
+    for (_, item) in ITEMS.iter() {
         vm.push_str(&format!("drop {}\n", item));
-        let buf = vm.get_string();
-        println!("{}", buf)
+        vm.get_string();
     }
-    let items: Vec<&str> = items.into_iter().map(|(_, item)| item).collect();
-    let combinations: Vec<Vec<&str>> = powerset(&items);
-    for items in combinations.iter() {
+    let items: Vec<&str> = ITEMS.iter().map(|&(_, item)| item).collect();
+    let mut result = None;
+    for items in powerset(&items).iter() {
         for item in items.iter() {
-            println!("$ take {}\n", item);
             vm.push_str(&format!("take {}\n", item));
-            let buf = vm.get_string();
-            println!("{}", buf)
+            vm.get_string();
         }
         push_direction(&mut vm, Direction::North);
         let buf = vm.get_string();
         if !(buf.contains("heavier") || buf.contains("lighter")) {
-            println!("{}", buf);
-            break;
-        } else {
-            for item in items.iter() {
-                println!("$ drop {}\n", item);
-                vm.push_str(&format!("drop {}\n", item));
-                let buf = vm.get_string();
+            if interactive {
                 println!("{}", buf)
             }
+            result = buf
+                .split("typing ")
+                .nth(1)
+                .and_then(|s| s.split_whitespace().next())
+                .and_then(|s| s.parse().ok());
+            break;
+        }
+        for item in items.iter() {
+            vm.push_str(&format!("drop {}\n", item));
+            vm.get_string();
         }
     }
-    loop {
-        println!("$");
-        let s = interactive_input(&mut vm);
+    if interactive {
+        play(&mut state, &mut vm)
+    }
+    result.expect("No combination of items opens the door")
+}
+
+// Lets the player move around, until the end of the input.
+fn play(state: &mut T, vm: &mut intcode::T) {
+    println!("$");
+    while let Some(s) = interactive_input() {
         if s == "rooms" {
             for room in state.rooms.values() {
                 println!("{:?}", room)
@@ -257,19 +251,38 @@ pub fn run(filename: &str) {
             println!("{:?}", state.current);
         } else if s == "items" {
             state.print_items()
-        } else if s.starts_with("goto ") {
-            let target = s.strip_prefix("goto ").unwrap();
-            goto(&mut state, &mut vm, &target.to_string())
+        } else if let Some(target) = s.strip_prefix("goto ") {
+            goto(state, vm, target);
+            println!("{:?}", state.current);
         } else {
             vm.push_str(&s);
             vm.push_u8(b'\n');
-            match room(&mut vm) {
-                Ok(r) => {
-                    state.current = r;
-                }
-                Err(_) => {}
+            let buf = vm.get_string();
+            println!("{}", buf);
+            if let Some(r) = parse(&buf) {
+                state.current = r;
             }
         }
+        println!("$");
+    }
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input = intcode::Program;
+
+    fn parse(&self, input: &str) -> Result<intcode::Program, String> {
+        intcode::parse(input)
+    }
+
+    fn part1(&self, program: &intcode::Program) -> Answer {
+        password(program, std::env::var_os("AOC_INTERACTIVE").is_some()).into()
+    }
+
+    // There is no second part on the last day.
+    fn part2(&self, _: &intcode::Program) -> Answer {
+        Answer::Nothing
     }
 }
 
@@ -1,30 +0,0 @@
-#![allow(dead_code)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
-
-impl Direction {
-    pub fn opposite(&self) -> Direction {
-        match self {
-            Direction::North => Direction::South,
-            Direction::South => Direction::North,
-            Direction::East => Direction::West,
-            Direction::West => Direction::East,
-        }
-    }
-
-    pub fn each() -> impl Iterator<Item = Direction> + Clone {
-        [
-            Direction::North,
-            Direction::South,
-            Direction::West,
-            Direction::East,
-        ]
-        .iter()
-        .cloned()
-    }
-}
@@ -82,7 +82,6 @@ pub struct T {
     output: Vec<i64>,
     pub status: Status,
     relative_base: i64,
-    steps: usize,
 }
 
 pub type Program = Vec<i64>;
@@ -95,14 +94,9 @@ impl T {
             output: vec![],
             status: Status::Continue(0),
             relative_base: 0,
-            steps: 0,
         }
     }
 
-    pub fn steps(&self) -> usize {
-        self.steps
-    }
-
     fn get(&mut self, address: usize) -> i64 {
         if self.program.len() <= address {
             self.program.resize(address + 1, 0);
@@ -302,7 +296,6 @@ impl T {
         };
 
         self.status = status;
-        self.steps += 1;
     }
 }
 
@@ -323,17 +316,15 @@ pub fn execute(vm: &mut T) {
     }
 }
 
-pub fn from_string(program: &str) -> Vec<i64> {
-    let program: Vec<_> = program
-        .split(',')
-        .map(|s| s.parse::<i64>().unwrap())
-        .collect();
+pub fn parse(program: &str) -> Result<Vec<i64>, String> {
     program
-}
-
-pub fn read_intcode_program(filename: &str) -> Vec<i64> {
-    let contents = std::fs::read_to_string(filename).unwrap();
-    from_string(&contents)
+        .trim()
+        .split(',')
+        .map(|s| {
+            s.parse::<i64>()
+                .map_err(|e| format!("Invalid intcode '{}': {}", s, e))
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -1,38 +0,0 @@
-#[derive(Clone, Copy, Debug)]
-pub struct Vector3<T> {
-    pub x: T,
-    pub y: T,
-    pub z: T,
-}
-
-use std::ops::Add;
-impl<T: Add<Output = T>> Add for Vector3<T> {
-    type Output = Self;
-
-    fn add(self, other: Self) -> Self {
-        Self {
-            x: self.x + other.x,
-            y: self.y + other.y,
-            z: self.z + other.z,
-        }
-    }
-}
-
-impl<T> Vector3<T> {
-    pub fn new(x: T, y: T, z: T) -> Vector3<T> {
-        Vector3 { x: x, y: y, z: z }
-    }
-}
-
-use std::ops::Index;
-impl<T> Index<usize> for Vector3<T> {
-    type Output = T;
-    fn index(&self, index: usize) -> &T {
-        match index {
-            0 => &self.x,
-            1 => &self.y,
-            2 => &self.z,
-            _ => panic!("Invalid index"),
-        }
-    }
-}
@@ -1,5 +1,4 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use aoc::{Answer, Solution};
 
 // Assumes that [v] is sorted
 fn sum2(v: &[i32], tgt: i32) -> Option<(usize, usize)> {
@@ -31,26 +30,39 @@ fn sum3(v: &[i32], tgt: i32) -> Option<(usize, usize, usize)> {
     return None;
 }
 
-pub fn run(filename: String) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-    let mut content = Vec::new();
-    for (_index, line) in reader.lines().enumerate() {
-        let line = line.unwrap();
-        let num: i32 = line.parse().unwrap();
-        content.push(num);
+const EXAMPLE: &str = "1721
+979
+366
+299
+675
+1456";
+
+pub struct Day;
+
+impl Solution for Day {
+    // The entries, sorted.
+    type Input = Vec<i32>;
+
+    fn parse(&self, input: &str) -> Result<Vec<i32>, String> {
+        let mut content = input
+            .lines()
+            .map(|line| line.parse().map_err(|e| format!("{}: {}", line, e)))
+            .collect::<Result<Vec<i32>, String>>()?;
+        content.sort_unstable();
+        Ok(content)
+    }
+
+    fn part1(&self, content: &Vec<i32>) -> Answer {
+        let (i, j) = sum2(content, 2020).unwrap();
+        (content[i] * content[j]).into()
+    }
+
+    fn part2(&self, content: &Vec<i32>) -> Answer {
+        let (i, j, k) = sum3(content, 2020).unwrap();
+        (content[i] * content[j] * content[k]).into()
     }
 
-    content.sort();
-    let (i, j) = sum2(&content, 2020).unwrap();
-    println!("sum2:{}", content[i] * content[j]);
-
-    let (i, j, k) = sum3(&content, 2020).unwrap();
-    println!(
-        "sum3:{} {} {} {}",
-        content[i],
-        content[j],
-        content[k],
-        content[i] * content[j] * content[k]
-    );
+    aoc::examples! {
+        EXAMPLE => part1: 514579, part2: 241861950;
+    }
 }
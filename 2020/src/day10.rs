@@ -1,8 +1,6 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use aoc::{Answer, Solution};
 
 fn count_arrangements(v: &[u32]) -> u64 {
-    println!("{:?}", v);
     let delta = |i: usize, j: usize| -> u64 {
         if v[j] - v[i] <= 3 {
             return 1;
@@ -20,31 +18,87 @@ fn count_arrangements(v: &[u32]) -> u64 {
             + arrangements[i - 2] * delta(i - 2, i)
             + arrangements[i - 3] * delta(i - 3, i)
     }
-    println!("{:?}", arrangements);
     return arrangements[v.len() - 1];
 }
 
-pub fn run(filename: String) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
+const SMALL: &str = "16
+10
+15
+5
+1
+11
+7
+19
+6
+12
+4";
 
-    let mut adapters: Vec<u32> = Vec::new();
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let joltage: u32 = line.parse().unwrap();
-        adapters.push(joltage);
+const LARGE: &str = "28
+33
+18
+42
+31
+14
+46
+20
+48
+47
+24
+23
+49
+45
+19
+38
+39
+11
+1
+32
+25
+35
+8
+17
+7
+9
+4
+2
+34
+10
+3";
+
+pub struct Day;
+
+impl Solution for Day {
+    // The joltages of the adapters, of the outlet and of the device, sorted.
+    type Input = Vec<u32>;
+
+    fn parse(&self, input: &str) -> Result<Vec<u32>, String> {
+        let mut adapters = input
+            .lines()
+            .map(|line| line.parse().map_err(|e| format!("{}: {}", line, e)))
+            .collect::<Result<Vec<u32>, String>>()?;
+        adapters.push(0);
+        let builtin: u32 = adapters.iter().max().unwrap() + 3;
+        adapters.push(builtin);
+        adapters.sort_unstable();
+        Ok(adapters)
     }
-    adapters.push(0);
-    let builtin: u32 = adapters.iter().max().unwrap() + 3;
-    adapters.push(builtin);
-
-    adapters.sort();
-    // We know that there exist a solution to the problem using all adapters,
-    // with differences between joltages less or equal than 3
-    let mut diffs: [u32; 4] = [0; 4];
-    for i in 1..adapters.len() {
-        diffs[(adapters[i] - adapters[i - 1]) as usize] += 1;
+
+    fn part1(&self, adapters: &Vec<u32>) -> Answer {
+        // We know that there exist a solution to the problem using all adapters,
+        // with differences between joltages less or equal than 3
+        let mut diffs: [u32; 4] = [0; 4];
+        for i in 1..adapters.len() {
+            diffs[(adapters[i] - adapters[i - 1]) as usize] += 1;
+        }
+        (diffs[1] * diffs[3]).into()
+    }
+
+    fn part2(&self, adapters: &Vec<u32>) -> Answer {
+        count_arrangements(adapters).into()
+    }
+
+    aoc::examples! {
+        SMALL => part1: 35, part2: 8;
+        LARGE => part1: 220, part2: 19208;
     }
-    println!("{}", diffs[1] * diffs[3]);
-    println!("{}", count_arrangements(&adapters));
 }
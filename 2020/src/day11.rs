@@ -1,5 +1,5 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use aoc::{Answer, Solution};
+use geometry::{automaton, Direction8, Grid, Vector2};
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum T {
@@ -8,160 +8,94 @@ pub enum T {
     E, // Empty
 }
 
-fn parse(s: &str) -> Vec<T> {
-    let mut row = Vec::new();
-    for c in s.chars() {
-        if c == '.' {
-            row.push(T::F)
-        } else if c == 'L' {
-            row.push(T::E)
-        } else {
-            panic!("Unexpected input {}", s)
-        }
+type Pos = Vector2<i32>;
+
+fn parse(s: &str) -> Grid<T> {
+    match Grid::parse(s, |c| match c {
+        '.' => Some(T::F),
+        'L' => Some(T::E),
+        _ => None,
+    }) {
+        Ok(grid) => grid,
+        Err(e) => panic!("Unexpected input: {}", e),
     }
-    return row;
 }
 
-fn count(grid: &Vec<Vec<T>>) -> u32 {
-    let mut count = 0;
-    for i in 0..grid.len() {
-        for j in 0..grid[i].len() {
-            if grid[i][j] == T::O {
-                count += 1
-            }
-        }
-    }
-    return count;
+fn count(grid: &Grid<T>) -> usize {
+    grid.iter().filter(|&&t| t == T::O).count()
 }
 
-fn step<F>(grid: &mut Vec<Vec<T>>, count: F, bound: u32) -> bool
+// Returns false once the seats no longer change.
+fn step<F>(grid: &mut Grid<T>, neighbours: F, bound: usize) -> bool
 where
-    F: Fn(&Vec<Vec<T>>, i32, i32) -> u32,
+    F: Fn(&Grid<T>, Pos) -> Vec<Pos>,
 {
-    let mut counts = Vec::new();
-    for i in 0..grid.len() {
-        let mut line = Vec::new();
-        for j in 0..grid[i].len() {
-            line.push(count(grid, i as i32, j as i32));
-        }
-        counts.push(line);
-    }
-
-    let mut changes = 0;
-    for i in 0..grid.len() {
-        for j in 0..grid[i].len() {
-            match grid[i][j] {
-                T::O => {
-                    if bound <= counts[i][j] {
-                        grid[i][j] = T::E;
-                        changes += 1
-                    }
-                }
-                T::E => {
-                    if counts[i][j] == 0 {
-                        grid[i][j] = T::O;
-                        changes += 1
-                    }
-                }
-                _ => {}
-            }
-        }
-    }
-    return changes > 0;
-}
-
-mod part1 {
-    use super::*;
-    fn get(grid: &Vec<Vec<T>>, i: i32, j: i32) -> Option<T> {
-        if 0 <= i && i < grid.len() as i32 {
-            let row = &grid[i as usize];
-            if 0 <= j && j < row.len() as i32 {
-                return Some(row[j as usize]);
-            } else {
-                return None;
-            }
-        } else {
-            return None;
-        }
-    }
-
-    pub fn count(grid: &Vec<Vec<T>>, i: i32, j: i32) -> u32 {
-        let mut n = 0;
-        for i1 in i - 1..i + 2 {
-            for j1 in j - 1..j + 2 {
-                if i1 != i || j1 != j {
-                    match get(grid, i1, j1) {
-                        Some(T::O) => n += 1,
-                        _ => {}
-                    }
-                }
-            }
+    let next = automaton::step_grid(grid, neighbours, |&tile, around| {
+        let occupied = around.iter().filter(|&&&t| t == T::O).count();
+        match tile {
+            T::O if bound <= occupied => T::E,
+            T::E if occupied == 0 => T::O,
+            _ => tile,
         }
-        return n;
-    }
+    });
+    let changed = next != *grid;
+    *grid = next;
+    changed
 }
 
 mod part2 {
     use super::*;
 
-    // Get, performing ray tracing along the vector di,dj
-    fn get(grid: &Vec<Vec<T>>, i: i32, j: i32, di: i32, dj: i32) -> bool {
-        let i = i + di;
-        let j = j + dj;
-        if 0 <= i && i < grid.len() as i32 {
-            let row = &grid[i as usize];
-            if 0 <= j && j < row.len() as i32 {
-                let c = row[j as usize];
-                match c {
-                    T::O => true,
-                    T::E => false,
-                    T::F => get(grid, i, j, di, dj),
-                }
-            } else {
-                false
-            }
-        } else {
-            return false;
+    // The first seat along the vector dir, performing ray tracing
+    fn get(grid: &Grid<T>, pos: Pos, dir: Pos) -> Option<Pos> {
+        let pos = pos + dir;
+        match grid.get(pos) {
+            Some(T::O) | Some(T::E) => Some(pos),
+            None => None,
+            Some(T::F) => get(grid, pos, dir),
         }
     }
 
-    pub fn count(grid: &Vec<Vec<T>>, i: i32, j: i32) -> u32 {
-        let mut n = 0;
-        for di in -1..2 {
-            for dj in -1..2 {
-                if di != 0 || dj != 0 {
-                    if get(grid, i, j, di, dj) {
-                        n += 1
-                    }
-                }
-            }
-        }
-        return n;
+    pub fn visible(grid: &Grid<T>, pos: Pos) -> Vec<Pos> {
+        Direction8::each()
+            .filter_map(|d| get(grid, pos, d.to_vector()))
+            .collect()
     }
 }
 
-pub fn run(filename: String) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
+const EXAMPLE: &str = "L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
 
-    let mut grid = Vec::new();
-    for line in reader.lines() {
-        let line = line.unwrap();
-        grid.push(parse(&line))
+pub struct Day;
+
+impl Solution for Day {
+    type Input = Grid<T>;
+
+    fn parse(&self, input: &str) -> Result<Grid<T>, String> {
+        Ok(parse(input))
     }
-    let mut grid1 = grid.clone();
-    while {
-        let changed = step(&mut grid1, part1::count, 4);
-        changed
-    } {}
 
-    println!("{}", count(&grid1));
+    fn part1(&self, grid: &Grid<T>) -> Answer {
+        let mut grid = grid.clone();
+        while step(&mut grid, automaton::moore, 4) {}
+        count(&grid).into()
+    }
 
-    let mut grid2 = grid.clone();
-    while {
-        let changed = step(&mut grid2, part2::count, 5);
-        changed
-    } {}
+    fn part2(&self, grid: &Grid<T>) -> Answer {
+        let mut grid = grid.clone();
+        while step(&mut grid, part2::visible, 5) {}
+        count(&grid).into()
+    }
 
-    println!("{}", count(&grid2))
+    aoc::examples! {
+        EXAMPLE => part1: 37, part2: 26;
+    }
 }
@@ -1,17 +1,11 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-const NORTH: (i32, i32) = (1, 0);
-const SOUTH: (i32, i32) = (-1, 0);
-const EAST: (i32, i32) = (0, 1);
-const WEST: (i32, i32) = (0, -1);
+use aoc::{Answer, Solution};
+use geometry::{Direction, Vector2};
 
 // In this problem, the ship can face 4 directions. Rotations are a multiple of 90 degrees.
-const DIRS: [(i32, i32); 4] = [NORTH, EAST, SOUTH, WEST];
-
 #[derive(Debug)]
-enum Action {
-    Move((i32, i32), i32),
+pub enum Action {
+    Move(Direction, i32),
+    // Clockwise rotation, in degrees
     Rotate(i32),
     Forward(i32),
 }
@@ -19,75 +13,59 @@ enum Action {
 fn parse(s: &str) -> Action {
     let value: i32 = s[1..].parse().unwrap();
     let action = match &s[0..1] {
-        "N" => Action::Move(NORTH, value),
-        "S" => Action::Move(SOUTH, value),
-        "E" => Action::Move(EAST, value),
-        "W" => Action::Move(WEST, value),
-        "L" => Action::Rotate((-value / 90).rem_euclid(4)),
-        "R" => Action::Rotate((value / 90).rem_euclid(4)),
+        "N" => Action::Move(Direction::North, value),
+        "S" => Action::Move(Direction::South, value),
+        "E" => Action::Move(Direction::East, value),
+        "W" => Action::Move(Direction::West, value),
+        "L" => Action::Rotate(-value),
+        "R" => Action::Rotate(value),
         "F" => Action::Forward(value),
         _ => panic!("Unexpected input {}", s),
     };
     action
 }
-pub fn run(filename: String) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
+pub struct Day;
+
+impl Solution for Day {
+    type Input = Vec<Action>;
 
-    let mut actions = Vec::new();
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let action = parse(&line);
-        actions.push(action);
+    fn parse(&self, input: &str) -> Result<Vec<Action>, String> {
+        Ok(input.lines().map(parse).collect())
     }
 
-    // part 1
-    let mut dir: i32 = 1; // Ship start facing east
-    let mut x: i32 = 0;
-    let mut y: i32 = 0;
-    for action in actions.iter() {
-        match action {
-            Action::Move((dx, dy), k) => {
-                x += k * dx;
-                y += k * dy;
-            }
-            Action::Rotate(i) => dir = (dir + i).rem_euclid(4),
-            Action::Forward(k) => {
-                let (dx, dy) = DIRS[dir as usize];
-                x += k * dx;
-                y += k * dy;
+    fn part1(&self, actions: &Vec<Action>) -> Answer {
+        let mut dir = Direction::East; // Ship start facing east
+        let mut pos: Vector2<i32> = Vector2::new(0, 0);
+        for action in actions.iter() {
+            match action {
+                Action::Move(d, k) => pos += d.to_vector() * *k,
+                Action::Rotate(degrees) => dir = dir.rotate(*degrees),
+                Action::Forward(k) => pos += dir.to_vector() * *k,
             }
         }
+        pos.norm1().into()
     }
 
-    println!("{}", x.abs() + y.abs());
-
-    // part 2
-    let mut x: i32 = 0;
-    let mut y: i32 = 0;
-    let mut wx: i32 = 1;
-    let mut wy: i32 = 10;
-    for action in actions.iter() {
-        match action {
-            Action::Move((dx, dy), k) => {
-                wx += k * dx;
-                wy += k * dy;
-            }
-            Action::Rotate(n) => {
-                for _i in 0..*n {
-                    let wx0 = wx;
-                    let wy0 = wy;
-
-                    wx = -wy0;
-                    wy = wx0
+    // The actions move a waypoint instead, relative to the ship.
+    fn part2(&self, actions: &Vec<Action>) -> Answer {
+        let mut pos: Vector2<i32> = Vector2::new(0, 0);
+        let mut waypoint: Vector2<i32> = Vector2::new(10, -1);
+        for action in actions.iter() {
+            match action {
+                Action::Move(d, k) => waypoint += d.to_vector() * *k,
+                Action::Rotate(degrees) => {
+                    for _i in 0..(degrees / 90).rem_euclid(4) {
+                        // Since the y axis points south, this is a clockwise rotation.
+                        waypoint = waypoint.rotate_ccw()
+                    }
                 }
-            }
-            Action::Forward(k) => {
-                x += k * wx;
-                y += k * wy;
+                Action::Forward(k) => pos += waypoint * *k,
             }
         }
+        pos.norm1().into()
     }
 
-    println!("{}", x.abs() + y.abs());
+    aoc::examples! {
+        "F10\nN3\nF7\nR90\nF11" => part1: 25, part2: 286;
+    }
 }
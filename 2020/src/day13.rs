@@ -1,55 +1,65 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use aoc::{Answer, Solution};
 
-pub fn run(filename: String) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
+pub struct Day;
 
-    let mut lines = reader.lines();
-    let start: u64 = lines.next().unwrap().unwrap().parse().unwrap();
-    let mut bus_ids = {
+impl Solution for Day {
+    // The earliest departure time, and the buses with their offset in the schedule.
+    type Input = (u64, Vec<(u64, u64)>);
+
+    fn parse(&self, input: &str) -> Result<(u64, Vec<(u64, u64)>), String> {
+        let mut lines = input.lines();
+        let start: u64 = lines
+            .next()
+            .ok_or("Missing departure time")?
+            .parse()
+            .map_err(|e| format!("Invalid departure time: {}", e))?;
         let mut bus_ids = Vec::new();
-        let line: String = lines.next().unwrap().unwrap();
-        let entries: Vec<&str> = line.split(",").collect();
-        for (index, bus) in entries.iter().enumerate() {
-            if bus == &"x" {
+        let line = lines.next().ok_or("Missing bus ids")?;
+        for (index, bus) in line.split(',').enumerate() {
+            if bus == "x" {
                 continue;
             } else {
-                let bus_id: u64 = bus.parse().unwrap();
+                let bus_id: u64 = bus.parse().map_err(|e| format!("{}: {}", bus, e))?;
                 bus_ids.push((index as u64, bus_id))
             }
         }
-        bus_ids
-    };
+        Ok((start, bus_ids))
+    }
 
-    // part 1
-    let (_offset, earliest_bus) = bus_ids
-        .iter()
-        .min_by_key(|(_offset, id)| {
-            let q = start / *id;
-            let time_to_wait = (q + 1) * id - start;
-            time_to_wait
-        })
-        .unwrap();
+    fn part1(&self, (start, bus_ids): &(u64, Vec<(u64, u64)>)) -> Answer {
+        let start = *start;
+        let (_offset, earliest_bus) = bus_ids
+            .iter()
+            .min_by_key(|(_offset, id)| {
+                let q = start / *id;
+                (q + 1) * id - start
+            })
+            .unwrap();
 
-    let time_to_wait = (1 + start / earliest_bus) * earliest_bus - start;
-    println!("{}", earliest_bus * time_to_wait);
+        let time_to_wait = (1 + start / earliest_bus) * earliest_bus - start;
+        (earliest_bus * time_to_wait).into()
+    }
 
-    // part 2
     // the input describes some congruences. We want to find the smallest t such that `t + offset_i mod bus_i = 0`.
     // It turns out that the bus_ids that are provided are prime numbers, and we can search for a solution
     // using a sieve (see https://en.wikipedia.org/wiki/Chinese_remainder_theorem).
-
-    bus_ids.sort_by_key(|(_offset, id)| *id);
-    bus_ids.reverse();
-    let (a1, n1) = bus_ids[0];
-    let mut t: u64 = (n1 - a1) % n1;
-    let mut n: u64 = n1;
-    for (offset, id) in bus_ids[1..].iter() {
-        while (t + offset) % id != 0 {
-            t += n;
+    fn part2(&self, (_, bus_ids): &(u64, Vec<(u64, u64)>)) -> Answer {
+        let mut bus_ids = bus_ids.clone();
+        bus_ids.sort_by_key(|(_offset, id)| *id);
+        bus_ids.reverse();
+        let (a1, n1) = bus_ids[0];
+        let mut t: u64 = (n1 - a1) % n1;
+        let mut n: u64 = n1;
+        for (offset, id) in bus_ids[1..].iter() {
+            while (t + offset) % id != 0 {
+                t += n;
+            }
+            n *= *id;
         }
-        n = n * *id;
+        t.into()
+    }
+
+    aoc::examples! {
+        "939\n7,13,x,x,59,x,31,19" => part1: 295, part2: 1068781;
     }
-    println!("{}", t);
 }
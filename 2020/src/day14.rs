@@ -1,9 +1,8 @@
+use aoc::{Answer, Solution};
 use std::collections::HashMap;
 use std::fmt;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 
-struct U36(u64);
+pub struct U36(u64);
 
 impl fmt::Debug for U36 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -12,7 +11,7 @@ impl fmt::Debug for U36 {
 }
 
 #[derive(Debug)]
-enum Instruction {
+pub enum Instruction {
     // Use three masks to avoid some bit fiddling later on
     Mask(U36, U36, U36),
     Mem(u64, u64),
@@ -44,7 +43,7 @@ fn parse(s: &str) -> Instruction {
     panic!("Parsing failure {}", s)
 }
 
-fn part1(prog: &Vec<Instruction>) {
+fn part1(prog: &Vec<Instruction>) -> u64 {
     // The program starts by specifying a bitmask.
     let mut mask1 = 0;
     let mut mask0 = 0;
@@ -64,8 +63,7 @@ fn part1(prog: &Vec<Instruction>) {
         }
     }
 
-    let sum: u64 = mem.into_iter().map(|(_key, value)| value).sum();
-    println!("{}", sum);
+    mem.into_iter().map(|(_key, value)| value).sum()
 }
 
 // Recursive function that iterates through the floating part of the address.
@@ -83,7 +81,7 @@ fn set(mem: &mut HashMap<u64, u64>, addr: u64, maskx: u64, value: u64) {
     }
 }
 
-fn part2(prog: &Vec<Instruction>) {
+fn part2(prog: &Vec<Instruction>) -> u64 {
     // The program starts by specifying a bitmask.
     let mut mask1 = 0;
     let mut maskx = 0;
@@ -101,21 +99,23 @@ fn part2(prog: &Vec<Instruction>) {
         }
     }
 
-    let sum: u64 = mem.into_iter().map(|(_key, value)| value).sum();
-    println!("{}", sum);
+    mem.into_iter().map(|(_key, value)| value).sum()
 }
 
-pub fn run(filename: String) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
+pub struct Day;
 
-    let mut prog = Vec::new();
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let instruction = parse(&line);
-        prog.push(instruction)
+impl Solution for Day {
+    type Input = Vec<Instruction>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Instruction>, String> {
+        Ok(input.lines().map(parse).collect())
+    }
+
+    fn part1(&self, prog: &Vec<Instruction>) -> Answer {
+        part1(prog).into()
     }
 
-    part1(&prog);
-    part2(&prog);
+    fn part2(&self, prog: &Vec<Instruction>) -> Answer {
+        part2(prog).into()
+    }
 }
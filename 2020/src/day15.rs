@@ -1,3 +1,4 @@
+use aoc::{Answer, Solution};
 use std::collections::HashMap;
 
 fn step(state: &mut HashMap<u32, u32>, turn: u32, num: u32) -> u32 {
@@ -30,8 +31,29 @@ fn play(nums: &Vec<u32>, until: u32) -> u32 {
     most_recent_num
 }
 
-pub fn run() {
-    let nums = vec![15, 5, 1, 4, 7, 0];
-    println!("{}", play(&nums, 2020));
-    println!("{}", play(&nums, 30000000));
+pub struct Day;
+
+impl Solution for Day {
+    type Input = Vec<u32>;
+
+    // The starting numbers, separated by commas.
+    fn parse(&self, input: &str) -> Result<Vec<u32>, String> {
+        input
+            .trim()
+            .split(',')
+            .map(|n| n.parse().map_err(|e| format!("{}: {}", n, e)))
+            .collect()
+    }
+
+    fn part1(&self, nums: &Vec<u32>) -> Answer {
+        play(nums, 2020).into()
+    }
+
+    fn part2(&self, nums: &Vec<u32>) -> Answer {
+        play(nums, 30000000).into()
+    }
+
+    aoc::examples! {
+        "0,3,6" => part1: 436, part2: 175594;
+    }
 }
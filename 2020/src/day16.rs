@@ -1,5 +1,4 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use aoc::{Answer, Solution};
 
 fn parse_range(s: &str) -> (u64, u64) {
     if let Ok((low, high)) = scan_fmt!(s, "{d}-{d}", u64, u64) {
@@ -139,29 +138,37 @@ fn part2(fields: &mut Vec<(String, Range, Range)>, tickets: &Vec<Vec<u64>>) -> u
     return result;
 }
 
-pub fn run(filename: String) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-    let mut fields = Vec::new();
-
-    let mut tickets = Vec::new();
-    for line in reader.lines() {
-        let line = line.unwrap();
-        if let Ok((field, range1, range2)) =
-            scan_fmt!(&line, "{[^:]}: {[^ ]} or {[^ ]}", String, String, String)
-        {
-            let range1 = parse_range(&range1);
-            let range2 = parse_range(&range2);
-            fields.push((field, range1, range2))
-        } else if &line == "your ticket:" || &line == "nearby tickets:" || &line == "" {
-            continue;
-        } else {
-            let ticket = parse_ticket(&line);
-            tickets.push(ticket);
+pub struct Day;
+
+impl Solution for Day {
+    // The fields with their valid ranges, and the tickets (starting with ours).
+    type Input = (Vec<(String, Range, Range)>, Vec<Vec<u64>>);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, String> {
+        let mut fields = Vec::new();
+        let mut tickets = Vec::new();
+        for line in input.lines() {
+            if let Ok((field, range1, range2)) =
+                scan_fmt!(line, "{[^:]}: {[^ ]} or {[^ ]}", String, String, String)
+            {
+                let range1 = parse_range(&range1);
+                let range2 = parse_range(&range2);
+                fields.push((field, range1, range2))
+            } else if line == "your ticket:" || line == "nearby tickets:" || line.is_empty() {
+                continue;
+            } else {
+                let ticket = parse_ticket(line);
+                tickets.push(ticket);
+            }
         }
+        Ok((fields, tickets))
     }
 
-    println!("{}", part1(&fields, &tickets));
+    fn part1(&self, (fields, tickets): &Self::Input) -> Answer {
+        part1(fields, tickets).into()
+    }
 
-    println!("{}", part2(&mut fields, &tickets))
+    fn part2(&self, (fields, tickets): &Self::Input) -> Answer {
+        part2(&mut fields.clone(), tickets).into()
+    }
 }
@@ -1,111 +1,139 @@
-use std::collections::HashSet;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use aoc::{Answer, Solution};
+use geometry::automaton::{self, Moore, Topology};
+use std::collections::{HashMap, HashSet};
 
-lazy_static! {
-    static ref DIRS3: Vec<(i32, i32, i32, i32)> = {
-        let mut dirs = Vec::new();
-        for i in -1..2 {
-            for j in -1..2 {
-                for k in -1..2 {
-                    if i != 0 || j != 0 || k != 0 {
-                        dirs.push((i, j, k, 0))
-                    }
-                }
-            }
-        }
-        dirs
-    };
-    static ref DIRS4: Vec<(i32, i32, i32, i32)> = {
-        let mut dirs = Vec::new();
-        for i in -1..2 {
-            for j in -1..2 {
-                for k in -1..2 {
-                    for l in -1..2 {
-                        if i != 0 || j != 0 || k != 0 || l != 0 {
-                            dirs.push((i, j, k, l))
-                        }
-                    }
-                }
-            }
-        }
-        dirs
-    };
+// The initial cubes lie in the plane of the first two dimensions, so the pocket dimension stays
+// symmetric in the other ones: flipping the sign of one of these coordinates, or swapping two of
+// them, maps active cubes to active cubes. We only keep track of one cube per class of
+// symmetric cubes, the canonical one, whose extra coordinates are non-negative and sorted.
+fn canonical<const N: usize>(mut cube: [i32; N]) -> [i32; N] {
+    for x in cube[2..].iter_mut() {
+        *x = x.abs()
+    }
+    cube[2..].sort_unstable();
+    cube
 }
 
-fn count(
-    cubes: &HashSet<(i32, i32, i32, i32)>,
-    dirs: &Vec<(i32, i32, i32, i32)>,
-    x: i32,
-    y: i32,
-    z: i32,
-    w: i32,
-) -> u32 {
-    let mut count = 0;
-    for (dx, dy, dz, dw) in dirs.iter() {
-        if cubes.contains(&(x + dx, y + dy, z + dz, w + dw)) {
-            count += 1
-        }
-    }
-    return count;
+fn factorial(n: usize) -> usize {
+    (1..=n).product()
 }
 
-fn step(
-    cubes: &HashSet<(i32, i32, i32, i32)>,
-    dirs: &Vec<(i32, i32, i32, i32)>,
-) -> HashSet<(i32, i32, i32, i32)> {
-    let mut neighbours = HashSet::new();
+// The number of cubes whose canonical form is `cube`.
+fn class_size<const N: usize>(cube: &[i32; N]) -> usize {
+    let extra = &cube[2..];
+    let permutations = extra
+        .chunk_by(|a, b| a == b)
+        .fold(factorial(extra.len()), |acc, run| {
+            acc / factorial(run.len())
+        });
+    permutations << extra.iter().filter(|&&x| x != 0).count()
+}
 
-    for (x, y, z, w) in cubes.iter() {
-        for (dx, dy, dz, dw) in dirs.iter() {
-            neighbours.insert((x + dx, y + dy, z + dz, w + dw));
+// Runs the 6 cycles of the boot process, in dimension `N`, and returns the number of active
+// cubes.
+fn boot<const N: usize>(init: &HashSet<[i32; 2]>) -> usize {
+    let moore = Moore::<N>::new();
+    let mut cubes: HashSet<[i32; N]> = init
+        .iter()
+        .map(|&[i, j]| {
+            let mut cube = [0; N];
+            cube[0] = i;
+            cube[1] = j;
+            cube
+        })
+        .collect();
+    for _i in 0..6 {
+        // Each active cube of a class is next to the same number of cubes of a given class. By
+        // counting the neighbours of the canonical cube of each class, weighted by the size of
+        // the class, we count the neighbours of the whole class of each cube, i.e. its number
+        // of neighbours times the size of its class.
+        let mut counts: HashMap<[i32; N], usize> = HashMap::new();
+        for cube in cubes.iter() {
+            let size = class_size(cube);
+            counts.entry(*cube).or_insert(0);
+            for n in moore.neighbours(cube) {
+                *counts.entry(canonical(n)).or_insert(0) += size
+            }
         }
+        cubes = counts
+            .into_iter()
+            .filter(|(cube, n)| automaton::conway(cubes.contains(cube), n / class_size(cube)))
+            .map(|(cube, _)| cube)
+            .collect();
     }
-
-    let mut next: HashSet<(i32, i32, i32, i32)> = HashSet::new();
-
-    for &(x, y, z, w) in neighbours.iter() {
-        let count = count(cubes, dirs, x, y, z, w);
-        let active = cubes.contains(&(x, y, z, w));
-        if active && (count == 2 || count == 3) {
-            next.insert((x, y, z, w));
-        };
-        if !active && count == 3 {
-            next.insert((x, y, z, w));
-        };
-    }
-    return next;
+    cubes.iter().map(class_size).sum()
 }
 
-pub fn run(filename: String) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    let mut init: HashSet<(i32, i32, i32, i32)> = HashSet::new();
-
-    for (i, line) in reader.lines().enumerate() {
-        let line = line.unwrap();
+fn parse(s: &str) -> HashSet<[i32; 2]> {
+    let mut init: HashSet<[i32; 2]> = HashSet::new();
+    for (i, line) in s.lines().enumerate() {
         for (j, char) in line.chars().enumerate() {
             if char == '#' {
-                init.insert((i as i32, j as i32, 0, 0));
+                init.insert([i as i32, j as i32]);
             } else if char == '.' {
             } else {
                 panic!("Invalid input {}", line)
             }
         }
     }
+    init
+}
 
-    let mut cubes = init.clone();
-    for _i in 0..6 {
-        cubes = step(&cubes, &DIRS3);
+pub struct Day;
+
+impl Solution for Day {
+    type Input = HashSet<[i32; 2]>;
+
+    fn parse(&self, input: &str) -> Result<HashSet<[i32; 2]>, String> {
+        Ok(parse(input))
     }
 
-    println!("{}", cubes.len());
+    fn part1(&self, init: &HashSet<[i32; 2]>) -> Answer {
+        boot::<3>(init).into()
+    }
 
-    let mut cubes = init.clone();
-    for _i in 0..6 {
-        cubes = step(&cubes, &DIRS4);
+    fn part2(&self, init: &HashSet<[i32; 2]>) -> Answer {
+        boot::<4>(init).into()
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    println!("{}", cubes.len());
+    const EXAMPLE: &str = ".#.
+..#
+###";
+
+    // Without taking the symmetries into account.
+    fn boot_unfolded<const N: usize>(init: &HashSet<[i32; 2]>) -> usize {
+        let moore = Moore::<N>::new();
+        let mut cubes: HashSet<[i32; N]> = init
+            .iter()
+            .map(|&[i, j]| std::array::from_fn(|k| [i, j].get(k).copied().unwrap_or(0)))
+            .collect();
+        for _i in 0..6 {
+            cubes = automaton::step(&moore, &cubes, automaton::conway);
+        }
+        cubes.len()
+    }
+
+    #[test]
+    fn test_class_size() {
+        assert_eq!(class_size(&[5, 5]), 1);
+        assert_eq!(class_size(&[0, 0, 0, 0]), 1);
+        assert_eq!(class_size(&[0, 0, 1, 1]), 4);
+        assert_eq!(class_size(&[0, 0, 0, 1, 2]), 3 * 2 * 4);
+        assert_eq!(canonical([1, 2, -3, 0, 3]), [1, 2, 0, 3, 3]);
+    }
+
+    #[test]
+    fn test_example() {
+        let init = parse(EXAMPLE);
+        assert_eq!(boot::<3>(&init), 112);
+        assert_eq!(boot::<4>(&init), 848);
+        assert_eq!(boot::<5>(&init), boot_unfolded::<5>(&init));
+        // Checked against `boot_unfolded`, which takes about 25s in release mode.
+        assert_eq!(boot::<6>(&init), 35936);
+    }
 }
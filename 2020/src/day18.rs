@@ -1,5 +1,4 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use aoc::{Answer, Solution};
 
 use nom::{
     branch::alt,
@@ -11,7 +10,7 @@ use nom::{
 };
 
 #[derive(Clone, Debug)]
-enum T {
+pub enum T {
     Int(u64),
     Add,
     Mul,
@@ -92,25 +91,40 @@ fn eval2(input: &[T], mut i: usize) -> (u64, usize) {
     return (nums.iter().product(), i);
 }
 
-pub fn run(filename: String) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    let mut part1 = 0;
-    let mut part2 = 0;
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let line = line.replace(" ", "");
-        let (_, v) = parse(&line).unwrap();
-        let (result, _last) = eval1(&v, 0);
-        part1 += result;
-        let (result, _last) = eval2(&v, 0);
-        part2 += result
+pub struct Day;
+
+impl Solution for Day {
+    // The tokens of each expression.
+    type Input = Vec<Vec<T>>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Vec<T>>, String> {
+        input
+            .lines()
+            .map(|line| {
+                let line = line.replace(' ', "");
+                let (_, v) = parse(&line).map_err(|e| e.to_string())?;
+                Ok(v)
+            })
+            .collect()
     }
 
-    println!("{}", part1);
+    // Operators have the same precedence.
+    fn part1(&self, expressions: &Vec<Vec<T>>) -> Answer {
+        expressions
+            .iter()
+            .map(|v| eval1(v, 0).0)
+            .sum::<u64>()
+            .into()
+    }
 
-    println!("{}", part2);
+    // Additions are evaluated before multiplications.
+    fn part2(&self, expressions: &Vec<Vec<T>>) -> Answer {
+        expressions
+            .iter()
+            .map(|v| eval2(v, 0).0)
+            .sum::<u64>()
+            .into()
+    }
 }
 
 #[cfg(test)]
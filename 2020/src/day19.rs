@@ -1,9 +1,7 @@
 #![allow(unused_imports)]
+use aoc::{Answer, Solution};
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::time::Instant;
 
 use nom::{
     branch::alt,
@@ -180,65 +178,66 @@ mod part2 {
 
 use parsing::parse;
 
-pub fn run(filename: String) {
-    let contents = std::fs::read_to_string(filename).unwrap();
-    let chunks: Vec<_> = contents.split("\n\n").collect();
-    assert_eq!(chunks.len(), 2);
-
-    let mut grammar: HashMap<u64, T> = HashMap::new();
-    for line in chunks[0].lines() {
-        let (_, t) = parse(line).unwrap();
-        let &id = match &t {
-            T::Rule { id, sub_rules: _ } => id,
-            T::Atom { id, content: _ } => id,
-        };
-        grammar.insert(id, t);
-    }
+pub struct Day;
+
+impl Solution for Day {
+    // The grammar, and the messages.
+    type Input = (HashMap<u64, T>, Vec<String>);
 
-    // Part 1, v1. This precomputes the set of words accepted by each rule, and then test for membership. It's slow to the tune of ~2s in release mode.
-    // let start = Instant::now();
-    // let mut cache = HashMap::new();
-    // for &id in grammar.keys() {
-    //     let language = part1::productions_memo(&grammar, &mut cache, id);
-    // }
-    // println!("part 1 precomputation {:?}", start.elapsed());
-
-    // let start = Instant::now();
-    // let mut part1 = 0;
-    // let language = cache.get(&0).unwrap();
-    // for message in chunks[1].lines() {
-    //     if language.contains(message) {
-    //         part1 += 1
-    //     }
-    // }
-    // println!("part 1 check {:?}", start.elapsed());
-    // println!("{}", part1);
-
-    // Part 1, v2
-    let start = Instant::now();
-    let mut part1_v2 = 0;
-    for message in chunks[1].lines() {
-        if part2::valid(&grammar, message) {
-            part1_v2 += 1
+    fn parse(&self, input: &str) -> Result<Self::Input, String> {
+        let chunks: Vec<_> = input.split("\n\n").collect();
+        if chunks.len() != 2 {
+            return Err("Expected rules and messages, separated by a blank line".to_string());
         }
-    }
-    println!("{}", part1_v2);
 
-    println!("part 1 check {:?}", start.elapsed());
+        let mut grammar: HashMap<u64, T> = HashMap::new();
+        for line in chunks[0].lines() {
+            let (_, t) = parse(line).map_err(|e| e.to_string())?;
+            let &id = match &t {
+                T::Rule { id, sub_rules: _ } => id,
+                T::Atom { id, content: _ } => id,
+            };
+            grammar.insert(id, t);
+        }
+        let messages = chunks[1].lines().map(|s| s.to_string()).collect();
+        Ok((grammar, messages))
+    }
 
-    // For part 2, we override a couple of rules, with the effect of making the language infinite.
-    let (_, rule8) = parse("8: 42 | 42 8").unwrap();
-    let (_, rule11) = parse("11: 42 31 | 42 11 31").unwrap();
-    grammar.insert(8, rule8);
-    grammar.insert(11, rule11);
+    fn part1(&self, (grammar, messages): &Self::Input) -> Answer {
+        // Part 1, v1. This precomputes the set of words accepted by each rule, and then test for membership. It's slow to the tune of ~2s in release mode.
+        // let mut cache = HashMap::new();
+        // for &id in grammar.keys() {
+        //     let language = part1::productions_memo(&grammar, &mut cache, id);
+        // }
+        //
+        // let mut part1 = 0;
+        // let language = cache.get(&0).unwrap();
+        // for message in messages.iter() {
+        //     if language.contains(message) {
+        //         part1 += 1
+        //     }
+        // }
+
+        // Part 1, v2
+        messages
+            .iter()
+            .filter(|message| part2::valid(grammar, message))
+            .count()
+            .into()
+    }
 
-    let start = Instant::now();
-    let mut part2 = 0;
-    for message in chunks[1].lines() {
-        if part2::valid(&grammar, message) {
-            part2 += 1
-        }
+    fn part2(&self, (grammar, messages): &Self::Input) -> Answer {
+        // For part 2, we override a couple of rules, with the effect of making the language infinite.
+        let mut grammar = grammar.clone();
+        let (_, rule8) = parse("8: 42 | 42 8").unwrap();
+        let (_, rule11) = parse("11: 42 31 | 42 11 31").unwrap();
+        grammar.insert(8, rule8);
+        grammar.insert(11, rule11);
+
+        messages
+            .iter()
+            .filter(|message| part2::valid(&grammar, message))
+            .count()
+            .into()
     }
-    println!("{}", part2);
-    println!("part 2 check {:?}", start.elapsed());
 }
@@ -1,8 +1,7 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use aoc::{Answer, Solution};
 
 #[derive(Debug)]
-struct Password {
+pub struct Password {
     low: i32,
     high: i32,
     letter: char,
@@ -50,23 +49,33 @@ impl Password {
     }
 }
 
-pub fn run(filename: String) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-    let mut check1 = 0;
-    let mut check2 = 0;
-    for (_index, line) in reader.lines().enumerate() {
-        let line = line.unwrap();
-        let password = Password::parse(line).unwrap();
-        if Password::check1(&password) {
-            check1 += 1
-        };
-        if Password::check2(&password) {
-            check2 += 1
-        }
+const EXAMPLE: &str = "1-3 a: abcde
+1-3 b: cdefg
+2-9 c: ccccccccc";
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input = Vec<Password>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Password>, String> {
+        input
+            .lines()
+            .map(|line| Password::parse(line.to_string()).ok_or(format!("Invalid line: {}", line)))
+            .collect()
+    }
+
+    fn part1(&self, passwords: &Vec<Password>) -> Answer {
+        passwords.iter().filter(|p| p.check1()).count().into()
+    }
+
+    fn part2(&self, passwords: &Vec<Password>) -> Answer {
+        passwords.iter().filter(|p| p.check2()).count().into()
+    }
+
+    aoc::examples! {
+        EXAMPLE => part1: 2, part2: 1;
     }
-    println!("check1 {}", check1);
-    println!("check2 {}", check2)
 }
 
 #[cfg(test)]
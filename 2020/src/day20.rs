@@ -1,6 +1,6 @@
+use aoc::{Answer, Solution};
+use geometry::{Grid, Vector2};
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 
 // In this problem, we need to fiddle with jigsaw pieces to construct a rectangular image, and find the ids of the corner pieces. We can observe that:
 // - each tile that is a corner piece will have exactly two borders that are not matching any other borders;
@@ -22,83 +22,30 @@ pub enum Operation {
 mod image {
 
     use super::*;
-    pub type T = Vec<Vec<bool>>;
-
-    pub fn width(t: &T) -> usize {
-        t[0].len()
-    }
-
-    pub fn height(t: &T) -> usize {
-        t.len()
-    }
-
-    pub fn rotate(image: &T) -> T {
-        assert_eq!(width(image), height(image));
-        let size = width(image);
-        let mut result = vec![];
-        for i in 0..size {
-            let mut line = vec![];
-            for j in 0..size {
-                line.push(image[j][size - i - 1])
-            }
-            result.push(line)
-        }
-        return result;
-    }
-
-    pub fn hflip(image: &T) -> T {
-        let size = width(image);
-        let mut result = vec![];
-        for i in 0..height(image) {
-            let mut line = vec![];
-            for j in 0..size {
-                line.push(image[i][size - j - 1]);
-            }
-            result.push(line);
-        }
-        return result;
-    }
-
-    pub fn vflip(image: &T) -> T {
-        let size = height(image);
-        let mut result = vec![];
-        for i in 0..size {
-            let mut line = vec![];
-            for j in 0..width(image) {
-                line.push(image[size - i - 1][j]);
-            }
-            result.push(line);
-        }
-        return result;
-    }
+    pub type T = Grid<bool>;
 
     pub fn apply_operation(op: Operation, image: &T) -> T {
         match op {
-            Operation::Rotate => rotate(image),
-            Operation::Vflip => vflip(image),
-            Operation::Hflip => hflip(image),
+            Operation::Rotate => image.rotate_ccw(),
+            Operation::Vflip => image.flip_vertical(),
+            Operation::Hflip => image.flip_horizontal(),
         }
     }
 
     fn present_at(image: &T, pattern: &T, i0: usize, j0: usize) -> bool {
-        if width(pattern) + j0 < width(image) && height(pattern) + i0 < width(image) {
-            for i in 0..height(pattern) {
-                for j in 0..width(pattern) {
-                    if pattern[i][j] && !image[i + i0][j + j0] {
-                        return false;
-                    }
-                }
-            }
-            return true;
+        if pattern.width() + j0 < image.width() && pattern.height() + i0 < image.width() {
+            pattern
+                .enumerate::<usize>()
+                .all(|(p, &b)| !b || image[p + Vector2::new(j0, i0)])
         } else {
-            return false;
+            false
         }
     }
 
     fn occurences(image: &T, pattern: &T) -> Vec<(usize, usize)> {
         let mut acc = vec![];
-        for i in 0..height(image) {
-            for j in 0..width(image) {
+        for i in 0..image.height() {
+            for j in 0..image.width() {
                 if present_at(image, pattern, i, j) {
                     acc.push((i, j))
                 }
@@ -121,23 +68,16 @@ mod image {
     }
 
     pub fn remove_pattern(image: &mut T, pattern: &T, i0: usize, j0: usize) {
-        for i in 0..height(pattern) {
-            for j in 0..width(pattern) {
-                if pattern[i][j] {
-                    image[i + i0][j + j0] = false
-                }
+        for (p, &b) in pattern.enumerate::<usize>() {
+            if b {
+                image[p + Vector2::new(j0, i0)] = false
             }
         }
     }
 
     /// Parse a rectangular bitmap from a string.
     pub fn parse(lines: &[&str]) -> T {
-        let mut acc = vec![];
-        for line in lines.iter() {
-            let line: Vec<bool> = line.chars().map(|c| c == '#').collect();
-            acc.push(line)
-        }
-        return acc;
+        Grid::parse(&lines.join("\n"), |c| Some(c == '#')).unwrap()
     }
 }
 
@@ -176,7 +116,10 @@ lazy_static! {
 }
 
 fn extract(tile: &image::T, positions: &Vec<Pos>) -> Vec<bool> {
-    positions.iter().map(|(i, j)| tile[*i][*j]).collect()
+    positions
+        .iter()
+        .map(|&(i, j)| tile[Vector2::new(j, i)])
+        .collect()
 }
 
 fn extract_flip(tile: &image::T, positions: &Vec<Pos>) -> Vec<bool> {
@@ -187,7 +130,7 @@ fn extract_flip(tile: &image::T, positions: &Vec<Pos>) -> Vec<bool> {
 
 fn matching(tile: &image::T, positions: &Vec<Pos>, border: &Vec<bool>) -> bool {
     for (idx, (i, j)) in positions.iter().enumerate() {
-        if tile[*i][*j] != border[idx] {
+        if tile[Vector2::new(*j, *i)] != border[idx] {
             return false;
         }
     }
@@ -265,7 +208,7 @@ fn cardinal(index: &Index, border: &Vec<bool>) -> usize {
 fn orientate_ul_corner(index: &Index, piece: &Piece) -> Piece {
     let (tile_id, mut tile) = piece.clone();
     while cardinal(index, &extract(&tile, &U)) != 1 && cardinal(index, &extract(&tile, &L)) != 1 {
-        tile = image::rotate(&tile)
+        tile = tile.rotate_ccw()
     }
     return (tile_id, tile);
 }
@@ -317,7 +260,7 @@ fn extract_line(pieces: &Vec<Vec<Piece>>, i: usize) -> Vec<bool> {
     for piece in line.iter() {
         let (_tile_id, tile) = piece;
         for j in 0..TILE_SIZE {
-            acc.push(tile[i % TILE_SIZE][j])
+            acc.push(tile[Vector2::new(j, i % TILE_SIZE)])
         }
     }
     acc
@@ -338,14 +281,14 @@ where
 }
 
 // Render the image as a bitmap, once it has been assembled.
-fn render(pieces: &Vec<Vec<Piece>>) -> Vec<Vec<bool>> {
+fn render(pieces: &Vec<Vec<Piece>>) -> image::T {
     let number_of_tiles = pieces.len();
     let mut lines = vec![];
     for i in 0..(number_of_tiles * TILE_SIZE - 1) {
         let line = remove_borders(&extract_line(pieces, i));
         lines.push(line);
     }
-    return remove_borders(&lines);
+    Grid::from_rows(remove_borders(&lines)).unwrap()
 }
 
 // Build the puzzle, one piece at a time, starting from the UL corner. This assumes that the UL corner has been properly oriented.
@@ -383,49 +326,53 @@ fn assembly(index: &Index, pieces: &[Piece], ul_corner: &Piece) -> Vec<Vec<Piece
     acc
 }
 
-pub fn run(filename: String) {
-    let contents = std::fs::read_to_string(filename).unwrap();
-    let chunks: Vec<_> = contents.split("\n\n").collect();
-    let mut pieces: Vec<Piece> = Vec::new();
-    for chunk in chunks.iter() {
-        let lines: Vec<_> = chunk.split("\n").collect();
-        let id = (scan_fmt!(lines[0], "Tile {d}:", u64)).unwrap();
-        let tile: image::T = image::parse(&lines[1..]);
-        pieces.push((id, tile))
+pub struct Day;
+
+impl Solution for Day {
+    type Input = Vec<Piece>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Piece>, String> {
+        let mut pieces: Vec<Piece> = Vec::new();
+        for chunk in input.split("\n\n").filter(|chunk| !chunk.trim().is_empty()) {
+            let lines: Vec<_> = chunk.lines().collect();
+            let id = scan_fmt!(lines[0], "Tile {d}:", u64).map_err(|e| e.to_string())?;
+            let tile: image::T = image::parse(&lines[1..]);
+            pieces.push((id, tile))
+        }
+        Ok(pieces)
     }
 
-    let index = create_index(&pieces);
-    let corners = corners(&index, &pieces);
-    println!(
-        "Corners {:?}, product {}",
-        corners.iter().map(|piece| piece.0).collect::<Vec<_>>(),
-        corners.iter().map(|piece| piece.0).product::<u64>()
-    );
-
-    let ul_corner = orientate_ul_corner(&index, &corners[0]);
-    let assembly = assembly(&index, &pieces, &ul_corner);
-    let image = render(&assembly);
-    println!("Final image size: {}x{}", image.len(), image[0].len());
-
-    let pattern = image::parse(&vec![
-        "                  # ",
-        "#    ##    ##    ###",
-        " #  #  #  #  #  #   ",
-    ]);
-
-    let (mut image, occurences) = image::find_matching_orientation(&image, &pattern).unwrap();
-
-    for (i, j) in occurences.into_iter() {
-        image::remove_pattern(&mut image, &pattern, i, j)
+    // The product of the ids of the corners.
+    fn part1(&self, pieces: &Vec<Piece>) -> Answer {
+        let index = create_index(pieces);
+        corners(&index, pieces)
+            .iter()
+            .map(|piece| piece.0)
+            .product::<u64>()
+            .into()
     }
 
-    let mut part2 = 0;
-    for i in 0..IMAGE_SIZE {
-        for j in 0..IMAGE_SIZE {
-            if image[i][j] {
-                part2 += 1
-            }
+    // The number of `#` that are not part of a sea monster.
+    fn part2(&self, pieces: &Vec<Piece>) -> Answer {
+        let index = create_index(pieces);
+        let corners = corners(&index, pieces);
+        let ul_corner = orientate_ul_corner(&index, &corners[0]);
+        let assembly = assembly(&index, pieces, &ul_corner);
+        let image = render(&assembly);
+
+        let pattern = image::parse(&[
+            "                  # ",
+            "#    ##    ##    ###",
+            " #  #  #  #  #  #   ",
+        ]);
+
+        let (mut image, occurences) = image::find_matching_orientation(&image, &pattern).unwrap();
+
+        for (i, j) in occurences.into_iter() {
+            image::remove_pattern(&mut image, &pattern, i, j)
         }
+
+        assert_eq!(image.width(), IMAGE_SIZE);
+        image.iter().filter(|&&b| b).count().into()
     }
-    println!("Part 2: {}", part2)
 }
@@ -1,7 +1,5 @@
+use aoc::{Answer, Solution};
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::time::Instant;
 
 use nom::{
     branch::alt,
@@ -60,72 +58,79 @@ fn possible_sources(allergen: &str, foods: &Vec<Food>) -> HashSet<String> {
     return possible_sources;
 }
 
-pub fn run(filename: String) {
-    let contents = std::fs::read_to_string(filename).unwrap();
-    let foods: Vec<Food> = contents
-        .split("\n")
-        .map(|line| {
-            let (_rest, food) = parsing::food(line).unwrap();
-            food
-        })
-        .collect();
+pub struct Day;
 
-    let allergens: HashSet<_> = foods
-        .iter()
-        .flat_map(|(_ingredients, allergens)| allergens)
-        .collect();
-    // The key observation here is that `each allergen is found in exactly one ingredient`.
+impl Solution for Day {
+    type Input = Vec<Food>;
 
-    let all_possible_sources: HashSet<_> = allergens
-        .iter()
-        .flat_map(|allergen| possible_sources(allergen, &foods))
-        .collect();
+    fn parse(&self, input: &str) -> Result<Vec<Food>, String> {
+        input
+            .lines()
+            .map(|line| {
+                let (_rest, food) = parsing::food(line).map_err(|e| e.to_string())?;
+                Ok(food)
+            })
+            .collect()
+    }
 
-    println!("{:?}", all_possible_sources);
+    // The number of occurences of the ingredients that cannot contain any allergen.
+    fn part1(&self, foods: &Vec<Food>) -> Answer {
+        let allergens: HashSet<_> = foods
+            .iter()
+            .flat_map(|(_ingredients, allergens)| allergens)
+            .collect();
+        // The key observation here is that `each allergen is found in exactly one ingredient`.
 
-    let part1 = foods
-        .iter()
-        .flat_map(|(ingredients, _allergens)| ingredients)
-        .filter(|ingredient| !all_possible_sources.contains(ingredient.clone()))
-        .count();
+        let all_possible_sources: HashSet<_> = allergens
+            .iter()
+            .flat_map(|allergen| possible_sources(allergen, foods))
+            .collect();
 
-    println!("{:?}", part1);
+        foods
+            .iter()
+            .flat_map(|(ingredients, _allergens)| ingredients)
+            .filter(|&ingredient| !all_possible_sources.contains(ingredient))
+            .count()
+            .into()
+    }
 
-    let start = Instant::now();
-    let mut candidates: Vec<_> = allergens
-        .iter()
-        .map(|allergen| (allergen, possible_sources(allergen, &foods)))
-        .collect();
+    // The ingredients containing each allergen.
+    fn part2(&self, foods: &Vec<Food>) -> Answer {
+        let allergens: HashSet<_> = foods
+            .iter()
+            .flat_map(|(_ingredients, allergens)| allergens)
+            .collect();
+        let mut candidates: Vec<_> = allergens
+            .iter()
+            .map(|allergen| (allergen, possible_sources(allergen, foods)))
+            .collect();
 
-    // The list of pairs `allergen, ingredient` that we have discovered.
-    let mut allergens = Vec::new();
+        // The list of pairs `allergen, ingredient` that we have discovered.
+        let mut allergens = Vec::new();
 
-    while candidates.len() > 0 {
-        candidates.sort_by_key(|(_allergen, sources)| sources.len());
+        while !candidates.is_empty() {
+            candidates.sort_by_key(|(_allergen, sources)| sources.len());
 
-        let (allergen, mut sources) = candidates.remove(0);
-        assert_eq!(sources.len(), 1);
+            let (allergen, mut sources) = candidates.remove(0);
+            assert_eq!(sources.len(), 1);
 
-        let source = sources.drain().next().unwrap();
-        allergens.push((allergen, source.clone()));
+            let source = sources.drain().next().unwrap();
+            allergens.push((allergen, source.clone()));
 
-        for (_allergen, sources) in candidates.iter_mut() {
-            sources.remove(&source);
+            for (_allergen, sources) in candidates.iter_mut() {
+                sources.remove(&source);
+            }
         }
-    }
 
-    // Let's sort the allergen mapping alphabetically by allergen, and then get out the list of ingredients they corrspond to.
-    allergens.sort();
+        // Let's sort the allergen mapping alphabetically by allergen, and then get out the list of ingredients they corrspond to.
+        allergens.sort();
 
-    println!("Time {:?}", start.elapsed());
-
-    println!(
-        "{}",
         allergens
             .iter()
             .map(|(_, ingredient)| ingredient)
             .cloned()
             .collect::<Vec<_>>()
             .join(",")
-    )
+            .into()
+    }
 }
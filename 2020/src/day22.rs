@@ -1,9 +1,9 @@
+use aoc::{Answer, Solution};
 use std::collections::HashSet;
 
 fn parse(s: &str) -> Vec<usize> {
-    let lines: Vec<_> = s.split("\n").collect();
-    lines[1..]
-        .iter()
+    s.lines()
+        .skip(1)
         .map(|s| {
             let u: usize = s.parse().unwrap();
             u
@@ -132,16 +132,28 @@ mod part2 {
     }
 }
 
-pub fn run(filename: String) {
-    let contents = std::fs::read_to_string(filename).unwrap();
+pub struct Day;
 
-    let players: Vec<_> = contents.split("\n\n").collect();
+impl Solution for Day {
+    // The decks of both players.
+    type Input = (Vec<usize>, Vec<usize>);
 
-    let player1_deck: Vec<_> = parse(players[0]);
-    let player2_deck: Vec<_> = parse(players[1]);
+    fn parse(&self, input: &str) -> Result<Self::Input, String> {
+        let players: Vec<_> = input.split("\n\n").collect();
+        if players.len() != 2 {
+            return Err(format!("Expected 2 players, got {}", players.len()));
+        }
+        Ok((parse(players[0]), parse(players[1])))
+    }
 
-    println!("{}", part1::play(&player1_deck, &player2_deck));
-    println!("{:?}", part2::play(&player1_deck, &player2_deck));
+    fn part1(&self, (player1_deck, player2_deck): &Self::Input) -> Answer {
+        part1::play(player1_deck, player2_deck).into()
+    }
+
+    fn part2(&self, (player1_deck, player2_deck): &Self::Input) -> Answer {
+        let (_winner, score) = part2::play(player1_deck, player2_deck);
+        score.into()
+    }
 }
 
 #[cfg(test)]
@@ -1,8 +1,7 @@
+use aoc::{Answer, Solution};
 use std::collections::HashMap;
 use std::fmt::Debug;
-use std::fs::File;
 use std::hash::Hash;
-use std::io::{BufRead, BufReader};
 use std::ops::{Index, IndexMut};
 
 type T = usize;
@@ -121,7 +120,7 @@ fn labels(permutation: &Permutation, x: T, n: usize) -> Vec<T> {
     return acc;
 }
 
-fn part1(permutation: &Permutation) {
+fn part1(permutation: &Permutation) -> String {
     let mut permutation = permutation.clone();
 
     let mut current = 0;
@@ -135,10 +134,10 @@ fn part1(permutation: &Permutation) {
         .iter()
         .map(|n| n.to_string())
         .collect();
-    println!("{}", labels.join(""))
+    labels.join("")
 }
 
-fn part2(permutation: &Permutation) {
+fn part2(permutation: &Permutation) -> usize {
     let mut permutation = permutation.clone();
 
     let mut current = 0;
@@ -148,25 +147,53 @@ fn part2(permutation: &Permutation) {
         current = next;
     }
 
-    let labels: Vec<usize> = labels(&permutation, 1, 2);
-    println!("{:?}", labels);
-    println!("{}", labels.iter().product::<usize>());
+    labels(&permutation, 1, 2).iter().product()
 }
 
-pub fn run() {
-    let example_seed = vec![3, 8, 9, 1, 2, 5, 4, 6, 7];
-    let permutation = create(&example_seed);
-    part1(&permutation);
+pub struct Day;
+
+impl Solution for Day {
+    // The labels of the cups, clockwise.
+    type Input = Vec<T>;
+
+    // The labels of the cups, one digit each, e.g. `389125467`.
+    fn parse(&self, input: &str) -> Result<Vec<T>, String> {
+        input
+            .trim()
+            .chars()
+            .map(|c| {
+                c.to_digit(10)
+                    .map(|d| d as T)
+                    .ok_or(format!("Invalid cup '{}'", c))
+            })
+            .collect()
+    }
+
+    fn part1(&self, seed: &Vec<T>) -> Answer {
+        part1(&create(seed)).into()
+    }
 
-    let my_seed = vec![2, 5, 3, 1, 4, 9, 8, 6, 7];
-    let permutation = create(&my_seed);
-    part1(&permutation);
+    // One million cups, the first ones labelled as in the input.
+    fn part2(&self, seed: &Vec<T>) -> Answer {
+        let mut input = seed.clone();
+        for i in seed.len()..1000000 {
+            input.push(i + 1);
+        }
+        part2(&create(&input)).into()
+    }
 
-    let mut input = my_seed.clone();
-    for i in my_seed.len()..1000000 {
-        input.push(i + 1);
+    aoc::examples! {
+        "389125467" => part1: "67384529", part2: 149245887792u64;
     }
-    let permutation = create(&input);
+}
 
-    part2(&permutation)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let example_seed = Day.parse("389125467\n").unwrap();
+        assert_eq!(part1(&create(&example_seed)), "67384529");
+    }
 }
@@ -1,96 +1,51 @@
+use aoc::{Answer, Solution};
+use geometry::automaton::{self, Hex};
+use geometry::{HexDirection, Vector2};
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 
-lazy_static! {
- // Iterating through OPS gives us all orientations
-    static ref DIRS: Vec<(&'static str, i32, i32)> = {
-        let dirs = vec![
-            ("e", 2, 0),
-            ("ne", 1, -1),
-            ("nw", -1, -1),
-            ("w", -2, 0),
-            ("sw", -1, 1),
-            ("se", 1, 1),
-    ];
-
-    return dirs};
+// Tiles are identified by their axial coordinates, relative to the reference tile.
+fn parse(s: &str) -> Vector2<i32> {
+    HexDirection::parse_all(s)
+        .unwrap()
+        .iter()
+        .fold(Vector2::new(0, 0), |pos, d| pos + d.to_vector())
 }
 
-fn parse(mut s: &str) -> (i32, i32) {
-    let mut x = 0;
-    let mut y = 0;
-
-    'outer: while s.len() != 0 {
-        for (d, dx, dy) in DIRS.iter() {
-            if s.starts_with(d) {
-                x += dx;
-                y += dy;
-                s = s.strip_prefix(d).unwrap();
-                continue 'outer;
-            }
-        }
-        panic!("Could not find matching prefix {}", s)
-    }
-    return (x, y);
+// A black tile stays black with 1 or 2 black neighbours, and a white tile turns black with 2.
+fn step(state: &HashSet<Vector2<i32>>) -> HashSet<Vector2<i32>> {
+    automaton::step(&Hex, state, |black, n| n == 2 || (black && n == 1))
 }
 
-fn step(state: &HashSet<(i32, i32)>) -> HashSet<(i32, i32)> {
-    let mut neighbours: HashSet<(i32, i32)> = HashSet::new();
+pub struct Day;
 
-    for (x, y) in state.iter() {
-        neighbours.insert((*x, *y));
-        for (_, dx, dy) in DIRS.iter() {
-            neighbours.insert((x + dx, y + dy));
-        }
-    }
-
-    let mut next = HashSet::new();
+impl Solution for Day {
+    // The black tiles, after the initial flips.
+    type Input = HashSet<Vector2<i32>>;
 
-    for (x, y) in neighbours.into_iter() {
-        let mut adjacent_black_tiles = 0;
-        for (_, dx, dy) in DIRS.iter() {
-            if state.contains(&(x + dx, y + dy)) {
-                adjacent_black_tiles += 1
+    fn parse(&self, input: &str) -> Result<HashSet<Vector2<i32>>, String> {
+        let mut black = HashSet::new();
+        for line in input.lines() {
+            let pos = parse(line);
+            if black.contains(&pos) {
+                black.remove(&pos);
+            } else {
+                black.insert(pos);
             }
         }
-
-        if state.contains(&(x, y)) && (adjacent_black_tiles == 1 || adjacent_black_tiles == 2) {
-            next.insert((x, y));
-        }
-        if !state.contains(&(x, y)) && adjacent_black_tiles == 2 {
-            next.insert((x, y));
-        }
+        Ok(black)
     }
 
-    return next;
-}
-
-pub fn run(filename: String) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    let mut black = HashSet::new();
-    for line in reader.lines() {
-        let line = line.unwrap();
-
-        let pos = parse(&line);
-
-        if black.contains(&pos) {
-            black.remove(&pos);
-        } else {
-            black.insert(pos);
-        }
+    fn part1(&self, black: &HashSet<Vector2<i32>>) -> Answer {
+        black.len().into()
     }
 
-    // part 1
-    println!("{}", black.len());
-
-    // part 2
-    for i in 0..100 {
-        black = step(&black);
+    fn part2(&self, black: &HashSet<Vector2<i32>>) -> Answer {
+        let mut black = black.clone();
+        for _i in 0..100 {
+            black = step(&black);
+        }
+        black.len().into()
     }
-    println!("{}", black.len())
 }
 
 #[cfg(test)]
@@ -100,12 +55,12 @@ mod tests {
     #[test]
     fn test_parsing() {
         let s = "sese";
-        assert_eq!(parse(s), (2, 2));
+        assert_eq!(parse(s), Vector2::new(0, 2));
 
         let s = "senw";
-        assert_eq!(parse(s), (0, 0));
+        assert_eq!(parse(s), Vector2::new(0, 0));
 
         let s = "nwwswee";
-        assert_eq!(parse(s), (0, 0));
+        assert_eq!(parse(s), Vector2::new(0, 0));
     }
 }
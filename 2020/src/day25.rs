@@ -1,3 +1,5 @@
+use aoc::{Answer, Solution};
+
 const MOD: u64 = 20201227;
 const SN: u64 = 7;
 
@@ -29,11 +31,36 @@ fn encryption_key(n1: u64, n2: u64) -> u64 {
     transform(l1, n2)
 }
 
-pub fn run() {
-    let n1: u64 = 13135480;
-    let n2: u64 = 8821721;
+pub struct Day;
+
+impl Solution for Day {
+    // The public keys of the card and of the door.
+    type Input = (u64, u64);
 
-    println!("{}", encryption_key(n1, n2));
+    // The public keys of the card and of the door, one per line.
+    fn parse(&self, input: &str) -> Result<(u64, u64), String> {
+        let keys = input
+            .lines()
+            .map(|line| line.parse().map_err(|e| format!("{}: {}", line, e)))
+            .collect::<Result<Vec<u64>, String>>()?;
+        match keys[..] {
+            [card, door] => Ok((card, door)),
+            _ => Err(format!("Expected 2 public keys, got {}", keys.len())),
+        }
+    }
+
+    fn part1(&self, &(n1, n2): &(u64, u64)) -> Answer {
+        encryption_key(n1, n2).into()
+    }
+
+    // There is no second part on the last day.
+    fn part2(&self, _: &(u64, u64)) -> Answer {
+        Answer::Nothing
+    }
+
+    aoc::examples! {
+        "5764801\n17807724" => part1: 14897079;
+    }
 }
 
 #[cfg(test)]
@@ -1,36 +1,60 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use aoc::{Answer, Solution};
+use geometry::{Grid, Vector2};
 
-fn check_slope(map: &Vec<Vec<u8>>, right: usize, down: usize) -> usize {
+// `true` for trees. The map repeats itself to the right.
+fn check_slope(map: &Grid<bool>, right: usize, down: usize) -> usize {
     let mut i = 0;
     let mut j = 0;
     let mut n = 0;
-    let len = map[0].len();
-    while i < map.len() {
-        if map[i][j % len] == b'#' {
+    while i < map.height() {
+        if map[Vector2::new(j % map.width(), i)] {
             n += 1
         };
         i += down;
         j += right
     }
-    return n;
+    n
 }
 
-pub fn run(filename: String) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-    let mut map: Vec<Vec<u8>> = Vec::new();
-    for (_index, line) in reader.lines().enumerate() {
-        let line = line.unwrap();
-        let line = line.as_bytes();
-        map.push(line.to_vec())
+const EXAMPLE: &str = "..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input = Grid<bool>;
+
+    fn parse(&self, input: &str) -> Result<Grid<bool>, String> {
+        Grid::parse(input, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+    }
+
+    fn part1(&self, map: &Grid<bool>) -> Answer {
+        check_slope(map, 3, 1).into()
+    }
+
+    fn part2(&self, map: &Grid<bool>) -> Answer {
+        let s11 = check_slope(map, 1, 1);
+        let s31 = check_slope(map, 3, 1);
+        let s51 = check_slope(map, 5, 1);
+        let s71 = check_slope(map, 7, 1);
+        let s12 = check_slope(map, 1, 2);
+        (s11 * s31 * s51 * s71 * s12).into()
     }
 
-    let s11 = check_slope(&map, 1, 1);
-    let s31 = check_slope(&map, 3, 1);
-    let s51 = check_slope(&map, 5, 1);
-    let s71 = check_slope(&map, 7, 1);
-    let s12 = check_slope(&map, 1, 2);
-    println!("{}", s31);
-    println!("{}", s11 * s31 * s51 * s71 * s12);
+    aoc::examples! {
+        EXAMPLE => part1: 7, part2: 336;
+    }
 }
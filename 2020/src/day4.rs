@@ -1,7 +1,6 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use aoc::{Answer, Solution};
 
-struct Passport {
+pub struct Passport {
     byr: Option<i32>,
     iyr: Option<i32>,
     eyr: Option<i32>,
@@ -102,37 +101,75 @@ impl Passport {
     }
 }
 
-pub fn run(filename: String) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-    let mut passports: Vec<Passport> = Vec::new();
-    let mut acc = String::new();
-
-    for line in reader.lines() {
-        let line = line.unwrap();
-        if line.len() == 0 {
-            passports.push(Passport::parse(&acc).unwrap());
-            acc.clear();
-        } else {
-            acc.push_str(&line);
-            acc.push(' ');
-        }
+const EXAMPLE: &str = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in";
+
+const INVALID: &str = "eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007";
+
+const VALID: &str = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719";
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input = Vec<Passport>;
+
+    // Passports are separated by blank lines.
+    fn parse(&self, input: &str) -> Result<Vec<Passport>, String> {
+        input
+            .split("\n\n")
+            .filter(|s| !s.trim().is_empty())
+            .map(|s| {
+                let s = s.replace('\n', " ");
+                Passport::parse(&s).ok_or(format!("Invalid passport: {}", s))
+            })
+            .collect()
     }
-    // deal with files without trailing new line
-    if acc.len() > 0 {
-        passports.push(Passport::parse(&acc).unwrap())
+
+    fn part1(&self, passports: &Vec<Passport>) -> Answer {
+        passports.iter().filter(|p| p.check1()).count().into()
     }
 
-    let mut count1 = 0;
-    let mut count2 = 0;
-    for p in passports.iter() {
-        if p.check1() {
-            count1 += 1
-        };
-        if p.check2() {
-            count2 += 1
-        };
+    fn part2(&self, passports: &Vec<Passport>) -> Answer {
+        passports.iter().filter(|p| p.check2()).count().into()
+    }
+
+    aoc::examples! {
+        EXAMPLE => part1: 2, part2: 2;
+        INVALID => part2: 0;
+        VALID => part2: 4;
     }
-    println!("{}", count1);
-    println!("{}", count2);
 }
@@ -1,5 +1,4 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use aoc::{Answer, Solution};
 
 fn parse_bsp(s: &str, high: char) -> usize {
     let mut r = 0;
@@ -19,26 +18,37 @@ fn parse_seat_id(s: &str) -> usize {
     row * 8 + col
 }
 
-pub fn run(filename: String) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-    let mut max_seen = 0;
-    let mut min_seen = 127 * 8;
-    // We actually don't know how many seats the plane has!
-    // However, we can over-approximate it using the number of bits in the seat id.
-    let mut bitmap: [bool; 127 * 8] = [false; 127 * 8];
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let seat_id = parse_seat_id(&line);
-        bitmap[seat_id] = true;
-        max_seen = usize::max(max_seen, seat_id);
-        min_seen = usize::min(min_seen, seat_id);
+pub struct Day;
+
+impl Solution for Day {
+    type Input = Vec<usize>;
+
+    fn parse(&self, input: &str) -> Result<Vec<usize>, String> {
+        Ok(input.lines().map(parse_seat_id).collect())
+    }
+
+    fn part1(&self, seats: &Vec<usize>) -> Answer {
+        (*seats.iter().max().unwrap()).into()
     }
-    println!("{}", max_seen);
-    for (index, value) in bitmap.iter().enumerate() {
-        if !value && min_seen <= index && index <= max_seen {
-            println!("{}", index)
+
+    // The only free seat between the first and the last ones.
+    fn part2(&self, seats: &Vec<usize>) -> Answer {
+        let min_seen = *seats.iter().min().unwrap();
+        let max_seen = *seats.iter().max().unwrap();
+        // We actually don't know how many seats the plane has!
+        // However, we can over-approximate it using the number of bits in the seat id.
+        let mut bitmap: [bool; 127 * 8] = [false; 127 * 8];
+        for &seat_id in seats.iter() {
+            bitmap[seat_id] = true;
         }
+        (min_seen..=max_seen)
+            .find(|&index| !bitmap[index])
+            .unwrap()
+            .into()
+    }
+
+    aoc::examples! {
+        "FBFBBFFRLR\nBFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL" => part1: 820;
     }
 }
 
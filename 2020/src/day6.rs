@@ -1,5 +1,4 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use aoc::{Answer, Solution};
 
 fn bit(c: char) -> usize {
     assert!(c.is_ascii_alphabetic());
@@ -14,32 +13,57 @@ fn parse(s: &str) -> usize {
     mask
 }
 
-pub fn run(filename: String) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    let mut acc_anyone: usize = 0;
-    let mut result_anyone: u32 = 0;
-    let mut acc_everyone: usize = !0;
-    let mut result_everyone: u32 = 0;
-    for line in reader.lines() {
-        let line = line.unwrap();
-        if line.len() == 0 {
-            result_everyone += acc_everyone.count_ones();
-            result_anyone += acc_anyone.count_ones();
-            acc_everyone = !0;
-            acc_anyone = 0;
-        } else {
-            acc_anyone |= parse(&line);
-            acc_everyone &= parse(&line);
-        }
+const EXAMPLE: &str = "abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b";
+
+pub struct Day;
+
+impl Solution for Day {
+    // The answers of each group, one mask per person.
+    type Input = Vec<Vec<usize>>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Vec<usize>>, String> {
+        Ok(input
+            .split("\n\n")
+            .filter(|group| !group.trim().is_empty())
+            .map(|group| group.lines().map(parse).collect())
+            .collect())
+    }
+
+    // The questions to which anyone answered yes.
+    fn part1(&self, groups: &Vec<Vec<usize>>) -> Answer {
+        groups
+            .iter()
+            .map(|group| group.iter().fold(0, |acc, &m| acc | m).count_ones())
+            .sum::<u32>()
+            .into()
+    }
+
+    // The questions to which everyone answered yes.
+    fn part2(&self, groups: &Vec<Vec<usize>>) -> Answer {
+        groups
+            .iter()
+            .map(|group| group.iter().fold(!0, |acc, &m| acc & m).count_ones())
+            .sum::<u32>()
+            .into()
+    }
+
+    aoc::examples! {
+        EXAMPLE => part1: 11, part2: 6;
     }
-    if acc_anyone != 0 || acc_everyone != 0 {
-        result_everyone += acc_everyone.count_ones();
-        result_anyone += acc_anyone.count_ones();
-    };
-    println!("{}", result_anyone);
-    println!("{}", result_everyone)
 }
 
 #[cfg(test)]
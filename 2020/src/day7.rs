@@ -1,6 +1,5 @@
+use aoc::{Answer, Solution};
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 
 // Each COLOR is made of a qualified (e.g. dull) and a base color (e.g. cyan).
 // Each entry has the form `COLOR contains no other` or `COLOR 'contains' sep_list([int COLOR], ',')`
@@ -54,30 +53,62 @@ fn contains(entries: &HashMap<String, Vec<(i32, String)>>, src: &str) -> i32 {
     return n;
 }
 
-// Input contain lines of the form `name -> (int * name) list` (the actual input is a bit obfuscated).
-pub fn run(filename: String) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-    let mut entries = HashMap::new();
-    for line in reader.lines() {
-        let line: String = line.unwrap();
-        let line = line
-            .replace("bags", "")
-            .replace("bag", "")
-            .replace("  ", " ")
-            .replace(" ,", ",")
-            .replace(" .", "");
-        let (bag, content) = parse(&line);
-        entries.insert(bag, content);
+const EXAMPLE: &str = "light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+
+const NESTED: &str = "shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.";
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input = HashMap<String, Vec<(i32, String)>>;
+
+    // Input contain lines of the form `name -> (int * name) list` (the actual input is a bit
+    // obfuscated).
+    fn parse(&self, input: &str) -> Result<HashMap<String, Vec<(i32, String)>>, String> {
+        let mut entries = HashMap::new();
+        for line in input.lines() {
+            let line = line
+                .replace("bags", "")
+                .replace("bag", "")
+                .replace("  ", " ")
+                .replace(" ,", ",")
+                .replace(" .", "");
+            let (bag, content) = parse(&line);
+            entries.insert(bag, content);
+        }
+        Ok(entries)
     }
 
-    let mut count = 0;
-    for i in entries.keys() {
-        if find(&entries, "shiny gold", i) {
-            count += 1
+    fn part1(&self, entries: &HashMap<String, Vec<(i32, String)>>) -> Answer {
+        let mut count = 0;
+        for i in entries.keys() {
+            if find(entries, "shiny gold", i) {
+                count += 1
+            }
         }
+        count.into()
+    }
+
+    fn part2(&self, entries: &HashMap<String, Vec<(i32, String)>>) -> Answer {
+        contains(entries, "shiny gold").into()
     }
-    println!("{}", count);
 
-    println!("{}", contains(&entries, "shiny gold"))
+    aoc::examples! {
+        EXAMPLE => part1: 4, part2: 32;
+        NESTED => part2: 126;
+    }
 }
@@ -1,5 +1,4 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use aoc::{Answer, Solution};
 
 fn is_two_sum(v: &[u64], n: u64) -> bool {
     for i in 0..v.len() {
@@ -42,24 +41,31 @@ fn find_range_summing_to(v: &[u64], n: u64) -> Option<(usize, usize)> {
     }
 }
 
-pub fn run(filename: String) {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
+pub struct Day;
 
-    let mut input: Vec<u64> = Vec::new();
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let num: u64 = line.parse().unwrap();
-        input.push(num)
+impl Solution for Day {
+    type Input = Vec<u64>;
+
+    fn parse(&self, input: &str) -> Result<Vec<u64>, String> {
+        input
+            .lines()
+            .map(|line| line.parse().map_err(|e| format!("{}: {}", line, e)))
+            .collect()
     }
 
-    let (_index, n) = find(&input, 25).unwrap();
-    println!("{}", n);
+    // The first number that is not the sum of two of the 25 numbers before it.
+    fn part1(&self, input: &Vec<u64>) -> Answer {
+        let (_index, n) = find(input, 25).unwrap();
+        n.into()
+    }
 
-    let (i, j) = find_range_summing_to(&input, n).unwrap();
-    let min = input[i..j].iter().min().unwrap();
-    let max = input[i..j].iter().max().unwrap();
-    println!("{} + {} = {}", min, max, min + max)
+    fn part2(&self, input: &Vec<u64>) -> Answer {
+        let (_index, n) = find(input, 25).unwrap();
+        let (i, j) = find_range_summing_to(input, n).unwrap();
+        let min = input[i..j].iter().min().unwrap();
+        let max = input[i..j].iter().max().unwrap();
+        (min + max).into()
+    }
 }
 
 #[cfg(test)]
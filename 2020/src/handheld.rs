@@ -0,0 +1,391 @@
+// The handheld game console from day 8: a tiny machine with a single accumulator and three
+// instructions.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Op {
+    Nop,
+    Acc,
+    Jmp,
+}
+
+impl Op {
+    pub fn parse(s: &str) -> Result<Op, String> {
+        match s {
+            "nop" => Ok(Op::Nop),
+            "acc" => Ok(Op::Acc),
+            "jmp" => Ok(Op::Jmp),
+            s => Err(format!("invalid operation {:?}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Instruction {
+    pub op: Op,
+    pub arg: i32,
+}
+
+impl Instruction {
+    pub fn new(op: Op, arg: i32) -> Instruction {
+        Instruction { op, arg }
+    }
+
+    // Parses a line of the form `acc +1`.
+    pub fn parse(s: &str) -> Result<Instruction, String> {
+        let mut words = s.split_whitespace();
+        let op = match words.next() {
+            Some(op) => Op::parse(op)?,
+            None => return Err("empty instruction".to_string()),
+        };
+        let arg = match words.next() {
+            Some(arg) => arg
+                .parse()
+                .map_err(|e| format!("invalid argument {:?}: {}", arg, e))?,
+            None => return Err(format!("missing argument for {:?}", op)),
+        };
+        if let Some(extra) = words.next() {
+            return Err(format!("unexpected {:?} after argument", extra));
+        }
+        Ok(Instruction { op, arg })
+    }
+}
+
+pub type Program = Vec<Instruction>;
+
+// Parses one instruction per line, skipping blank lines. Errors mention the (1-indexed) line.
+pub fn parse(s: &str) -> Result<Program, String> {
+    let mut program = Vec::new();
+    for (i, line) in s.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let instruction = Instruction::parse(line).map_err(|e| format!("line {}: {}", i + 1, e))?;
+        program.push(instruction)
+    }
+    Ok(program)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    // The program counter points to an instruction of the program.
+    Running,
+    // The program counter points just after the last instruction.
+    Terminated,
+    // The program counter points anywhere else.
+    Fault,
+}
+
+#[derive(Debug, Clone)]
+pub struct Machine<'a> {
+    program: &'a [Instruction],
+    pub pc: i32,
+    pub acc: i32,
+}
+
+impl<'a> Machine<'a> {
+    pub fn new(program: &'a [Instruction]) -> Machine<'a> {
+        Machine {
+            program,
+            pc: 0,
+            acc: 0,
+        }
+    }
+
+    pub fn status(&self) -> Status {
+        let len = self.program.len() as i32;
+        if 0 <= self.pc && self.pc < len {
+            Status::Running
+        } else if self.pc == len {
+            Status::Terminated
+        } else {
+            Status::Fault
+        }
+    }
+
+    // Executes the instruction at the program counter, if any, and returns the new status.
+    pub fn step(&mut self) -> Status {
+        if self.status() != Status::Running {
+            return self.status();
+        }
+        let instruction = self.program[self.pc as usize];
+        match instruction.op {
+            Op::Nop => self.pc += 1,
+            Op::Acc => {
+                self.acc += instruction.arg;
+                self.pc += 1
+            }
+            Op::Jmp => self.pc += instruction.arg,
+        }
+        self.status()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Execution {
+    // An instruction is about to be executed a second time. Holds the accumulator at that point.
+    Loop(i32),
+    Terminate(i32),
+    Fault { pc: i32, acc: i32 },
+}
+
+// Runs the program until it terminates, faults, or is about to execute an instruction twice.
+// Since the machine has no other state than the program counter and the accumulator, the
+// latter means that the program never terminates.
+pub fn execute_until_loop(program: &[Instruction]) -> Execution {
+    let mut machine = Machine::new(program);
+    let mut visited = vec![false; program.len()];
+    loop {
+        match machine.status() {
+            Status::Terminated => return Execution::Terminate(machine.acc),
+            Status::Fault => {
+                return Execution::Fault {
+                    pc: machine.pc,
+                    acc: machine.acc,
+                }
+            }
+            Status::Running => {
+                let pc = machine.pc as usize;
+                if visited[pc] {
+                    return Execution::Loop(machine.acc);
+                }
+                visited[pc] = true;
+                machine.step();
+            }
+        }
+    }
+}
+
+// Replacing the instruction at `index` by `instruction`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Patch {
+    pub index: usize,
+    pub instruction: Instruction,
+}
+
+impl Patch {
+    pub fn apply(&self, program: &mut [Instruction]) {
+        program[self.index] = self.instruction
+    }
+}
+
+// The mutation used in day 8: swap `nop` and `jmp`, leaving `acc` alone.
+pub fn flip_nop_jmp(instruction: &Instruction) -> Vec<Instruction> {
+    match instruction.op {
+        Op::Nop => vec![Instruction::new(Op::Jmp, instruction.arg)],
+        Op::Jmp => vec![Instruction::new(Op::Nop, instruction.arg)],
+        Op::Acc => vec![],
+    }
+}
+
+// Another mutation: change the argument of a `jmp` or an `acc` by one, either way.
+pub fn nudge_arg(instruction: &Instruction) -> Vec<Instruction> {
+    match instruction.op {
+        Op::Nop => vec![],
+        Op::Acc | Op::Jmp => vec![
+            Instruction::new(instruction.op, instruction.arg - 1),
+            Instruction::new(instruction.op, instruction.arg + 1),
+        ],
+    }
+}
+
+// Tries every single-instruction patch proposed by `mutations`, and returns each patch for which
+// the program terminates, along with the final value of the accumulator. This is quadratic, while
+// `find_flip` only handles `flip_nop_jmp` but is linear.
+pub fn repair<F>(program: &[Instruction], mutations: F) -> Vec<(Patch, i32)>
+where
+    F: Fn(&Instruction) -> Vec<Instruction>,
+{
+    let mut patched = program.to_vec();
+    let mut result = vec![];
+    for index in 0..program.len() {
+        for instruction in mutations(&program[index]) {
+            let patch = Patch { index, instruction };
+            patch.apply(&mut patched);
+            if let Execution::Terminate(acc) = execute_until_loop(&patched) {
+                result.push((patch, acc))
+            }
+        }
+        patched[index] = program[index];
+    }
+    result
+}
+
+// Static analysis of the control-flow graph. Each instruction has a single successor, so the
+// graph is a functional graph over `0..=len`, where `len` is the end of the program.
+
+fn successor(pc: usize, instruction: &Instruction) -> i64 {
+    match instruction.op {
+        Op::Nop | Op::Acc => pc as i64 + 1,
+        Op::Jmp => pc as i64 + instruction.arg as i64,
+    }
+}
+
+fn in_program(program: &[Instruction], target: i64) -> Option<usize> {
+    if 0 <= target && target <= program.len() as i64 {
+        Some(target as usize)
+    } else {
+        None
+    }
+}
+
+// Returns, for each position in `0..=len`, whether execution starting there terminates. This is
+// the set of positions from which the end is reachable, computed by a traversal of the reversed
+// graph starting from the end.
+pub fn terminating(program: &[Instruction]) -> Vec<bool> {
+    let len = program.len();
+    let mut predecessors: Vec<Vec<usize>> = vec![vec![]; len + 1];
+    for (pc, instruction) in program.iter().enumerate() {
+        if let Some(next) = in_program(program, successor(pc, instruction)) {
+            predecessors[next].push(pc)
+        }
+    }
+    let mut result = vec![false; len + 1];
+    result[len] = true;
+    let mut todo = vec![len];
+    while let Some(pc) = todo.pop() {
+        for &p in predecessors[pc].iter() {
+            if !result[p] {
+                result[p] = true;
+                todo.push(p)
+            }
+        }
+    }
+    result
+}
+
+// Finds a `nop`/`jmp` flip that makes a looping (or faulting) program terminate, in linear time,
+// and returns it with the final value of the accumulator.
+//
+// Only instructions executed by the original program matter, and the patch is correct iff the
+// new successor of the flipped instruction is terminating. Flipping does not change the
+// terminating set, because the path from the new successor to the end cannot go through the
+// flipped instruction: otherwise the original program would terminate.
+pub fn find_flip(program: &[Instruction]) -> Option<(Patch, i32)> {
+    let terminating = terminating(program);
+    if terminating[0] {
+        return None;
+    }
+    let mut visited = vec![false; program.len()];
+    let mut pc = 0;
+    while pc < program.len() && !visited[pc] {
+        visited[pc] = true;
+        for instruction in flip_nop_jmp(&program[pc]) {
+            match in_program(program, successor(pc, &instruction)) {
+                Some(next) if terminating[next] => {
+                    let patch = Patch {
+                        index: pc,
+                        instruction,
+                    };
+                    let mut patched = program.to_vec();
+                    patch.apply(&mut patched);
+                    match execute_until_loop(&patched) {
+                        Execution::Terminate(acc) => return Some((patch, acc)),
+                        e => panic!("Patched program does not terminate: {:?}", e),
+                    }
+                }
+                _ => {}
+            }
+        }
+        match in_program(program, successor(pc, &program[pc])) {
+            Some(next) => pc = next,
+            None => return None,
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph::rng::Rng;
+
+    const EXAMPLE: &str = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+    #[test]
+    fn test_parse() {
+        let program = parse(EXAMPLE).unwrap();
+        assert_eq!(program.len(), 9);
+        assert_eq!(program[5], Instruction::new(Op::Acc, -99));
+        assert!(parse("nop +0\nmul +2").unwrap_err().starts_with("line 2:"));
+        assert!(parse("jmp").is_err());
+        assert!(parse("acc 1 2").is_err());
+    }
+
+    #[test]
+    fn test_loop_and_repair() {
+        let program = parse(EXAMPLE).unwrap();
+        assert_eq!(execute_until_loop(&program), Execution::Loop(5));
+        let patch = Patch {
+            index: 7,
+            instruction: Instruction::new(Op::Nop, -4),
+        };
+        assert_eq!(repair(&program, flip_nop_jmp), vec![(patch, 8)]);
+    }
+
+    #[test]
+    fn test_repair_nudge() {
+        let program = parse("acc +1\njmp +3\nacc +1\njmp +2\njmp -4").unwrap();
+        let patch = |index, arg| Patch {
+            index,
+            instruction: Instruction::new(Op::Jmp, arg),
+        };
+        assert_eq!(
+            repair(&program, nudge_arg),
+            vec![(patch(1, 2), 1), (patch(1, 4), 1)]
+        );
+    }
+
+    #[test]
+    fn test_fault() {
+        let program = parse("acc +2\njmp +5\nnop +0").unwrap();
+        assert_eq!(
+            execute_until_loop(&program),
+            Execution::Fault { pc: 6, acc: 2 }
+        );
+        let program = parse("jmp -1").unwrap();
+        assert_eq!(
+            execute_until_loop(&program),
+            Execution::Fault { pc: -1, acc: 0 }
+        );
+    }
+
+    #[test]
+    fn test_find_flip() {
+        let program = parse(EXAMPLE).unwrap();
+        let terminating = terminating(&program);
+        assert_eq!(
+            terminating,
+            vec![false, false, false, false, false, false, false, false, true, true]
+        );
+        assert_eq!(find_flip(&program), repair(&program, flip_nop_jmp).pop());
+    }
+
+    // Cross-checks the static analysis against the brute-force search on pseudo-random programs.
+    #[test]
+    fn test_find_flip_random() {
+        let mut rng = Rng::new(42);
+        for _ in 0..500 {
+            let len = 1 + rng.below(20) as i32;
+            let program: Program = (0..len)
+                .map(|_| {
+                    let op = [Op::Nop, Op::Acc, Op::Jmp][rng.below(3) as usize];
+                    Instruction::new(op, rng.below(2 * len as u64 + 1) as i32 - len)
+                })
+                .collect();
+            let solutions = repair(&program, flip_nop_jmp);
+            match (execute_until_loop(&program), find_flip(&program)) {
+                (Execution::Terminate(_), result) => assert_eq!(result, None),
+                (_, None) => assert!(solutions.is_empty(), "{:?}", program),
+                (_, Some(result)) => assert!(solutions.contains(&result), "{:?}", program),
+            }
+        }
+    }
+}
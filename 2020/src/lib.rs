@@ -0,0 +1,73 @@
+// The solutions of Advent of Code 2020, run through the `aoc` command line.
+
+#![allow(unused_imports)]
+
+use aoc::Registry;
+
+#[macro_use]
+extern crate scan_fmt;
+
+#[macro_use]
+extern crate lazy_static;
+
+mod day1;
+mod day2;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+mod day7;
+mod day8;
+mod day9;
+//
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day17;
+mod day18;
+mod day19;
+//
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
+
+// The console of day 8, with a search for the patches that repair a program.
+pub mod handheld;
+
+const YEAR: u32 = 2020;
+
+// All the solutions of this year.
+pub fn register(registry: &mut Registry) {
+    registry.add(YEAR, 1, day1::Day);
+    registry.add(YEAR, 2, day2::Day);
+    registry.add(YEAR, 3, day3::Day);
+    registry.add(YEAR, 4, day4::Day);
+    registry.add(YEAR, 5, day5::Day);
+    registry.add(YEAR, 6, day6::Day);
+    registry.add(YEAR, 7, day7::Day);
+    registry.add(YEAR, 8, day8::Day);
+    registry.add(YEAR, 9, day9::Day);
+    registry.add(YEAR, 10, day10::Day);
+    registry.add(YEAR, 11, day11::Day);
+    registry.add(YEAR, 12, day12::Day);
+    registry.add(YEAR, 13, day13::Day);
+    registry.add(YEAR, 14, day14::Day);
+    registry.add(YEAR, 15, day15::Day);
+    registry.add(YEAR, 16, day16::Day);
+    registry.add(YEAR, 17, day17::Day);
+    registry.add(YEAR, 18, day18::Day);
+    registry.add(YEAR, 19, day19::Day);
+    registry.add(YEAR, 20, day20::Day);
+    registry.add(YEAR, 21, day21::Day);
+    registry.add(YEAR, 22, day22::Day);
+    registry.add(YEAR, 23, day23::Day);
+    registry.add(YEAR, 24, day24::Day);
+    registry.add(YEAR, 25, day25::Day);
+}
@@ -0,0 +1,98 @@
+use std::fmt;
+
+// The answer to one part of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+    // A picture, one string per row, for the letters that could not be recognized.
+    Grid(Vec<String>),
+    // The second part of the last day, which is given for free.
+    Nothing,
+}
+
+impl Answer {
+    // A grid rendered as lines of text, e.g. by `geometry::Grid::render`.
+    pub fn grid(rendered: &str) -> Answer {
+        Answer::Grid(rendered.lines().map(|row| row.to_string()).collect())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+            Answer::Nothing => write!(f, "-"),
+        }
+    }
+}
+
+macro_rules! from_small_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Int(i64::from(n))
+                }
+            }
+        )*
+    };
+}
+
+from_small_int!(i8, i16, i32, i64, u8, u16, u32);
+
+// Integers that may not fit in an `i64` are kept as text.
+macro_rules! from_large_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    match i64::try_from(n) {
+                        Ok(n) => Answer::Int(n),
+                        Err(_) => Answer::Text(n.to_string()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_large_int!(u64, i128, u128, isize, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Answer::from(42u8), Answer::Int(42));
+        assert_eq!(Answer::from(-3i32), Answer::Int(-3));
+        assert_eq!(Answer::from(7usize), Answer::Int(7));
+        assert_eq!(
+            Answer::from(u64::MAX),
+            Answer::Text("18446744073709551615".to_string())
+        );
+        assert_eq!(Answer::from("ABC"), Answer::Text("ABC".to_string()));
+        assert_eq!(Answer::from(-12i64).to_string(), "-12");
+        assert_eq!(Answer::Nothing.to_string(), "-");
+        assert_eq!(
+            Answer::grid("#.#\n.#.\n"),
+            Answer::Grid(vec!["#.#".to_string(), ".#.".to_string()])
+        );
+        assert_eq!(Answer::grid("#.#\n.#.\n").to_string(), "#.#\n.#.");
+    }
+}
@@ -0,0 +1,156 @@
+// The answers that are known to be correct, to check that the solutions still find them. They
+// are stored in a text file, with one answer per line: `YEAR DAY PART ANSWER`, e.g.
+// `2019 8 2 HZCZU`. Newlines and backslashes in answers are escaped as `\n` and `\\`.
+
+use crate::Answer;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<(u32, u32, u32), String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    Correct,
+    Wrong { expected: String },
+    // There is no recorded answer.
+    Unknown,
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut result = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                result.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                result.push('\\');
+                chars.next();
+            }
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+impl Answers {
+    pub fn new() -> Answers {
+        Answers {
+            answers: BTreeMap::new(),
+        }
+    }
+
+    // Blank lines and lines starting with `#` are ignored.
+    pub fn parse(s: &str) -> Result<Answers, String> {
+        let mut answers = Answers::new();
+        for (i, line) in s.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.splitn(4, ' ').collect();
+            let key = match fields[..] {
+                [year, day, part, _] => (year.parse(), day.parse(), part.parse()),
+                _ => return Err(format!("line {}: expected YEAR DAY PART ANSWER", i + 1)),
+            };
+            match key {
+                (Ok(year), Ok(day), Ok(part @ (1 | 2))) => {
+                    answers
+                        .answers
+                        .insert((year, day, part), unescape(fields[3]));
+                }
+                _ => return Err(format!("line {}: invalid year, day or part", i + 1)),
+            }
+        }
+        Ok(answers)
+    }
+
+    // A missing file has no answers.
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        if !path.exists() {
+            return Ok(Answers::new());
+        }
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read '{}': {}", path.display(), e))?;
+        Answers::parse(&contents).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        std::fs::write(path, self.to_string())
+            .map_err(|e| format!("Cannot write '{}': {}", path.display(), e))
+    }
+
+    pub fn get(&self, year: u32, day: u32, part: u32) -> Option<&str> {
+        self.answers.get(&(year, day, part)).map(|s| s.as_str())
+    }
+
+    // There is nothing to record for parts without an answer.
+    pub fn insert(&mut self, year: u32, day: u32, part: u32, answer: &Answer) {
+        if *answer != Answer::Nothing {
+            self.answers.insert((year, day, part), answer.to_string());
+        }
+    }
+
+    pub fn check(&self, year: u32, day: u32, part: u32, answer: &Answer) -> Check {
+        match self.get(year, day, part) {
+            // Parts without an answer (e.g. the second part of the last day) are never recorded.
+            None if *answer == Answer::Nothing => Check::Correct,
+            None => Check::Unknown,
+            Some(expected) if expected == answer.to_string() => Check::Correct,
+            Some(expected) => Check::Wrong {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for ((year, day, part), answer) in self.answers.iter() {
+            writeln!(f, "{} {} {} {}", year, day, part, escape(answer))?
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answers() {
+        let s = "# Some answers\n2019 8 2 HZCZU\n\n2018 1 1 445\n2018 13 1 8,3\n";
+        let mut answers = Answers::parse(s).unwrap();
+        assert_eq!(answers.get(2019, 8, 2), Some("HZCZU"));
+        assert_eq!(answers.check(2018, 1, 1, &445.into()), Check::Correct);
+        assert_eq!(
+            answers.check(2018, 1, 1, &446.into()),
+            Check::Wrong {
+                expected: "445".to_string()
+            }
+        );
+        assert_eq!(answers.check(2018, 1, 2, &219.into()), Check::Unknown);
+        assert_eq!(answers.check(2018, 25, 2, &Answer::Nothing), Check::Correct);
+
+        answers.insert(2018, 25, 1, &"a b\\c\nd".into());
+        answers.insert(2018, 25, 2, &Answer::Nothing);
+        let s = answers.to_string();
+        assert_eq!(
+            s,
+            "2018 1 1 445\n2018 13 1 8,3\n2018 25 1 a b\\\\c\\nd\n2019 8 2 HZCZU\n"
+        );
+        assert_eq!(Answers::parse(&s), Ok(answers));
+
+        assert!(Answers::parse("2018 1 3 445").is_err());
+        assert!(Answers::parse("2018 1").is_err());
+    }
+}
@@ -0,0 +1,126 @@
+// The examples given in the puzzles, with their expected answers. They are declared next to each
+// solution with the `examples!` macro, in its `impl Solution`:
+//
+//   aoc::examples! {
+//       "1721\n979\n366" => part1: 514579, part2: 241861950;
+//       EXAMPLE => part2: 8;
+//   }
+//
+// Only the parts that are given run on an example, since the other one may not apply to it.
+
+use crate::{Answer, Solver};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Example {
+    pub input: &'static str,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Example {
+    pub fn new(input: &'static str) -> Example {
+        Example {
+            input,
+            part1: None,
+            part2: None,
+        }
+    }
+
+    pub fn part1<A: Into<Answer>>(self, answer: A) -> Example {
+        Example {
+            part1: Some(answer.into()),
+            ..self
+        }
+    }
+
+    pub fn part2<A: Into<Answer>>(self, answer: A) -> Example {
+        Example {
+            part2: Some(answer.into()),
+            ..self
+        }
+    }
+
+    // Runs the solution on the example, and describes the parts whose answer is wrong.
+    pub fn check(&self, solver: &dyn Solver) -> Result<(), String> {
+        let parsed = solver.parse(self.input)?;
+        let mut errors = vec![];
+        for (part, expected) in (1..).zip([&self.part1, &self.part2]) {
+            let expected = match expected {
+                Some(expected) => expected,
+                None => continue,
+            };
+            let answer = if part == 1 {
+                parsed.part1()
+            } else {
+                parsed.part2()
+            };
+            if answer != *expected {
+                errors.push(format!(
+                    "part {}: expected {}, got {}",
+                    part, expected, answer
+                ))
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! examples {
+    ($($input:expr => $($part:ident: $answer:expr),+;)*) => {
+        fn examples(&self) -> Vec<$crate::Example> {
+            vec![$($crate::Example::new($input)$(.$part($answer))+),*]
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solution;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<i64>;
+
+        fn parse(&self, input: &str) -> Result<Vec<i64>, String> {
+            input
+                .split(',')
+                .map(|n| n.parse().map_err(|e| format!("{}: {}", n, e)))
+                .collect()
+        }
+
+        fn part1(&self, numbers: &Vec<i64>) -> Answer {
+            numbers.iter().sum::<i64>().into()
+        }
+
+        fn part2(&self, numbers: &Vec<i64>) -> Answer {
+            numbers.iter().product::<i64>().into()
+        }
+
+        examples! {
+            "1,2,3" => part1: 6, part2: 6;
+            "2,5" => part2: 10;
+        }
+    }
+
+    #[test]
+    fn test_examples() {
+        let examples = Solution::examples(&Sum);
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[1].part1, None);
+        assert!(examples.iter().all(|example| example.check(&Sum).is_ok()));
+
+        let wrong = Example::new("2,5").part1(8).part2(11);
+        assert_eq!(
+            wrong.check(&Sum),
+            Err("part 1: expected 8, got 7; part 2: expected 11, got 10".to_string())
+        );
+        assert!(Example::default().check(&Sum).is_err());
+    }
+}
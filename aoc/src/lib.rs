@@ -0,0 +1,14 @@
+// The common interface of the solutions of all years, so that they can be enumerated, run and
+// tested uniformly.
+
+pub mod answer;
+pub mod answers;
+pub mod example;
+pub mod registry;
+pub mod solution;
+
+pub use answer::Answer;
+pub use answers::{Answers, Check};
+pub use example::Example;
+pub use registry::{parse_day, Registry};
+pub use solution::{Parsed, Solution, Solver};
@@ -0,0 +1,129 @@
+// The solutions of all the days of all years, keyed by (year, day).
+
+use crate::Solver;
+use std::collections::BTreeMap;
+
+#[derive(Default)]
+pub struct Registry {
+    solutions: BTreeMap<(u32, u32), Box<dyn Solver>>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry {
+            solutions: BTreeMap::new(),
+        }
+    }
+
+    pub fn add<S: Solver + 'static>(&mut self, year: u32, day: u32, solution: S) {
+        assert!((1..=25).contains(&day), "Invalid day {}", day);
+        let previous = self.solutions.insert((year, day), Box::new(solution));
+        assert!(
+            previous.is_none(),
+            "Day {} of {} is registered twice",
+            day,
+            year
+        );
+    }
+
+    pub fn get(&self, year: u32, day: u32) -> Option<&dyn Solver> {
+        self.solutions.get(&(year, day)).map(|s| s.as_ref())
+    }
+
+    // The years with at least one solution, in increasing order.
+    pub fn years(&self) -> Vec<u32> {
+        let mut years: Vec<u32> = self.solutions.keys().map(|&(year, _)| year).collect();
+        years.dedup();
+        years
+    }
+
+    // The days of `year` that have a solution, in increasing order.
+    pub fn days(&self, year: u32) -> Vec<u32> {
+        self.solutions
+            .range((year, 0)..(year + 1, 0))
+            .map(|(&(_, day), _)| day)
+            .collect()
+    }
+
+    // All the solutions, by year and then by day.
+    pub fn iter(&self) -> impl Iterator<Item = ((u32, u32), &dyn Solver)> {
+        self.solutions.iter().map(|(&key, s)| (key, s.as_ref()))
+    }
+}
+
+// Days are written either `7` or `07`.
+pub fn parse_day(s: &str) -> Result<u32, String> {
+    match s.parse() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!(
+            "Invalid day '{}', expected a number from 1 to 25",
+            s
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Answer, Solution};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<i64>;
+
+        fn parse(&self, input: &str) -> Result<Vec<i64>, String> {
+            input
+                .split_whitespace()
+                .map(|n| n.parse().map_err(|e| format!("{}: {}", n, e)))
+                .collect()
+        }
+
+        fn part1(&self, input: &Vec<i64>) -> Answer {
+            input.iter().sum::<i64>().into()
+        }
+
+        fn part2(&self, input: &Vec<i64>) -> Answer {
+            input.iter().map(|n| n * n).sum::<i64>().into()
+        }
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = Registry::new();
+        registry.add(2019, 3, Sum);
+        registry.add(2018, 25, Sum);
+        registry.add(2019, 1, Sum);
+        assert_eq!(registry.years(), vec![2018, 2019]);
+        assert_eq!(registry.days(2019), vec![1, 3]);
+        assert!(registry.days(2020).is_empty());
+        let keys: Vec<_> = registry.iter().map(|(key, _)| key).collect();
+        assert_eq!(keys, vec![(2018, 25), (2019, 1), (2019, 3)]);
+
+        let solution = registry.get(2019, 3).unwrap();
+        assert_eq!(
+            solution.run("1 2\n3\n"),
+            Ok([Answer::Int(6), Answer::Int(14)])
+        );
+        assert!(solution.run("1 x").is_err());
+        assert!(registry.get(2019, 2).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_duplicate() {
+        let mut registry = Registry::new();
+        registry.add(2019, 3, Sum);
+        registry.add(2019, 3, Sum);
+    }
+
+    #[test]
+    fn test_parse_day() {
+        assert_eq!(parse_day("7"), Ok(7));
+        assert_eq!(parse_day("07"), Ok(7));
+        assert_eq!(parse_day("25"), Ok(25));
+        assert!(parse_day("0").is_err());
+        assert!(parse_day("26").is_err());
+        assert!(parse_day("seven").is_err());
+    }
+}
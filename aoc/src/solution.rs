@@ -0,0 +1,70 @@
+use crate::{Answer, Example};
+
+// The solution of a puzzle: the input is parsed once, and then shared by both parts.
+//
+// `parse` reports the invalid inputs that it detects as errors, but it may also panic on them, as
+// many older days do. So may the parts. The commands that run several days catch these panics,
+// and report them as failures of the day.
+pub trait Solution {
+    type Input;
+    fn parse(&self, input: &str) -> Result<Self::Input, String>;
+    fn part1(&self, input: &Self::Input) -> Answer;
+    fn part2(&self, input: &Self::Input) -> Answer;
+
+    // The examples of the puzzle, usually declared with `examples!`.
+    fn examples(&self) -> Vec<Example> {
+        vec![]
+    }
+}
+
+// The parsed input of a solution, whatever its type.
+pub trait Parsed {
+    fn part1(&self) -> Answer;
+    fn part2(&self) -> Answer;
+}
+
+// Solutions of any input type, so that they can be stored together in a `Registry`. This is
+// implemented for every `Solution`.
+pub trait Solver: Send + Sync {
+    fn parse<'a>(&'a self, input: &str) -> Result<Box<dyn Parsed + 'a>, String>;
+
+    fn examples(&self) -> Vec<Example>;
+
+    fn run(&self, input: &str) -> Result<[Answer; 2], String> {
+        let parsed = self.parse(input)?;
+        Ok([parsed.part1(), parsed.part2()])
+    }
+}
+
+struct WithInput<'a, S: Solution> {
+    solution: &'a S,
+    input: S::Input,
+}
+
+impl<'a, S: Solution> Parsed for WithInput<'a, S> {
+    fn part1(&self) -> Answer {
+        self.solution.part1(&self.input)
+    }
+
+    fn part2(&self) -> Answer {
+        self.solution.part2(&self.input)
+    }
+}
+
+impl<S> Solver for S
+where
+    S: Solution + Send + Sync,
+    S::Input: 'static,
+{
+    fn parse<'a>(&'a self, input: &str) -> Result<Box<dyn Parsed + 'a>, String> {
+        let input = Solution::parse(self, input)?;
+        Ok(Box::new(WithInput {
+            solution: self,
+            input,
+        }))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        Solution::examples(self)
+    }
+}
@@ -0,0 +1,299 @@
+// Measures the time taken to parse the input of a day and to solve each of its parts. Each day is
+// run a few times to warm up, and then repeatedly: the median of the runs is reported. The results
+// are appended to `bench_history.txt` (not versioned), to compare them with the previous runs.
+
+use crate::{catch_panic, find_input, root};
+use aoc::Registry;
+use prettytable::format::{consts, Alignment};
+use prettytable::{Cell, Row, Table};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+
+pub struct Options {
+    pub warmup: usize,
+    pub runs: usize,
+    // Days that are slow are not repeated once they have been measured for this long.
+    pub budget: Duration,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            warmup: 1,
+            runs: 10,
+            budget: Duration::from_secs(5),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+// One line of the history: `TIMESTAMP COMMIT YEAR DAY PARSE PART1 PART2`, in nanoseconds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    pub timestamp: u64,
+    pub commit: String,
+    pub year: u32,
+    pub day: u32,
+    pub timings: Timings,
+}
+
+impl Record {
+    fn parse(line: &str) -> Option<Record> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields[..] {
+            [timestamp, commit, year, day, parse, part1, part2] => {
+                let nanos = |s: &str| s.parse().ok().map(Duration::from_nanos);
+                Some(Record {
+                    timestamp: timestamp.parse().ok()?,
+                    commit: commit.to_string(),
+                    year: year.parse().ok()?,
+                    day: day.parse().ok()?,
+                    timings: Timings {
+                        parse: nanos(parse)?,
+                        part1: nanos(part1)?,
+                        part2: nanos(part2)?,
+                    },
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{} {} {} {} {} {} {}",
+            self.timestamp,
+            self.commit,
+            self.year,
+            self.day,
+            self.timings.parse.as_nanos(),
+            self.timings.part1.as_nanos(),
+            self.timings.part2.as_nanos()
+        )
+    }
+}
+
+pub fn history_path() -> PathBuf {
+    root().join("bench_history.txt")
+}
+
+// The last recorded timings of each day. Invalid lines are ignored.
+fn load_history() -> BTreeMap<(u32, u32), Record> {
+    let contents = std::fs::read_to_string(history_path()).unwrap_or_default();
+    contents
+        .lines()
+        .filter_map(Record::parse)
+        .map(|record| ((record.year, record.day), record))
+        .collect()
+}
+
+fn save_history(records: &[Record]) -> Result<(), String> {
+    let path = history_path();
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Cannot open '{}': {}", path.display(), e))?;
+    for record in records.iter() {
+        writeln!(file, "{}", record.to_line())
+            .map_err(|e| format!("Cannot write '{}': {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+// The commit that is measured, when the repository is available.
+fn commit() -> String {
+    std::process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(root())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn median(mut durations: Vec<Duration>) -> Duration {
+    durations.sort();
+    durations[durations.len() / 2]
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+pub fn bench_day(
+    registry: &Registry,
+    year: u32,
+    day: u32,
+    options: &Options,
+) -> Result<Timings, String> {
+    let solution = registry
+        .get(year, day)
+        .ok_or(format!("Day {} of {} is not solved", day, year))?;
+    let path = find_input(year, day).ok_or(format!("No input for day {} in {}/data", day, year))?;
+    let input = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Cannot read '{}': {}", path.display(), e))?;
+
+    let mut runs = vec![];
+    let start = Instant::now();
+    for i in 0..options.warmup + options.runs.max(1) {
+        let (parsed, parse) = time(|| solution.parse(&input));
+        let parsed = parsed?;
+        let (_, part1) = time(|| parsed.part1());
+        let (_, part2) = time(|| parsed.part2());
+        if i >= options.warmup {
+            runs.push(Timings {
+                parse,
+                part1,
+                part2,
+            })
+        }
+        if !runs.is_empty() && start.elapsed() > options.budget {
+            break;
+        }
+    }
+    Ok(Timings {
+        parse: median(runs.iter().map(|t| t.parse).collect()),
+        part1: median(runs.iter().map(|t| t.part1).collect()),
+        part2: median(runs.iter().map(|t| t.part2).collect()),
+    })
+}
+
+pub fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos() as f64;
+    if nanos < 1e3 {
+        format!("{} ns", nanos)
+    } else if nanos < 1e6 {
+        format!("{:.1} µs", nanos / 1e3)
+    } else if nanos < 1e9 {
+        format!("{:.1} ms", nanos / 1e6)
+    } else {
+        format!("{:.2} s", nanos / 1e9)
+    }
+}
+
+// The relative change from `before` to `after`, e.g. `+12%`.
+fn format_change(before: Duration, after: Duration) -> String {
+    let before = before.as_secs_f64();
+    if before == 0.0 {
+        return String::new();
+    }
+    format!("{:+.0}%", (after.as_secs_f64() - before) / before * 100.0)
+}
+
+fn right(s: &str) -> Cell {
+    Cell::new_align(s, Alignment::RIGHT)
+}
+
+// Measures the given days, prints a table of their timings along with the previous ones, and
+// records them in the history. Returns false if some days failed.
+pub fn bench(registry: &Registry, days: &[(u32, u32)], options: &Options) -> Result<bool, String> {
+    let history = load_history();
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let commit = commit();
+
+    let mut table = Table::new();
+    table.set_format(*consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    table.set_titles(Row::new(
+        [
+            "Year", "Day", "Parse", "Part 1", "Part 2", "Total", "Previous", "Change",
+        ]
+        .iter()
+        .map(|title| Cell::new(title))
+        .collect(),
+    ));
+    let mut records = vec![];
+    let mut total = Duration::ZERO;
+    let mut ok = true;
+    for &(year, day) in days.iter() {
+        let timings = match catch_panic(|| bench_day(registry, year, day, options)) {
+            Ok(timings) => timings,
+            Err(e) => {
+                eprintln!("error: {} {:02}: {}", year, day, e);
+                ok = false;
+                continue;
+            }
+        };
+        let (previous, change) = match history.get(&(year, day)) {
+            Some(record) => (
+                format!(
+                    "{} ({})",
+                    format_duration(record.timings.total()),
+                    record.commit
+                ),
+                format_change(record.timings.total(), timings.total()),
+            ),
+            None => (String::new(), String::new()),
+        };
+        table.add_row(Row::new(vec![
+            Cell::new(&year.to_string()),
+            right(&day.to_string()),
+            right(&format_duration(timings.parse)),
+            right(&format_duration(timings.part1)),
+            right(&format_duration(timings.part2)),
+            right(&format_duration(timings.total())),
+            right(&previous),
+            right(&change),
+        ]));
+        total += timings.total();
+        records.push(Record {
+            timestamp,
+            commit: commit.clone(),
+            year,
+            day,
+            timings,
+        });
+    }
+    table.printstd();
+    println!("Total: {}", format_duration(total));
+    save_history(&records)?;
+    Ok(ok)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let line = "1671000000 968cdc3 2019 7 1200 35000 2000000";
+        let record = Record::parse(line).unwrap();
+        assert_eq!(record.commit, "968cdc3");
+        assert_eq!((record.year, record.day), (2019, 7));
+        assert_eq!(record.timings.total(), Duration::from_nanos(2036200));
+        assert_eq!(record.to_line(), line);
+        assert_eq!(Record::parse("1671000000 968cdc3 2019 7"), None);
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(format_duration(Duration::from_nanos(512)), "512 ns");
+        assert_eq!(format_duration(Duration::from_micros(1300)), "1.3 ms");
+        assert_eq!(format_duration(Duration::from_millis(2500)), "2.50 s");
+        assert_eq!(
+            format_change(Duration::from_millis(10), Duration::from_millis(12)),
+            "+20%"
+        );
+    }
+}
@@ -0,0 +1,82 @@
+// The JSON lines output of `aoc run --json`, for scripts: one object per part, e.g.
+//
+//   {"year":2019,"day":8,"part":2,"answer":"HZCZU","duration_ns":10532}
+//
+// Integer answers are numbers, text answers are strings, grids are arrays of rows, and missing
+// answers are null. A day that fails gives a single object with its error instead.
+
+use aoc::Answer;
+use std::time::Duration;
+
+fn string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Int(n) => n.to_string(),
+        Answer::Text(s) => string(s),
+        Answer::Grid(rows) => {
+            let rows: Vec<String> = rows.iter().map(|row| string(row)).collect();
+            format!("[{}]", rows.join(","))
+        }
+        Answer::Nothing => "null".to_string(),
+    }
+}
+
+pub fn part(year: u32, day: u32, part: u32, result: &Answer, duration: Duration) -> String {
+    format!(
+        "{{\"year\":{},\"day\":{},\"part\":{},\"answer\":{},\"duration_ns\":{}}}",
+        year,
+        day,
+        part,
+        answer(result),
+        duration.as_nanos()
+    )
+}
+
+pub fn error(year: u32, day: u32, error: &str) -> String {
+    format!(
+        "{{\"year\":{},\"day\":{},\"error\":{}}}",
+        year,
+        day,
+        string(error)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            part(2019, 8, 2, &"HZCZU".into(), Duration::from_micros(12)),
+            r#"{"year":2019,"day":8,"part":2,"answer":"HZCZU","duration_ns":12000}"#
+        );
+        assert_eq!(
+            part(2018, 1, 1, &(-445).into(), Duration::ZERO),
+            r#"{"year":2018,"day":1,"part":1,"answer":-445,"duration_ns":0}"#
+        );
+        assert_eq!(answer(&Answer::Nothing), "null");
+        assert_eq!(answer(&Answer::grid("#.\n.#")), r##"["#.",".#"]"##);
+        assert_eq!(
+            error(2020, 15, "Cannot read 'a\"b'\n"),
+            r#"{"year":2020,"day":15,"error":"Cannot read 'a\"b'\n"}"#
+        );
+    }
+}
@@ -0,0 +1,412 @@
+// The command line to run the solutions of all years:
+//
+//   aoc run YEAR DAY [INPUT] runs a day on an input file, `-` for stdin, or its input in
+//                            YEAR/data/ by default
+//   aoc run [YEAR] --all     runs all the days, or those of a year, on their inputs in YEAR/data/
+//                            in parallel (see `parallel`)
+//   aoc verify [YEAR [DAY]]  checks the answers against the recorded ones (see `verify`), or
+//                            against the examples of the puzzles with `--examples`
+//   aoc bench [YEAR [DAY]]   measures the time taken by each part (see `bench`)
+//   aoc new YEAR DAY         creates the skeleton of a new day, or year (see `scaffold`)
+//
+// With `--input-str INPUT`, `run` takes the input itself, e.g. `aoc run 2018 14 --input-str 9`.
+// With `--json`, it prints JSON lines with the answers and their durations instead. With `--all`,
+// `--jobs N` sets the number of threads, and `--timeout SECS` the time given to each day.
+
+use aoc::{parse_day, Answer, Registry, Solver};
+use std::io::Read;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+mod bench;
+mod json;
+mod parallel;
+mod scaffold;
+mod verify;
+
+const USAGE: &str = "Usage:
+    aoc run YEAR DAY [INPUT | - | --input-str INPUT] [--json]
+    aoc run [YEAR] --all [--jobs N] [--timeout SECS] [--json]
+    aoc verify [YEAR [DAY]] [--record [--overwrite] | --examples]
+    aoc bench [YEAR [DAY]] [--runs N] [--warmup N]
+    aoc new YEAR DAY";
+
+// Where the input of a day is read from.
+enum Input {
+    File(PathBuf),
+    Stdin,
+    Inline(String),
+}
+
+impl Input {
+    fn read(&self) -> Result<String, String> {
+        match self {
+            Input::File(path) => std::fs::read_to_string(path)
+                .map_err(|e| format!("Cannot read '{}': {}", path.display(), e)),
+            Input::Stdin => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| format!("Cannot read the standard input: {}", e))?;
+                Ok(input)
+            }
+            Input::Inline(input) => Ok(input.clone()),
+        }
+    }
+}
+
+enum Error {
+    // The command line is invalid: the usage is printed along with the message.
+    Usage(String),
+    Failed(String),
+}
+
+fn registry() -> Registry {
+    let mut registry = Registry::new();
+    advent_of_code_2018::register(&mut registry);
+    advent_of_code_2019::register(&mut registry);
+    advent_of_code_2020::register(&mut registry);
+    registry
+}
+
+// The root of the repository, with one directory per year.
+fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+// The input of a day in the data directory of its year: either `day_07.txt` or `7.txt`.
+fn find_input(year: u32, day: u32) -> Option<PathBuf> {
+    let data = root().join(year.to_string()).join("data");
+    [format!("day_{:02}.txt", day), format!("{}.txt", day)]
+        .iter()
+        .map(|name| data.join(name))
+        .find(|path| path.is_file())
+}
+
+fn parse_year(registry: &Registry, s: &str) -> Result<u32, Error> {
+    let years = registry.years();
+    match s.parse() {
+        Ok(year) if years.contains(&year) => Ok(year),
+        _ => {
+            let years: Vec<String> = years.iter().map(|y| y.to_string()).collect();
+            Err(Error::Usage(format!(
+                "Invalid year '{}', expected one of {}",
+                s,
+                years.join(", ")
+            )))
+        }
+    }
+}
+
+// Runs `f`, and turns a panic into an error with its message (see `aoc::Solution`). The message
+// is also printed by the panic hook, with its location.
+fn catch_panic<T>(f: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = match (
+            payload.downcast_ref::<&str>(),
+            payload.downcast_ref::<String>(),
+        ) {
+            (Some(message), _) => message.to_string(),
+            (_, Some(message)) => message.clone(),
+            (None, None) => "unknown".to_string(),
+        };
+        Err(format!("panicked: {}", message))
+    })
+}
+
+// Parses the input and runs both parts, which are timed.
+fn solve(solution: &dyn Solver, input: &str) -> Result<Vec<(Answer, Duration)>, String> {
+    let parsed = solution.parse(input)?;
+    Ok([1, 2]
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = if part == 1 {
+                parsed.part1()
+            } else {
+                parsed.part2()
+            };
+            (answer, start.elapsed())
+        })
+        .collect())
+}
+
+// Prints the answers of a day, or their JSON lines (see `json`).
+fn print_answers(year: u32, day: u32, answers: &[(Answer, Duration)], json: bool) {
+    for (part, (answer, duration)) in (1..).zip(answers) {
+        if json {
+            println!("{}", json::part(year, day, part, answer, *duration))
+        } else {
+            println!("{}", answer)
+        }
+    }
+}
+
+// Runs a day on its input, and prints the answers.
+fn run_day(
+    registry: &Registry,
+    year: u32,
+    day: u32,
+    input: &Input,
+    json: bool,
+) -> Result<(), String> {
+    let solution = registry
+        .get(year, day)
+        .ok_or(format!("Day {} of {} is not solved", day, year))?;
+    let answers = solve(solution, &input.read()?)?;
+    print_answers(year, day, &answers, json);
+    Ok(())
+}
+
+// Runs all the given days on their inputs in parallel, and keeps going when some of them fail.
+// The answers are printed in order.
+fn run_all(
+    registry: &Arc<Registry>,
+    days: &[(u32, u32)],
+    threads: usize,
+    budget: Duration,
+    json: bool,
+) -> Result<(), Error> {
+    let jobs = days
+        .iter()
+        .map(|&(year, day)| parallel::Job {
+            year,
+            day,
+            input: find_input(year, day).map(Input::File),
+        })
+        .collect();
+    let mut failed = vec![];
+    let mut timed_out = vec![];
+    parallel::run(registry.clone(), jobs, threads, budget, |job, outcome| {
+        let (year, day) = (job.year, job.day);
+        if !json {
+            println!("# {} {:02}", year, day);
+        }
+        match outcome {
+            parallel::Outcome::Passed(answers) => print_answers(year, day, answers, json),
+            parallel::Outcome::Failed(e) => {
+                if json {
+                    println!("{}", json::error(year, day, e))
+                } else {
+                    eprintln!("error: {}", e)
+                }
+                failed.push(format!("{} {:02}", year, day))
+            }
+            parallel::Outcome::TimedOut => {
+                let e = format!("Timed out after {} s", budget.as_secs_f64());
+                if json {
+                    println!("{}", json::error(year, day, &e))
+                } else {
+                    eprintln!("error: {}", e)
+                }
+                timed_out.push(format!("{} {:02}", year, day))
+            }
+        }
+        if !json {
+            println!();
+        }
+    });
+    if !json {
+        println!(
+            "{} passed, {} failed, {} timed out",
+            days.len() - failed.len() - timed_out.len(),
+            failed.len(),
+            timed_out.len()
+        );
+    }
+    let mut errors = vec![];
+    if !failed.is_empty() {
+        errors.push(format!("failed: {}", failed.join(", ")))
+    }
+    if !timed_out.is_empty() {
+        errors.push(format!("timed out: {}", timed_out.join(", ")))
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Failed(format!("Some days {}", errors.join("; "))))
+    }
+}
+
+fn run(registry: &Arc<Registry>, args: &[String]) -> Result<(), Error> {
+    let mut args = args.to_vec();
+    let json = take_flag(&mut args, "--json");
+    let all = take_flag(&mut args, "--all");
+    let inline = take_option(&mut args, "--input-str")?;
+    let jobs = take_number(&mut args, "--jobs")?;
+    let timeout = take_number(&mut args, "--timeout")?;
+    if !all && (jobs.is_some() || timeout.is_some()) {
+        return Err(Error::Usage(
+            "--jobs and --timeout only apply to --all".to_string(),
+        ));
+    }
+    let (year, day, input) = match (&args[..], all, inline) {
+        ([] | [_], true, None) => {
+            let days = select_days(registry, &args)?;
+            let threads = jobs.unwrap_or_else(|| {
+                thread::available_parallelism().map_or(1, |threads| threads.get())
+            });
+            let budget = Duration::from_secs(timeout.unwrap_or(60) as u64);
+            return run_all(registry, &days, threads, budget, json);
+        }
+        ([year, day], false, Some(input)) => (year, day, Some(Input::Inline(input))),
+        ([year, day], false, None) => (year, day, None),
+        ([year, day, input], false, None) if input == "-" => (year, day, Some(Input::Stdin)),
+        ([year, day, input], false, None) => (year, day, Some(Input::File(input.into()))),
+        _ => {
+            return Err(Error::Usage(
+                "Expected a year, and a day with its input or --all".to_string(),
+            ))
+        }
+    };
+    let year = parse_year(registry, year)?;
+    let day = parse_day(day).map_err(Error::Usage)?;
+    let input = match input {
+        Some(input) => input,
+        None => find_input(year, day)
+            .map(Input::File)
+            .ok_or(Error::Failed(format!(
+                "No input for day {} in {}/data, give it as an argument",
+                day, year
+            )))?,
+    };
+    run_day(registry, year, day, &input, json).map_err(|e| {
+        if json {
+            println!("{}", json::error(year, day, &e))
+        }
+        Error::Failed(e)
+    })
+}
+
+// All the days, the days of a year, or a single day.
+fn select_days(registry: &Registry, args: &[String]) -> Result<Vec<(u32, u32)>, Error> {
+    match args {
+        [] => Ok(registry.iter().map(|(key, _)| key).collect()),
+        [year] => {
+            let year = parse_year(registry, year)?;
+            Ok(registry.days(year).iter().map(|&day| (year, day)).collect())
+        }
+        [year, day] => {
+            let year = parse_year(registry, year)?;
+            Ok(vec![(year, parse_day(day).map_err(Error::Usage)?)])
+        }
+        _ => Err(Error::Usage("Too many arguments".to_string())),
+    }
+}
+
+// Removes `name` from `args`, and tells whether it was there.
+fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    let len = args.len();
+    args.retain(|arg| arg != name);
+    args.len() != len
+}
+
+// Removes `--name VALUE` from `args`, and returns its value.
+fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>, Error> {
+    let i = match args.iter().position(|arg| arg == name) {
+        Some(i) => i,
+        None => return Ok(None),
+    };
+    if i + 1 == args.len() {
+        return Err(Error::Usage(format!("Expected a value after {}", name)));
+    }
+    let value = args.remove(i + 1);
+    args.remove(i);
+    Ok(Some(value))
+}
+
+fn take_number(args: &mut Vec<String>, name: &str) -> Result<Option<usize>, Error> {
+    match take_option(args, name)? {
+        Some(value) => match value.parse() {
+            Ok(n) => Ok(Some(n)),
+            Err(_) => Err(Error::Usage(format!("Expected a number after {}", name))),
+        },
+        None => Ok(None),
+    }
+}
+
+fn verify(registry: &Registry, args: &[String]) -> Result<(), Error> {
+    let mut args = args.to_vec();
+    let record = take_flag(&mut args, "--record");
+    let overwrite = take_flag(&mut args, "--overwrite");
+    let examples = take_flag(&mut args, "--examples");
+    if overwrite && !record {
+        return Err(Error::Usage(
+            "--overwrite only applies to --record".to_string(),
+        ));
+    }
+    let days = select_days(registry, &args)?;
+    let result = match (record, examples) {
+        (true, true) => {
+            return Err(Error::Usage(
+                "The answers of the examples cannot be recorded".to_string(),
+            ))
+        }
+        (_, true) => Ok(verify::verify_all_examples(registry, &days)),
+        (_, false) => verify::verify(registry, &days, record, overwrite),
+    };
+    match result {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(Error::Failed("Some answers are wrong".to_string())),
+        Err(e) => Err(Error::Failed(e)),
+    }
+}
+
+fn bench(registry: &Registry, args: &[String]) -> Result<(), Error> {
+    let mut args = args.to_vec();
+    let mut options = bench::Options::default();
+    if let Some(runs) = take_number(&mut args, "--runs")? {
+        options.runs = runs
+    }
+    if let Some(warmup) = take_number(&mut args, "--warmup")? {
+        options.warmup = warmup
+    }
+    let days = select_days(registry, &args)?;
+    match bench::bench(registry, &days, &options) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(Error::Failed("Some days failed".to_string())),
+        Err(e) => Err(Error::Failed(e)),
+    }
+}
+
+fn new(args: &[String]) -> Result<(), Error> {
+    match args {
+        [year, day] => {
+            let year = match year.parse() {
+                Ok(year) if year >= 2015 => year,
+                _ => return Err(Error::Usage(format!("Invalid year '{}'", year))),
+            };
+            let day = parse_day(day).map_err(Error::Usage)?;
+            scaffold::new_day(year, day).map_err(Error::Failed)
+        }
+        _ => Err(Error::Usage("Expected a year and a day".to_string())),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let registry = Arc::new(registry());
+    let result = match args.split_first() {
+        Some((command, args)) if command == "run" => run(&registry, args),
+        Some((command, args)) if command == "verify" => verify(&registry, args),
+        Some((command, args)) if command == "bench" => bench(&registry, args),
+        Some((command, args)) if command == "new" => new(args),
+        Some((command, _)) => Err(Error::Usage(format!("Unknown command '{}'", command))),
+        None => Err(Error::Usage("Missing command".to_string())),
+    };
+    match result {
+        Ok(()) => {}
+        Err(Error::Usage(e)) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            process::exit(2)
+        }
+        Err(Error::Failed(e)) => {
+            eprintln!("error: {}", e);
+            process::exit(1)
+        }
+    }
+}
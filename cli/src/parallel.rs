@@ -0,0 +1,295 @@
+// Runs many days at once on a pool of threads. A day that panics is reported as failed without
+// stopping the others, and a day that takes longer than the time budget is reported as timed
+// out: its thread cannot be stopped, so it is left behind and replaced by a new one.
+
+use crate::{solve, Input};
+use aoc::{Answer, Registry};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub struct Job {
+    pub year: u32,
+    pub day: u32,
+    // The days without an input fail.
+    pub input: Option<Input>,
+}
+
+pub enum Outcome {
+    Passed(Vec<(Answer, Duration)>),
+    Failed(String),
+    TimedOut,
+}
+
+enum Message {
+    Started(usize),
+    Finished(usize, Result<Vec<(Answer, Duration)>, String>),
+}
+
+thread_local! {
+    // Whether the thread is a worker, whose panics are reported with the outcomes.
+    static WORKER: Cell<bool> = const { Cell::new(false) };
+    // The message of the last panic of the worker, with its location.
+    static PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+type Hook = Box<dyn Fn(&panic::PanicHookInfo) + Sync + Send>;
+
+// Records the panics of the workers rather than printing them, and forwards the others to the
+// previous hook, which is restored when the guard is dropped.
+struct HookGuard(Option<Arc<Hook>>);
+
+impl HookGuard {
+    fn new() -> HookGuard {
+        let previous = Arc::new(panic::take_hook());
+        let forward = previous.clone();
+        panic::set_hook(Box::new(move |info| {
+            if WORKER.with(|worker| worker.get()) {
+                let message = info.to_string().replace('\n', " ");
+                PANIC.with(|panic| *panic.borrow_mut() = Some(message))
+            } else {
+                forward(info)
+            }
+        }));
+        HookGuard(Some(previous))
+    }
+}
+
+impl Drop for HookGuard {
+    fn drop(&mut self) {
+        // The hook cannot be changed while panicking, but ours forwards the panics of this thread.
+        if thread::panicking() {
+            return;
+        }
+        // Dropping our hook releases its reference to the previous one, unless it is running.
+        drop(panic::take_hook());
+        if let Some(previous) = self.0.take() {
+            match Arc::try_unwrap(previous) {
+                Ok(previous) => panic::set_hook(previous),
+                Err(previous) => panic::set_hook(Box::new(move |info| previous(info))),
+            }
+        }
+    }
+}
+
+fn run_job(registry: &Registry, job: &Job) -> Result<Vec<(Answer, Duration)>, String> {
+    let solution = registry
+        .get(job.year, job.day)
+        .ok_or(format!("Day {} of {} is not solved", job.day, job.year))?;
+    let input = match &job.input {
+        Some(input) => input.read()?,
+        None => return Err(format!("No input for day {} in {}/data", job.day, job.year)),
+    };
+    solve(solution, &input)
+}
+
+// Runs the jobs that are not taken yet, one at a time. Each job is settled once, either by its
+// worker when it is done, or by `run` when it times out: a worker whose job timed out stops, since
+// it was replaced in the meantime.
+fn spawn_worker(
+    registry: Arc<Registry>,
+    jobs: Arc<Vec<Job>>,
+    next: Arc<AtomicUsize>,
+    settled: Arc<Vec<AtomicBool>>,
+    sender: Sender<Message>,
+) {
+    thread::spawn(move || {
+        WORKER.with(|worker| worker.set(true));
+        loop {
+            let i = next.fetch_add(1, Ordering::SeqCst);
+            if i >= jobs.len() || sender.send(Message::Started(i)).is_err() {
+                break;
+            }
+            let result = panic::catch_unwind(AssertUnwindSafe(|| run_job(&registry, &jobs[i])))
+                .unwrap_or_else(|_| {
+                    let message = PANIC.with(|panic| panic.borrow_mut().take());
+                    Err(message.unwrap_or_else(|| "panicked".to_string()))
+                });
+            if settled[i].swap(true, Ordering::SeqCst)
+                || sender.send(Message::Finished(i, result)).is_err()
+            {
+                break;
+            }
+        }
+    });
+}
+
+// Runs the jobs on `threads` threads, and gives their outcomes to `report` in the order of the
+// jobs, as soon as they are known.
+pub fn run(
+    registry: Arc<Registry>,
+    jobs: Vec<Job>,
+    threads: usize,
+    budget: Duration,
+    mut report: impl FnMut(&Job, &Outcome),
+) {
+    // The panics of the jobs are reported with the outcomes, rather than printed when they happen.
+    let _hook = HookGuard::new();
+
+    let settled: Arc<Vec<AtomicBool>> =
+        Arc::new(jobs.iter().map(|_| AtomicBool::new(false)).collect());
+    let jobs = Arc::new(jobs);
+    let next = Arc::new(AtomicUsize::new(0));
+    let (sender, receiver) = mpsc::channel();
+    let spawn = || {
+        spawn_worker(
+            registry.clone(),
+            jobs.clone(),
+            next.clone(),
+            settled.clone(),
+            sender.clone(),
+        )
+    };
+    for _ in 0..threads.clamp(1, jobs.len().max(1)) {
+        spawn()
+    }
+
+    let mut outcomes: Vec<Option<Outcome>> = jobs.iter().map(|_| None).collect();
+    let mut started: HashMap<usize, Instant> = HashMap::new();
+    let mut reported = 0;
+    while reported < jobs.len() {
+        let now = Instant::now();
+        let timeout = started
+            .values()
+            .map(|&start| (start + budget).saturating_duration_since(now))
+            .min()
+            .unwrap_or(budget);
+        match receiver.recv_timeout(timeout) {
+            Ok(Message::Started(i)) => {
+                started.insert(i, Instant::now());
+            }
+            Ok(Message::Finished(i, result)) => {
+                started.remove(&i);
+                outcomes[i] = Some(match result {
+                    Ok(answers) => Outcome::Passed(answers),
+                    Err(e) => Outcome::Failed(e),
+                })
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => unreachable!(),
+        }
+        let now = Instant::now();
+        let timed_out: Vec<usize> = started
+            .iter()
+            .filter(|(_, &start)| now.duration_since(start) >= budget)
+            .map(|(&i, _)| i)
+            .collect();
+        // The jobs that were settled by their worker in the meantime are not timed out: their
+        // results are on the way.
+        for i in timed_out {
+            if settled[i].swap(true, Ordering::SeqCst) {
+                continue;
+            }
+            started.remove(&i);
+            outcomes[i] = Some(Outcome::TimedOut);
+            spawn()
+        }
+        while let Some(Some(outcome)) = outcomes.get(reported) {
+            report(&jobs[reported], outcome);
+            reported += 1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::Solution;
+
+    struct Day(u64);
+
+    impl Solution for Day {
+        type Input = u64;
+
+        fn parse(&self, input: &str) -> Result<u64, String> {
+            input.parse().map_err(|e| format!("{}: {}", input, e))
+        }
+
+        // Sleeps for the given number of milliseconds, and panics on 0.
+        fn part1(&self, &n: &u64) -> Answer {
+            assert!(n > 0, "zero");
+            thread::sleep(Duration::from_millis(n));
+            (n + self.0).into()
+        }
+
+        fn part2(&self, _: &u64) -> Answer {
+            Answer::Nothing
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let mut registry = Registry::new();
+        for day in 1..=6 {
+            registry.add(2020, day, Day(day as u64))
+        }
+        let inputs = ["10", "0", "x", "5000", "20"];
+        let mut jobs: Vec<Job> = (1..)
+            .zip(inputs.iter())
+            .map(|(day, input)| Job {
+                year: 2020,
+                day,
+                input: Some(Input::Inline(input.to_string())),
+            })
+            .collect();
+        jobs.push(Job {
+            year: 2020,
+            day: 6,
+            input: None,
+        });
+
+        let mut outcomes = vec![];
+        run(
+            Arc::new(registry),
+            jobs,
+            2,
+            Duration::from_millis(500),
+            |job, outcome| {
+                outcomes.push(match outcome {
+                    Outcome::Passed(answers) => format!("{} {}", job.day, answers[0].0),
+                    Outcome::Failed(e) => format!("{} failed: {}", job.day, e),
+                    Outcome::TimedOut => format!("{} timed out", job.day),
+                })
+            },
+        );
+        assert_eq!(outcomes.len(), 6);
+        assert_eq!(outcomes[0], "1 11");
+        assert!(
+            outcomes[1].starts_with("2 failed: panicked at"),
+            "{}",
+            outcomes[1]
+        );
+        assert!(outcomes[1].ends_with("zero"), "{}", outcomes[1]);
+        assert_eq!(outcomes[2], "3 failed: x: invalid digit found in string");
+        assert_eq!(outcomes[3], "4 timed out");
+        assert_eq!(outcomes[4], "5 25");
+        assert_eq!(outcomes[5], "6 failed: No input for day 6 in 2020/data");
+    }
+
+    // The panics outside of the jobs are not caught.
+    #[test]
+    fn test_report_panic() {
+        let mut registry = Registry::new();
+        registry.add(2020, 1, Day(1));
+        let jobs = vec![Job {
+            year: 2020,
+            day: 1,
+            input: Some(Input::Inline("1".to_string())),
+        }];
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            run(
+                Arc::new(registry),
+                jobs,
+                1,
+                Duration::from_secs(10),
+                |_, _| panic!("report"),
+            )
+        }));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,309 @@
+// Generates the skeleton of a new day: its module with a `Solution` to fill in, its registration
+// in the `register` function of its year, and an empty input file. The first day of a new year
+// also creates the crate of that year, and adds it to the workspace and to this command line.
+//
+// Modules are named `day_07.rs` with inputs in `data/day_07.txt`, except in the years that
+// already use `day7.rs` and `data/7.txt`.
+
+use crate::root;
+use std::path::Path;
+
+const MODULE: &str = "use aoc::{Answer, Solution};
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, String> {
+        Ok(input.lines().map(|line| line.to_string()).collect())
+    }
+
+    // No answer until the day is solved, so that none is recorded by `aoc verify --record`.
+    fn part1(&self, _lines: &Vec<String>) -> Answer {
+        Answer::Nothing
+    }
+
+    fn part2(&self, _lines: &Vec<String>) -> Answer {
+        Answer::Nothing
+    }
+
+    // The examples of the puzzle, e.g. `EXAMPLE => part1: 42, part2: 7;`.
+    aoc::examples! {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        for example in Solution::examples(&Day) {
+            assert_eq!(example.check(&Day), Ok(()));
+        }
+    }
+}
+";
+
+fn cargo_toml(year: u32) -> String {
+    format!(
+        "[package]
+name = \"advent-of-code-{}\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+[dependencies]
+aoc = {{ path = \"../aoc\" }}
+geometry = {{ path = \"../geometry\" }}
+graph = {{ path = \"../graph\" }}
+",
+        year
+    )
+}
+
+// The days are added to the empty `register` function.
+fn lib_rs(year: u32) -> String {
+    format!(
+        "// The solutions of Advent of Code {year}, run through the `aoc` command line.
+
+use aoc::Registry;
+
+const YEAR: u32 = {year};
+
+// All the solutions of this year.
+pub fn register(registry: &mut Registry) {{
+}}
+",
+        year = year
+    )
+}
+
+// The day of a `mod day_07;` or `mod day7;` line.
+fn module_day(line: &str) -> Option<u32> {
+    line.strip_prefix("mod day")?
+        .trim_start_matches('_')
+        .strip_suffix(';')?
+        .parse()
+        .ok()
+}
+
+// The day of a `registry.add(YEAR, 7, day_07::Day);` line.
+fn registered_day(line: &str) -> Option<u32> {
+    let (day, _) = line
+        .trim()
+        .strip_prefix("registry.add(YEAR, ")?
+        .split_once(',')?;
+    day.parse().ok()
+}
+
+// Inserts `line` among the lines for which `day_of` gives a day, in order. When there are none,
+// it is inserted before the first line that satisfies `otherwise`, followed by `separator`.
+fn insert_sorted(
+    s: &str,
+    day: u32,
+    line: &str,
+    day_of: impl Fn(&str) -> Option<u32>,
+    otherwise: impl Fn(&str) -> bool,
+    separator: Option<&str>,
+) -> Result<String, String> {
+    let mut lines: Vec<&str> = s.lines().collect();
+    let days: Vec<(usize, u32)> = (0..)
+        .zip(lines.iter())
+        .filter_map(|(i, l)| day_of(l).map(|d| (i, d)))
+        .collect();
+    if days.iter().any(|&(_, d)| d == day) {
+        return Err(format!("Day {} is already there", day));
+    }
+    let i = match (days.iter().rev().find(|&&(_, d)| d < day), days.first()) {
+        (Some(&(i, _)), _) => i + 1,
+        (None, Some(&(i, _))) => i,
+        (None, None) => {
+            let i = lines
+                .iter()
+                .position(|l| otherwise(l))
+                .ok_or("Cannot find where to insert the day")?;
+            if let Some(separator) = separator {
+                lines.insert(i, separator)
+            }
+            i
+        }
+    };
+    lines.insert(i, line);
+    Ok(lines.join("\n") + "\n")
+}
+
+// Declares and registers the module of a day in the `lib.rs` of its year.
+fn register_day(lib: &str, day: u32, module: &str) -> Result<String, String> {
+    let lib = insert_sorted(
+        lib,
+        day,
+        &format!("mod {};", module),
+        module_day,
+        |l| l.starts_with("const YEAR"),
+        Some(""),
+    )?;
+    insert_sorted(
+        &lib,
+        day,
+        &format!("    registry.add(YEAR, {}, {}::Day);", day, module),
+        registered_day,
+        |l| l == "}",
+        None,
+    )
+}
+
+// Adds a crate to the `members` of the workspace.
+fn add_member(workspace: &str, year: u32) -> Result<String, String> {
+    let mut found = false;
+    let lines: Vec<String> = workspace
+        .lines()
+        .map(|line| match line.strip_prefix("members = [") {
+            Some(members) if !found => {
+                found = true;
+                let members = members.trim_end_matches(']');
+                format!("members = [{}, \"{}\"]", members, year)
+            }
+            _ => line.to_string(),
+        })
+        .collect();
+    if found {
+        Ok(lines.join("\n") + "\n")
+    } else {
+        Err("Cannot find the members of the workspace".to_string())
+    }
+}
+
+// Inserts `line` after the last line that satisfies `after`.
+fn insert_after(s: &str, line: &str, after: impl Fn(&str) -> bool) -> Result<String, String> {
+    let mut lines: Vec<&str> = s.lines().collect();
+    let i = lines
+        .iter()
+        .rposition(|l| after(l))
+        .ok_or(format!("Cannot find where to insert '{}'", line.trim()))?;
+    lines.insert(i + 1, line);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("Cannot read '{}': {}", path.display(), e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Cannot create '{}': {}", parent.display(), e))?;
+    }
+    std::fs::write(path, contents)
+        .map_err(|e| format!("Cannot write '{}': {}", path.display(), e))?;
+    println!("{}", path.strip_prefix(root()).unwrap_or(path).display());
+    Ok(())
+}
+
+// Creates the crate of a new year, and runs it from this command line.
+fn new_year(year: u32) -> Result<(), String> {
+    let dir = root().join(year.to_string());
+    if dir.exists() {
+        return Err(format!("'{}' exists, but is not a crate", dir.display()));
+    }
+    write(&dir.join("Cargo.toml"), &cargo_toml(year))?;
+    write(&dir.join("src").join("lib.rs"), &lib_rs(year))?;
+
+    let path = root().join("Cargo.toml");
+    write(&path, &add_member(&read(&path)?, year)?)?;
+    let path = root().join("cli").join("Cargo.toml");
+    let dependency = format!("advent-of-code-{} = {{ path = \"../{}\" }}", year, year);
+    let manifest = insert_after(&read(&path)?, &dependency, |l| {
+        l.starts_with("advent-of-code-")
+    })?;
+    write(&path, &manifest)?;
+    let path = root().join("cli").join("src").join("main.rs");
+    let register = format!("    advent_of_code_{}::register(&mut registry);", year);
+    let main = insert_after(&read(&path)?, &register, |l| {
+        l.trim().starts_with("advent_of_code_") && l.ends_with("::register(&mut registry);")
+    })?;
+    write(&path, &main)
+}
+
+pub fn new_day(year: u32, day: u32) -> Result<(), String> {
+    let dir = root().join(year.to_string());
+    if !dir.join("src").join("lib.rs").is_file() {
+        new_year(year)?
+    }
+    let lib_path = dir.join("src").join("lib.rs");
+    let lib = read(&lib_path)?;
+    // The years that name their days `day7` keep doing so.
+    let short = lib
+        .lines()
+        .any(|l| l.starts_with("mod day") && module_day(l).is_some() && !l.contains('_'));
+    let (module, input) = if short {
+        (format!("day{}", day), format!("{}.txt", day))
+    } else {
+        (format!("day_{:02}", day), format!("day_{:02}.txt", day))
+    };
+    let module_path = dir.join("src").join(format!("{}.rs", module));
+    if module_path.exists() {
+        return Err(format!("'{}' already exists", module_path.display()));
+    }
+    let lib = register_day(&lib, day, &module)?;
+    write(&module_path, MODULE)?;
+    write(&lib_path, &lib)?;
+    let input_path = dir.join("data").join(input);
+    if !input_path.exists() {
+        write(&input_path, "")?
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_day() {
+        let lib = lib_rs(2022);
+        let lib = register_day(&lib, 7, "day_07").unwrap();
+        let lib = register_day(&lib, 2, "day_02").unwrap();
+        let lib = register_day(&lib, 12, "day_12").unwrap();
+        assert_eq!(
+            lib,
+            "// The solutions of Advent of Code 2022, run through the `aoc` command line.
+
+use aoc::Registry;
+
+mod day_02;
+mod day_07;
+mod day_12;
+
+const YEAR: u32 = 2022;
+
+// All the solutions of this year.
+pub fn register(registry: &mut Registry) {
+    registry.add(YEAR, 2, day_02::Day);
+    registry.add(YEAR, 7, day_07::Day);
+    registry.add(YEAR, 12, day_12::Day);
+}
+"
+        );
+        assert!(register_day(&lib, 7, "day_07").is_err());
+    }
+
+    #[test]
+    fn test_new_year() {
+        let workspace = "[workspace]\nmembers = [\"aoc\", \"2020\"]\nresolver = \"2\"\n";
+        assert_eq!(
+            add_member(workspace, 2022).unwrap(),
+            "[workspace]\nmembers = [\"aoc\", \"2020\", \"2022\"]\nresolver = \"2\"\n"
+        );
+        let main = "fn registry() {\n    advent_of_code_2020::register(&mut registry);\n}\n";
+        let register = "    advent_of_code_2022::register(&mut registry);";
+        assert_eq!(
+            insert_after(main, register, |l| l
+                .ends_with("::register(&mut registry);"))
+            .unwrap(),
+            format!(
+                "fn registry() {{\n    advent_of_code_2020::register(&mut registry);\n{}\n}}\n",
+                register
+            )
+        );
+    }
+}
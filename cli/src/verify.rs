@@ -0,0 +1,259 @@
+// Checks the answers of the solutions on their inputs against the recorded ones, in
+// `answers.txt`, so that changes to shared code do not silently break older days. The examples
+// of the puzzles (see `aoc::example`) can be checked the same way.
+
+use crate::{catch_panic, find_input, root};
+use aoc::{Answers, Check, Registry};
+use std::path::PathBuf;
+
+pub fn answers_path() -> PathBuf {
+    root().join("answers.txt")
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Correct,
+    // Some parts have no recorded answer, and the others are correct.
+    Unknown(Vec<u32>),
+    Wrong(Vec<String>),
+    Failed(String),
+    // There is nothing to run the day on: no input, or no examples.
+    Skipped(&'static str),
+}
+
+impl Status {
+    pub fn is_ok(&self) -> bool {
+        !matches!(self, Status::Wrong(_) | Status::Failed(_))
+    }
+}
+
+// Runs a day on its input, and checks its answers. The answers of the parts that have none are
+// recorded in `record`, if any, and so are the wrong ones with `overwrite`.
+pub fn verify_day(
+    registry: &Registry,
+    answers: &Answers,
+    mut record: Option<&mut Answers>,
+    overwrite: bool,
+    year: u32,
+    day: u32,
+) -> Status {
+    let solution = match registry.get(year, day) {
+        Some(solution) => solution,
+        None => return Status::Failed(format!("Day {} of {} is not solved", day, year)),
+    };
+    let path = match find_input(year, day) {
+        Some(path) => path,
+        None => return Status::Skipped("no input"),
+    };
+    let result = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Cannot read '{}': {}", path.display(), e))
+        .and_then(|input| catch_panic(|| solution.run(&input)));
+    let results = match result {
+        Ok(results) => results,
+        Err(e) => return Status::Failed(e),
+    };
+
+    let mut unknown = vec![];
+    let mut wrong = vec![];
+    for (part, answer) in (1..).zip(results.iter()) {
+        let save = match answers.check(year, day, part, answer) {
+            Check::Correct => false,
+            Check::Unknown => {
+                unknown.push(part);
+                true
+            }
+            Check::Wrong { expected } => {
+                wrong.push(format!(
+                    "part {}: expected {}, got {}",
+                    part, expected, answer
+                ));
+                overwrite
+            }
+        };
+        if let (true, Some(record)) = (save, record.as_deref_mut()) {
+            record.insert(year, day, part, answer)
+        }
+    }
+    if !wrong.is_empty() {
+        Status::Wrong(wrong)
+    } else if !unknown.is_empty() {
+        Status::Unknown(unknown)
+    } else {
+        Status::Correct
+    }
+}
+
+// Runs a day on its examples, if any.
+pub fn verify_examples(registry: &Registry, year: u32, day: u32) -> Status {
+    let solution = match registry.get(year, day) {
+        Some(solution) => solution,
+        None => return Status::Failed(format!("Day {} of {} is not solved", day, year)),
+    };
+    let examples = solution.examples();
+    if examples.is_empty() {
+        return Status::Skipped("no examples");
+    }
+    let wrong: Vec<String> = (1..)
+        .zip(examples.iter())
+        .filter_map(
+            |(i, example)| match catch_panic(|| example.check(solution)) {
+                Ok(()) => None,
+                Err(e) => Some(format!("example {}: {}", i, e)),
+            },
+        )
+        .collect();
+    if wrong.is_empty() {
+        Status::Correct
+    } else {
+        Status::Wrong(wrong)
+    }
+}
+
+// Prints the status of each day, and a summary. Returns false if some days failed.
+fn report(days: &[(u32, u32)], mut verify: impl FnMut(u32, u32) -> Status) -> bool {
+    let mut ok = true;
+    let mut counts = [0; 5];
+    for &(year, day) in days.iter() {
+        let status = verify(year, day);
+        let (i, message) = match &status {
+            Status::Correct => (0, "ok".to_string()),
+            Status::Unknown(parts) => {
+                let parts: Vec<String> = parts.iter().map(|p| p.to_string()).collect();
+                (1, format!("no answer for part {}", parts.join(", ")))
+            }
+            Status::Wrong(errors) => (2, format!("WRONG {}", errors.join("; "))),
+            Status::Failed(e) => (3, format!("FAILED {}", e)),
+            Status::Skipped(reason) => (4, format!("skipped ({})", reason)),
+        };
+        println!("{} {:02}: {}", year, day, message);
+        counts[i] += 1;
+        ok &= status.is_ok();
+    }
+    println!(
+        "{} ok, {} without answers, {} wrong, {} failed, {} skipped",
+        counts[0], counts[1], counts[2], counts[3], counts[4]
+    );
+    ok
+}
+
+// Verifies the given days on their inputs. With `record`, the answers of the parts that have none
+// are saved as the correct ones. The wrong answers are only replaced with `overwrite`, so that a
+// regression is not recorded by mistake. Returns false if some days failed.
+pub fn verify(
+    registry: &Registry,
+    days: &[(u32, u32)],
+    record: bool,
+    overwrite: bool,
+) -> Result<bool, String> {
+    let path = answers_path();
+    let answers = Answers::load(&path)?;
+    let mut recorded = answers.clone();
+    let ok = report(days, |year, day| {
+        verify_day(
+            registry,
+            &answers,
+            Some(&mut recorded),
+            overwrite,
+            year,
+            day,
+        )
+    });
+    if record {
+        recorded.save(&path)?;
+        println!("Answers recorded in {}", path.display());
+        if !ok && !overwrite {
+            println!("The wrong answers were kept, use --overwrite to replace them");
+        }
+    }
+    Ok(ok)
+}
+
+// Verifies the given days on their examples. Returns false if some days failed.
+pub fn verify_all_examples(registry: &Registry, days: &[(u32, u32)]) -> bool {
+    report(days, |year, day| verify_examples(registry, year, day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // All the days have an input and recorded answers, and still find them. The second part of
+    // day 25 needs none, since it has no answer (see `Answers::check`).
+    #[test]
+    fn test_answers() {
+        let registry = crate::registry();
+        let answers = Answers::load(&answers_path()).unwrap();
+        let mut failures = vec![];
+        for ((year, day), _) in registry.iter() {
+            match verify_day(&registry, &answers, None, false, year, day) {
+                Status::Correct => {}
+                Status::Unknown(parts) => {
+                    failures.push(format!("{} {}: no answer for parts {:?}", year, day, parts))
+                }
+                Status::Wrong(errors) => failures.push(format!("{} {}: {:?}", year, day, errors)),
+                Status::Failed(e) => failures.push(format!("{} {}: {}", year, day, e)),
+                Status::Skipped(reason) => failures.push(format!("{} {}: {}", year, day, reason)),
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn test_record() {
+        let registry = crate::registry();
+        let mut answers = Answers::load(&answers_path()).unwrap();
+        answers.insert(2018, 1, 1, &0.into());
+        let mut recorded = Answers::new();
+        recorded.insert(2018, 1, 1, &0.into());
+        let status = verify_day(&registry, &answers, Some(&mut recorded), false, 2018, 1);
+        assert!(matches!(status, Status::Wrong(_)));
+        assert_eq!(recorded.get(2018, 1, 1), Some("0"));
+        verify_day(&registry, &answers, Some(&mut recorded), true, 2018, 1);
+        assert_ne!(recorded.get(2018, 1, 1), Some("0"));
+    }
+
+    #[test]
+    fn test_examples() {
+        let registry = crate::registry();
+        let mut failures = vec![];
+        for ((year, day), _) in registry.iter() {
+            if let Status::Wrong(errors) = verify_examples(&registry, year, day) {
+                failures.push(format!("{} {}: {:?}", year, day, errors))
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    struct Panics;
+
+    impl aoc::Solution for Panics {
+        type Input = ();
+
+        fn parse(&self, input: &str) -> Result<(), String> {
+            panic!("invalid input {:?}", input)
+        }
+
+        fn part1(&self, _: &()) -> aoc::Answer {
+            aoc::Answer::Nothing
+        }
+
+        fn part2(&self, _: &()) -> aoc::Answer {
+            aoc::Answer::Nothing
+        }
+
+        aoc::examples! {
+            "x" => part1: 1;
+        }
+    }
+
+    // A panic is a failure of its day, rather than of the whole command.
+    #[test]
+    fn test_panic() {
+        let mut registry = Registry::new();
+        registry.add(2020, 1, Panics);
+        assert_eq!(
+            verify_examples(&registry, 2020, 1),
+            Status::Wrong(vec!["example 1: panicked: invalid input \"x\"".to_string()])
+        );
+    }
+}
@@ -0,0 +1,281 @@
+// Axis-aligned boxes over integer coordinates, in any dimension. Bounds are inclusive, and a
+// box is empty when `low[i] > high[i]` along some axis.
+
+use crate::Coordinate;
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
+pub struct Aabb<T, const N: usize> {
+    pub low: [T; N],
+    pub high: [T; N],
+}
+
+impl<T: Coordinate, const N: usize> Aabb<T, N> {
+    pub fn new(low: [T; N], high: [T; N]) -> Self {
+        Aabb { low, high }
+    }
+
+    // The smallest box containing all the points, or `None` if there are none.
+    pub fn from_points<I>(mut it: I) -> Option<Self>
+    where
+        I: Iterator<Item = [T; N]>,
+    {
+        let first = it.next()?;
+        let mut result = Aabb::new(first, first);
+        for p in it {
+            result.extend(&p)
+        }
+        Some(result)
+    }
+
+    // Grows the box to contain `p`.
+    pub fn extend(&mut self, p: &[T; N]) {
+        for (i, &c) in p.iter().enumerate() {
+            self.low[i] = std::cmp::min(self.low[i], c);
+            self.high[i] = std::cmp::max(self.high[i], c);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        (0..N).any(|i| self.low[i] > self.high[i])
+    }
+
+    // Number of points along each axis.
+    pub fn size(&self) -> [T; N] {
+        let mut result = [T::ZERO; N];
+        for (i, s) in result.iter_mut().enumerate() {
+            if self.low[i] <= self.high[i] {
+                *s = self.high[i] - self.low[i] + T::ONE
+            }
+        }
+        result
+    }
+
+    // Number of points in the box, saturating at `u128::MAX`.
+    pub fn volume(&self) -> u128 {
+        if self.is_empty() {
+            return 0;
+        }
+        (0..N)
+            .map(|i| (self.high[i].to_i128() - self.low[i].to_i128() + 1) as u128)
+            .fold(1, u128::saturating_mul)
+    }
+
+    pub fn contains(&self, p: &[T; N]) -> bool {
+        (0..N).all(|i| self.low[i] <= p[i] && p[i] <= self.high[i])
+    }
+
+    pub fn contains_box(&self, other: &Self) -> bool {
+        other.is_empty() || (self.contains(&other.low) && self.contains(&other.high))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut result = *self;
+        for i in 0..N {
+            result.low[i] = std::cmp::max(self.low[i], other.low[i]);
+            result.high[i] = std::cmp::min(self.high[i], other.high[i]);
+        }
+        if result.is_empty() {
+            None
+        } else {
+            Some(result)
+        }
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    // Manhattan distance from `p` to the closest point of the (non-empty) box.
+    pub fn manhattan_distance(&self, p: &[T; N]) -> T {
+        let mut result = T::ZERO;
+        for (i, &c) in p.iter().enumerate() {
+            if c < self.low[i] {
+                result = result + (self.low[i] - c)
+            } else if self.high[i] < c {
+                result = result + (c - self.high[i])
+            }
+        }
+        result
+    }
+
+    // Splits the box in halves along each axis, yielding up to 2^N disjoint boxes that cover it
+    // (8 octants in dimension 3). Axes along which the box is one point thick are not split, so
+    // a single point yields itself.
+    pub fn split(&self) -> Vec<Self> {
+        if self.is_empty() {
+            return vec![];
+        }
+        let mut result = vec![*self];
+        for i in 0..N {
+            if self.low[i] == self.high[i] {
+                continue;
+            }
+            // Computed in i128 to avoid overflows. `mid` lies in `[low, high)`, so it fits in `T`,
+            // and so does its successor.
+            let (low, high) = (self.low[i].to_i128(), self.high[i].to_i128());
+            let mid = T::from_i128(low + (high - low) / 2);
+            let mut next = Vec::with_capacity(2 * result.len());
+            for b in result {
+                let mut lower = b;
+                let mut upper = b;
+                lower.high[i] = mid;
+                upper.low[i] = mid + T::ONE;
+                next.push(lower);
+                next.push(upper);
+            }
+            result = next;
+        }
+        result
+    }
+
+    // Iterates over the points of the box, in lexicographic order.
+    pub fn points(&self) -> Points<T, N> {
+        Points {
+            aabb: *self,
+            next: if self.is_empty() {
+                None
+            } else {
+                Some(self.low)
+            },
+        }
+    }
+}
+
+pub struct Points<T, const N: usize> {
+    aabb: Aabb<T, N>,
+    next: Option<[T; N]>,
+}
+
+impl<T: Coordinate, const N: usize> Iterator for Points<T, N> {
+    type Item = [T; N];
+
+    fn next(&mut self) -> Option<[T; N]> {
+        let current = self.next?;
+        // Odometer-style increment, last axis first.
+        let mut next = current;
+        let mut i = N;
+        self.next = loop {
+            if i == 0 {
+                break None;
+            }
+            i -= 1;
+            if next[i] < self.aabb.high[i] {
+                next[i] = next[i] + T::ONE;
+                break Some(next);
+            }
+            next[i] = self.aabb.low[i];
+        };
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph::rng::Rng;
+
+    fn point(rng: &mut Rng) -> [i64; 3] {
+        [rng.range(-6, 6), rng.range(-6, 6), rng.range(-6, 6)]
+    }
+
+    fn aabb(rng: &mut Rng) -> Aabb<i64, 3> {
+        let a = point(rng);
+        let b = point(rng);
+        Aabb::from_points([a, b].into_iter()).unwrap()
+    }
+
+    #[test]
+    fn test_basics() {
+        let b = Aabb::new([0, 0], [2, 3]);
+        assert_eq!(b.size(), [3, 4]);
+        assert_eq!(b.volume(), 12);
+        assert!(b.contains(&[2, 0]));
+        assert!(!b.contains(&[3, 0]));
+        assert_eq!(b.manhattan_distance(&[5, -1]), 4);
+        assert_eq!(b.manhattan_distance(&[1, 1]), 0);
+        assert_eq!(
+            b.intersection(&Aabb::new([1, 3], [5, 5])),
+            Some(Aabb::new([1, 3], [2, 3]))
+        );
+        assert_eq!(b.intersection(&Aabb::new([3, 0], [5, 5])), None);
+        assert_eq!(b.points().count(), 12);
+        assert_eq!(b.split().len(), 4);
+        let p = Aabb::new([1u8, 1], [1, 1]);
+        assert_eq!(p.split(), vec![p]);
+        let e = Aabb::new([1, 1], [0, 1]);
+        assert!(e.is_empty());
+        assert_eq!(e.volume(), 0);
+        assert_eq!(e.points().count(), 0);
+    }
+
+    #[test]
+    fn test_from_points() {
+        let points = [[1, 5, 0], [-2, 3, 7], [4, -1, 2]];
+        assert_eq!(
+            Aabb::from_points(points.iter().cloned()),
+            Some(Aabb::new([-2, -1, 0], [4, 5, 7]))
+        );
+        assert_eq!(Aabb::<i32, 3>::from_points(std::iter::empty()), None);
+    }
+
+    #[test]
+    fn test_points_property() {
+        let mut rng = Rng::new(1);
+        for _ in 0..200 {
+            let b = aabb(&mut rng);
+            let points: Vec<_> = b.points().collect();
+            assert_eq!(points.len() as u128, b.volume());
+            assert!(points.windows(2).all(|w| w[0] < w[1]));
+            assert!(points.iter().all(|p| b.contains(p)));
+            assert_eq!(Aabb::from_points(points.into_iter()), Some(b));
+        }
+    }
+
+    #[test]
+    fn test_intersection_property() {
+        let mut rng = Rng::new(2);
+        for _ in 0..200 {
+            let a = aabb(&mut rng);
+            let b = aabb(&mut rng);
+            let i = a.intersection(&b);
+            assert_eq!(i, b.intersection(&a));
+            for p in a.points() {
+                let in_i = i.is_some_and(|i| i.contains(&p));
+                assert_eq!(in_i, b.contains(&p));
+            }
+            if let Some(i) = i {
+                assert!(a.contains_box(&i) && b.contains_box(&i));
+            }
+            assert_eq!(a.contains_box(&b), i == Some(b));
+        }
+    }
+
+    #[test]
+    fn test_split_property() {
+        let mut rng = Rng::new(3);
+        for _ in 0..200 {
+            let b = aabb(&mut rng);
+            let parts = b.split();
+            assert!(!parts.is_empty() && parts.len() <= 8);
+            assert_eq!(parts.iter().map(|p| p.volume()).sum::<u128>(), b.volume());
+            for p in b.points() {
+                assert_eq!(parts.iter().filter(|part| part.contains(&p)).count(), 1);
+            }
+            if b.volume() > 1 {
+                assert!(parts.iter().all(|part| part.volume() < b.volume()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_manhattan_distance_property() {
+        let mut rng = Rng::new(4);
+        for _ in 0..200 {
+            let b = aabb(&mut rng);
+            let p = point(&mut rng);
+            let d = |q: &[i64; 3]| (0..3).map(|i| (p[i] - q[i]).abs()).sum::<i64>();
+            let expected = b.points().map(|q| d(&q)).min().unwrap();
+            assert_eq!(b.manhattan_distance(&p), expected);
+        }
+    }
+}
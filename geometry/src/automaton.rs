@@ -0,0 +1,305 @@
+// Cellular automata: grids of cells whose next state only depends on the states of their
+// neighbours.
+//
+// There are two backends:
+// - dense grids (`step_grid`), where each cell has a state of any type, and the neighbourhood
+//   is given by a function of the grid and the position;
+// - sparse sets of live cells (`step`), for automata where cells are either dead or alive,
+//   which can grow without bounds. The neighbourhood is given by a `Topology`, and the set of
+//   live cells can be stored in any way through the `LiveCells` trait (e.g. as a bitset for
+//   small bounded grids).
+
+use crate::{Aabb, Grid, HexDirection, Vector2};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+// The neighbours of each position in `grid`, given e.g. by `moore` or `von_neumann`, are passed
+// to `rule` along with the state of the position.
+pub fn step_grid<T, N, R>(grid: &Grid<T>, mut neighbours: N, mut rule: R) -> Grid<T>
+where
+    N: FnMut(&Grid<T>, Vector2<i32>) -> Vec<Vector2<i32>>,
+    R: FnMut(&T, &[&T]) -> T,
+{
+    Grid::from_fn(grid.width(), grid.height(), |pos: Vector2<i32>| {
+        let around: Vec<&T> = neighbours(grid, pos)
+            .into_iter()
+            .map(|p| &grid[p])
+            .collect();
+        rule(&grid[pos], &around)
+    })
+}
+
+// The 8 surrounding cells.
+pub fn moore<T>(grid: &Grid<T>, pos: Vector2<i32>) -> Vec<Vector2<i32>> {
+    grid.neighbours8(pos).collect()
+}
+
+// The 4 orthogonally adjacent cells.
+pub fn von_neumann<T>(grid: &Grid<T>, pos: Vector2<i32>) -> Vec<Vector2<i32>> {
+    grid.neighbours4(pos).collect()
+}
+
+// The neighbours of cells, for sparse automata. Neighbourhoods must be symmetric: `b` is a
+// neighbour of `a` iff `a` is a neighbour of `b`.
+pub trait Topology {
+    type Cell: Copy + Eq + Hash;
+    fn neighbours(&self, cell: &Self::Cell) -> Vec<Self::Cell>;
+}
+
+// The cells within distance 1 (along each axis) in dimension `N`, i.e. `3^N - 1` neighbours.
+#[derive(Clone, Debug)]
+pub struct Moore<const N: usize> {
+    offsets: Vec<[i32; N]>,
+}
+
+impl<const N: usize> Default for Moore<N> {
+    fn default() -> Self {
+        Moore::new()
+    }
+}
+
+impl<const N: usize> Moore<N> {
+    pub fn new() -> Moore<N> {
+        let mut offsets = vec![[0; N]];
+        for i in 0..N {
+            offsets = offsets
+                .into_iter()
+                .flat_map(|o| {
+                    [-1, 0, 1].map(|d| {
+                        let mut o = o;
+                        o[i] = d;
+                        o
+                    })
+                })
+                .collect();
+        }
+        offsets.retain(|o| o.iter().any(|&d| d != 0));
+        Moore { offsets }
+    }
+}
+
+impl<const N: usize> Topology for Moore<N> {
+    type Cell = [i32; N];
+    fn neighbours(&self, cell: &[i32; N]) -> Vec<[i32; N]> {
+        self.offsets
+            .iter()
+            .map(|o| std::array::from_fn(|i| cell[i] + o[i]))
+            .collect()
+    }
+}
+
+// The `2N` cells adjacent along one axis, in dimension `N`.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct VonNeumann<const N: usize>;
+
+impl<const N: usize> Topology for VonNeumann<N> {
+    type Cell = [i32; N];
+    fn neighbours(&self, cell: &[i32; N]) -> Vec<[i32; N]> {
+        let mut result = Vec::with_capacity(2 * N);
+        for i in 0..N {
+            for d in [-1, 1] {
+                let mut n = *cell;
+                n[i] += d;
+                result.push(n)
+            }
+        }
+        result
+    }
+}
+
+// The 6 adjacent tiles of a hexagonal grid, in axial coordinates.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Hex;
+
+impl Topology for Hex {
+    type Cell = Vector2<i32>;
+    fn neighbours(&self, cell: &Vector2<i32>) -> Vec<Vector2<i32>> {
+        HexDirection::each()
+            .map(|d| *cell + d.to_vector())
+            .collect()
+    }
+}
+
+// Restricts a topology to a box: cells outside of it are always dead.
+#[derive(Clone, Debug)]
+pub struct Bounded<T, const N: usize> {
+    pub topology: T,
+    pub bounds: Aabb<i32, N>,
+}
+
+impl<T, const N: usize> Topology for Bounded<T, N>
+where
+    T: Topology<Cell = [i32; N]>,
+{
+    type Cell = [i32; N];
+    fn neighbours(&self, cell: &[i32; N]) -> Vec<[i32; N]> {
+        let mut result = self.topology.neighbours(cell);
+        result.retain(|n| self.bounds.contains(n));
+        result
+    }
+}
+
+// Square grids of odd `size`, nested into each other: the center cell of each grid is replaced
+// by the grid of the next level, and the grid is itself the center of the grid of the previous
+// level. Cells are von Neumann neighbours, across levels at the borders.
+#[derive(Copy, Clone, Debug)]
+pub struct Recursive {
+    pub size: i32,
+}
+
+impl Topology for Recursive {
+    // The position in the grid, and the level of the grid.
+    type Cell = ([i32; 2], i32);
+
+    fn neighbours(&self, &([i, j], level): &Self::Cell) -> Vec<Self::Cell> {
+        let (last, c) = (self.size - 1, self.size / 2);
+        let mut result = Vec::new();
+        // The cells around the center of the previous level.
+        if i == 0 {
+            result.push(([c - 1, c], level - 1))
+        }
+        if i == last {
+            result.push(([c + 1, c], level - 1))
+        }
+        if j == 0 {
+            result.push(([c, c - 1], level - 1))
+        }
+        if j == last {
+            result.push(([c, c + 1], level - 1))
+        }
+        // The border of the next level.
+        for x in 0..self.size {
+            match [i - c, j - c] {
+                [-1, 0] => result.push(([0, x], level + 1)),
+                [1, 0] => result.push(([last, x], level + 1)),
+                [0, -1] => result.push(([x, 0], level + 1)),
+                [0, 1] => result.push(([x, last], level + 1)),
+                _ => {}
+            }
+        }
+        for n in VonNeumann::<2>.neighbours(&[i, j]) {
+            if n.iter().all(|&x| 0 <= x && x <= last) && n != [c, c] {
+                result.push((n, level))
+            }
+        }
+        result
+    }
+}
+
+// A set of live cells.
+pub trait LiveCells<C>: Default {
+    fn contains(&self, cell: &C) -> bool;
+    fn insert(&mut self, cell: C);
+    fn cells(&self) -> Box<dyn Iterator<Item = C> + '_>;
+    fn count(&self) -> usize;
+}
+
+impl<C: Copy + Eq + Hash> LiveCells<C> for HashSet<C> {
+    fn contains(&self, cell: &C) -> bool {
+        HashSet::contains(self, cell)
+    }
+
+    fn insert(&mut self, cell: C) {
+        HashSet::insert(self, cell);
+    }
+
+    fn cells(&self) -> Box<dyn Iterator<Item = C> + '_> {
+        Box::new(self.iter().copied())
+    }
+
+    fn count(&self) -> usize {
+        self.len()
+    }
+}
+
+// Computes the next generation: a cell is alive iff `rule(alive, n)`, where `alive` is its
+// current state and `n` the number of its live neighbours. Dead cells without live neighbours
+// stay dead (`rule(false, 0)` must be false).
+pub fn step<T, S, R>(topology: &T, live: &S, rule: R) -> S
+where
+    T: Topology,
+    S: LiveCells<T::Cell>,
+    R: Fn(bool, usize) -> bool,
+{
+    // Only the live cells and their neighbours can be alive at the next generation.
+    let mut counts: HashMap<T::Cell, usize> = HashMap::new();
+    for cell in live.cells() {
+        counts.entry(cell).or_insert(0);
+        for n in topology.neighbours(&cell) {
+            *counts.entry(n).or_insert(0) += 1
+        }
+    }
+    let mut next = S::default();
+    for (cell, n) in counts {
+        if rule(live.contains(&cell), n) {
+            next.insert(cell)
+        }
+    }
+    next
+}
+
+// The classic rule of Conway's game of life.
+pub fn conway(alive: bool, n: usize) -> bool {
+    n == 3 || (alive && n == 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topologies() {
+        assert_eq!(Moore::<2>::new().neighbours(&[0, 0]).len(), 8);
+        assert_eq!(Moore::<4>::new().neighbours(&[0, 0, 0, 0]).len(), 80);
+        assert_eq!(VonNeumann::<3>.neighbours(&[0, 0, 0]).len(), 6);
+        assert_eq!(Hex.neighbours(&Vector2::new(0, 0)).len(), 6);
+        let bounded = Bounded {
+            topology: VonNeumann::<2>,
+            bounds: Aabb::new([0, 0], [4, 4]),
+        };
+        assert_eq!(bounded.neighbours(&[0, 0]).len(), 2);
+
+        // The counts from the example of 2019 day 24.
+        let recursive = Recursive { size: 5 };
+        assert_eq!(recursive.neighbours(&([3, 3], 0)).len(), 4);
+        assert_eq!(recursive.neighbours(&([0, 4], 0)).len(), 4);
+        assert_eq!(recursive.neighbours(&([1, 2], 0)).len(), 8);
+        // All neighbourhoods are symmetric.
+        for i in 0..5 {
+            for j in 0..5 {
+                if [i, j] == [2, 2] {
+                    continue;
+                }
+                for n in recursive.neighbours(&([i, j], 0)) {
+                    assert!(recursive.neighbours(&n).contains(&([i, j], 0)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_glider() {
+        let glider = [[1, 0], [2, 1], [0, 2], [1, 2], [2, 2]];
+        let mut live: HashSet<[i32; 2]> = glider.into_iter().collect();
+        let moore = Moore::<2>::new();
+        for _ in 0..4 {
+            live = step(&moore, &live, conway);
+        }
+        let moved: HashSet<[i32; 2]> = glider.iter().map(|&[x, y]| [x + 1, y + 1]).collect();
+        assert_eq!(live, moved);
+    }
+
+    #[test]
+    fn test_step_grid() {
+        // Blinkers, on a dense grid.
+        let grid = Grid::parse(".....\n..#..\n..#..\n..#..\n.....", |c| Some(c == '#')).unwrap();
+        let rule =
+            |&alive: &bool, around: &[&bool]| conway(alive, around.iter().filter(|&&&b| b).count());
+        let next = step_grid(&grid, moore, rule);
+        assert_eq!(
+            next.render(|&b| if b { '#' } else { '.' }),
+            ".....\n.....\n.###.\n.....\n.....\n"
+        );
+        assert_eq!(step_grid(&next, moore, rule), grid);
+    }
+}
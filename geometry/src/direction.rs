@@ -0,0 +1,302 @@
+// Directions on square and hexagonal grids.
+//
+// Conversions to vectors use grid coordinates: x grows towards the east, and y grows towards
+// the south (i.e., y is the row index when reading a map from top to bottom). Rotations are
+// expressed in degrees, positive for clockwise turns, as on a compass.
+
+use crate::Vector2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+// Turns a rotation in degrees into a number of clockwise steps of `step` degrees, in
+// `0..360 / step`.
+fn steps(degrees: i32, step: i32) -> i32 {
+    if degrees % step != 0 {
+        panic!("Invalid rotation of {} degrees", degrees)
+    }
+    (degrees / step).rem_euclid(360 / step)
+}
+
+impl Direction {
+    // Clockwise order
+    const CLOCKWISE: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+        }
+    }
+
+    pub fn each() -> impl Iterator<Item = Direction> + Clone {
+        [
+            Direction::North,
+            Direction::South,
+            Direction::West,
+            Direction::East,
+        ]
+        .iter()
+        .cloned()
+    }
+
+    pub fn turn_left(&self) -> Direction {
+        self.rotate(-90)
+    }
+
+    pub fn turn_right(&self) -> Direction {
+        self.rotate(90)
+    }
+
+    // Panics if `degrees` is not a multiple of 90.
+    pub fn rotate(&self, degrees: i32) -> Direction {
+        let i = Self::CLOCKWISE.iter().position(|d| d == self).unwrap() as i32;
+        Self::CLOCKWISE[((i + steps(degrees, 90)) % 4) as usize]
+    }
+
+    pub fn to_vector<T: From<i8>>(&self) -> Vector2<T> {
+        let (x, y) = match self {
+            Direction::North => (0, -1),
+            Direction::South => (0, 1),
+            Direction::East => (1, 0),
+            Direction::West => (-1, 0),
+        };
+        Vector2::new(T::from(x), T::from(y))
+    }
+}
+
+// The 8 neighbours of a cell of a square grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    // Clockwise order
+    const CLOCKWISE: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    // Iterates clockwise, starting from the north.
+    pub fn each() -> impl Iterator<Item = Direction8> + Clone {
+        Self::CLOCKWISE.iter().cloned()
+    }
+
+    pub fn opposite(&self) -> Direction8 {
+        self.rotate(180)
+    }
+
+    // Turns by 45 degrees.
+    pub fn turn_left(&self) -> Direction8 {
+        self.rotate(-45)
+    }
+
+    pub fn turn_right(&self) -> Direction8 {
+        self.rotate(45)
+    }
+
+    // Panics if `degrees` is not a multiple of 45.
+    pub fn rotate(&self, degrees: i32) -> Direction8 {
+        let i = Self::CLOCKWISE.iter().position(|d| d == self).unwrap() as i32;
+        Self::CLOCKWISE[((i + steps(degrees, 45)) % 8) as usize]
+    }
+
+    pub fn to_vector<T: From<i8>>(&self) -> Vector2<T> {
+        let (x, y) = match self {
+            Direction8::North => (0, -1),
+            Direction8::NorthEast => (1, -1),
+            Direction8::East => (1, 0),
+            Direction8::SouthEast => (1, 1),
+            Direction8::South => (0, 1),
+            Direction8::SouthWest => (-1, 1),
+            Direction8::West => (-1, 0),
+            Direction8::NorthWest => (-1, -1),
+        };
+        Vector2::new(T::from(x), T::from(y))
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(d: Direction) -> Direction8 {
+        match d {
+            Direction::North => Direction8::North,
+            Direction::South => Direction8::South,
+            Direction::East => Direction8::East,
+            Direction::West => Direction8::West,
+        }
+    }
+}
+
+// The 6 neighbours of a cell of a hexagonal grid with pointy tops (cells in a row are neighbours
+// east and west of each other). Vectors use axial coordinates: `x` grows towards the east, and
+// `y` towards the south-east.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HexDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl HexDirection {
+    // Clockwise order
+    const CLOCKWISE: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::SouthEast,
+        HexDirection::SouthWest,
+        HexDirection::West,
+        HexDirection::NorthWest,
+        HexDirection::NorthEast,
+    ];
+
+    // Iterates clockwise, starting from the east.
+    pub fn each() -> impl Iterator<Item = HexDirection> + Clone {
+        Self::CLOCKWISE.iter().cloned()
+    }
+
+    pub fn opposite(&self) -> HexDirection {
+        self.rotate(180)
+    }
+
+    // Turns by 60 degrees.
+    pub fn turn_left(&self) -> HexDirection {
+        self.rotate(-60)
+    }
+
+    pub fn turn_right(&self) -> HexDirection {
+        self.rotate(60)
+    }
+
+    // Panics if `degrees` is not a multiple of 60.
+    pub fn rotate(&self, degrees: i32) -> HexDirection {
+        let i = Self::CLOCKWISE.iter().position(|d| d == self).unwrap() as i32;
+        Self::CLOCKWISE[((i + steps(degrees, 60)) % 6) as usize]
+    }
+
+    pub fn to_vector<T: From<i8>>(&self) -> Vector2<T> {
+        let (x, y) = match self {
+            HexDirection::East => (1, 0),
+            HexDirection::SouthEast => (0, 1),
+            HexDirection::SouthWest => (-1, 1),
+            HexDirection::West => (-1, 0),
+            HexDirection::NorthWest => (0, -1),
+            HexDirection::NorthEast => (1, -1),
+        };
+        Vector2::new(T::from(x), T::from(y))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HexDirection::East => "e",
+            HexDirection::SouthEast => "se",
+            HexDirection::SouthWest => "sw",
+            HexDirection::West => "w",
+            HexDirection::NorthWest => "nw",
+            HexDirection::NorthEast => "ne",
+        }
+    }
+
+    // Parses a list of directions written without separators, e.g. `esenee`.
+    pub fn parse_all(mut s: &str) -> Result<Vec<HexDirection>, String> {
+        let mut result = vec![];
+        'outer: while !s.is_empty() {
+            for d in Self::each() {
+                if let Some(rest) = s.strip_prefix(d.name()) {
+                    result.push(d);
+                    s = rest;
+                    continue 'outer;
+                }
+            }
+            return Err(format!("Invalid hex direction at {:?}", s));
+        }
+        Ok(result)
+    }
+}
+
+// Number of steps between the origin and `v`, in axial coordinates.
+pub fn hex_distance(v: &Vector2<i64>) -> i64 {
+    (v.x.abs() + v.y.abs() + (v.x + v.y).abs()) / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        for d in Direction::each() {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.rotate(180), d.opposite());
+            assert_eq!(d.rotate(-270), d.turn_right());
+            assert_eq!(d.rotate(720), d);
+            let v: Vector2<i32> = d.to_vector();
+            assert_eq!(d.opposite().to_vector(), -v);
+            assert_eq!(Direction8::from(d).to_vector(), v);
+        }
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::East.to_vector::<i64>(), Vector2::new(1, 0));
+        assert_eq!(Direction::North.to_vector::<i64>(), Vector2::new(0, -1));
+    }
+
+    #[test]
+    fn test_direction8() {
+        assert_eq!(Direction8::each().count(), 8);
+        for d in Direction8::each() {
+            assert_eq!(d.turn_right().turn_left(), d);
+            let v: Vector2<i32> = d.to_vector();
+            assert_eq!(d.opposite().to_vector(), -v);
+            assert_eq!(v.norm_inf(), 1);
+        }
+        assert_eq!(Direction8::North.rotate(135), Direction8::SouthEast);
+    }
+
+    #[test]
+    fn test_hex() {
+        let mut sum = Vector2::new(0i64, 0);
+        for d in HexDirection::each() {
+            assert_eq!(d.turn_right().turn_left(), d);
+            let v: Vector2<i64> = d.to_vector();
+            assert_eq!(d.opposite().to_vector(), -v);
+            assert_eq!(hex_distance(&v), 1);
+            // Two consecutive directions are neighbours of each other.
+            assert_eq!(hex_distance(&(d.turn_right().to_vector() - v)), 1);
+            sum += v;
+        }
+        assert_eq!(sum, Vector2::new(0, 0));
+
+        let path = HexDirection::parse_all("nwwswee").unwrap();
+        assert_eq!(path.len(), 5);
+        let end = path.iter().fold(sum, |acc, d| acc + d.to_vector());
+        assert_eq!(end, Vector2::new(0, 0));
+        assert!(HexDirection::parse_all("nex").is_err());
+    }
+}
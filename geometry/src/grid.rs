@@ -0,0 +1,402 @@
+// Dense rectangular grids, stored row by row. Positions are `Vector2`s with x growing to the
+// right and y growing downwards, so that `(0, 0)` is the top-left corner of the rendered grid.
+// Any integer type can be used for positions: accesses out of bounds (including negative
+// coordinates) are reported rather than wrapped around.
+
+use crate::{Coordinate, Vector2};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+// Offsets of the neighbours of a cell, in reading order.
+const NEIGHBOURS4: [(i128, i128); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const NEIGHBOURS8: [(i128, i128); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn from_fn<C, F>(width: usize, height: usize, mut f: F) -> Grid<T>
+    where
+        C: Coordinate,
+        F: FnMut(Vector2<C>) -> T,
+    {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(position(x, y)))
+            }
+        }
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    // Fails if the rows do not all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, String> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(format!(
+                    "Row {} has length {}, expected {}",
+                    y,
+                    row.len(),
+                    width
+                ));
+            }
+            cells.extend(row)
+        }
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    // Parses a map with one character per cell, and one line per row. `f` returns `None` for
+    // characters that are not valid cells. Trailing empty lines are ignored.
+    pub fn parse<F>(s: &str, mut f: F) -> Result<Grid<T>, String>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut lines: Vec<&str> = s.lines().collect();
+        while lines.last().is_some_and(|l| l.is_empty()) {
+            lines.pop();
+        }
+        let mut rows = Vec::with_capacity(lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            let mut row = Vec::with_capacity(line.len());
+            for (x, c) in line.chars().enumerate() {
+                match f(c) {
+                    Some(t) => row.push(t),
+                    None => return Err(format!("Unexpected {:?} at ({}, {})", c, x, y)),
+                }
+            }
+            rows.push(row)
+        }
+        Grid::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn offset<C: Coordinate>(&self, pos: Vector2<C>) -> Option<usize> {
+        let (x, y) = (pos.x.to_i128(), pos.y.to_i128());
+        if 0 <= x && x < self.width as i128 && 0 <= y && y < self.height as i128 {
+            Some(y as usize * self.width + x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn contains<C: Coordinate>(&self, pos: Vector2<C>) -> bool {
+        self.offset(pos).is_some()
+    }
+
+    pub fn get<C: Coordinate>(&self, pos: Vector2<C>) -> Option<&T> {
+        self.offset(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut<C: Coordinate>(&mut self, pos: Vector2<C>) -> Option<&mut T> {
+        self.offset(pos).map(move |i| &mut self.cells[i])
+    }
+
+    // Cells in reading order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    // Positions in reading order.
+    pub fn positions<C: Coordinate>(&self) -> impl Iterator<Item = Vector2<C>> {
+        let width = self.width;
+        (0..self.width * self.height).map(move |i| position(i % width, i / width))
+    }
+
+    pub fn enumerate<C: Coordinate>(&self) -> impl Iterator<Item = (Vector2<C>, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    fn shifted<C: Coordinate>(
+        &self,
+        pos: Vector2<C>,
+        offsets: &'static [(i128, i128)],
+    ) -> impl Iterator<Item = Vector2<C>> {
+        let (x, y) = (pos.x.to_i128(), pos.y.to_i128());
+        let (width, height) = (self.width as i128, self.height as i128);
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let (x, y) = (x + dx, y + dy);
+            if 0 <= x && x < width && 0 <= y && y < height {
+                Some(Vector2::new(C::from_i128(x), C::from_i128(y)))
+            } else {
+                None
+            }
+        })
+    }
+
+    // The orthogonal neighbours of `pos` that are in the grid, in reading order (up, left,
+    // right, down).
+    pub fn neighbours4<C: Coordinate>(&self, pos: Vector2<C>) -> impl Iterator<Item = Vector2<C>> {
+        self.shifted(pos, &NEIGHBOURS4)
+    }
+
+    // The orthogonal and diagonal neighbours of `pos` that are in the grid, in reading order.
+    pub fn neighbours8<C: Coordinate>(&self, pos: Vector2<C>) -> impl Iterator<Item = Vector2<C>> {
+        self.shifted(pos, &NEIGHBOURS8)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` does not accept 0, but there are no cells in that case anyway.
+        self.cells.chunks(std::cmp::max(self.width, 1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Invalid column {}", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // Builds a new grid of the given size, where the cell at `pos` is the cell of `self` at
+    // `f(pos)`.
+    fn remap<F>(&self, width: usize, height: usize, f: F) -> Grid<T>
+    where
+        T: Clone,
+        F: Fn(usize, usize) -> (usize, usize),
+    {
+        Grid::from_fn(width, height, |pos: Vector2<usize>| {
+            let (x, y) = f(pos.x, pos.y);
+            self.cells[y * self.width + x].clone()
+        })
+    }
+
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    // Quarter turns, in the sense of the rendered grid.
+    pub fn rotate_cw(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.remap(self.height, self.width, |x, y| (y, height - 1 - x))
+    }
+
+    pub fn rotate_ccw(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.remap(self.height, self.width, |x, y| (width - 1 - y, x))
+    }
+
+    // Mirrors the grid left to right.
+    pub fn flip_horizontal(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.remap(self.width, self.height, |x, y| (width - 1 - x, y))
+    }
+
+    // Mirrors the grid top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.remap(self.width, self.height, |x, y| (x, height - 1 - y))
+    }
+
+    // Renders the grid with one character per cell, and a newline after each row.
+    pub fn render<F>(&self, mut f: F) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            s.extend(row.iter().map(&mut f));
+            s.push('\n')
+        }
+        s
+    }
+}
+
+fn position<C: Coordinate>(x: usize, y: usize) -> Vector2<C> {
+    Vector2::new(C::from_i128(x as i128), C::from_i128(y as i128))
+}
+
+impl<T, C: Coordinate> Index<Vector2<C>> for Grid<T> {
+    type Output = T;
+    fn index(&self, pos: Vector2<C>) -> &T {
+        match self.get(pos) {
+            Some(t) => t,
+            None => panic!("Position {:?} is out of the grid", pos),
+        }
+    }
+}
+
+impl<T, C: Coordinate> IndexMut<Vector2<C>> for Grid<T> {
+    fn index_mut(&mut self, pos: Vector2<C>) -> &mut T {
+        match self.offset(pos) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("Position {:?} is out of the grid", pos),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?
+            }
+            writeln!(f)?
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "#..
+.#.
+..#
+##.
+";
+
+    fn parse(s: &str) -> Result<Grid<bool>, String> {
+        Grid::parse(s, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_parse_and_render() {
+        let g = parse(MAP).unwrap();
+        assert_eq!((g.width(), g.height()), (3, 4));
+        assert_eq!(g.render(|&b| if b { '#' } else { '.' }), MAP);
+        assert_eq!(
+            g.map(|&b| if b { 'X' } else { ' ' }).to_string(),
+            "X  \n X \n  X\nXX \n"
+        );
+        assert!(parse("#.\n#").is_err());
+        assert!(parse("#.\n#x").unwrap_err().contains("(1, 1)"));
+        assert_eq!(parse("").unwrap().rows().count(), 0);
+    }
+
+    #[test]
+    fn test_access() {
+        let mut g = parse(MAP).unwrap();
+        assert_eq!(g.get(Vector2::new(1, 1)), Some(&true));
+        assert_eq!(g.get(Vector2::new(-1, 1)), None);
+        assert_eq!(g.get(Vector2::new(3usize, 0)), None);
+        assert!(!g[Vector2::new(2i64, 3)]);
+        g[Vector2::new(2i64, 3)] = true;
+        assert_eq!(g.row(3), &[true, true, true]);
+        assert_eq!(
+            g.column(0).copied().collect::<Vec<_>>(),
+            vec![true, false, false, true]
+        );
+        assert_eq!(g.columns().count(), 3);
+        let positions: Vec<Vector2<i32>> = g.positions().collect();
+        assert_eq!(positions.len(), 12);
+        assert_eq!(positions[4], Vector2::new(1, 1));
+        assert_eq!(g.enumerate::<i32>().filter(|(_, &b)| b).count(), 6);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let g = Grid::new(3, 3, 0);
+        let n: Vec<_> = g.neighbours4(Vector2::new(1, 1)).collect();
+        assert_eq!(
+            n,
+            vec![
+                Vector2::new(1, 0),
+                Vector2::new(0, 1),
+                Vector2::new(2, 1),
+                Vector2::new(1, 2)
+            ]
+        );
+        assert_eq!(g.neighbours8(Vector2::new(1, 1)).count(), 8);
+        assert_eq!(g.neighbours4(Vector2::new(0, 0)).count(), 2);
+        assert_eq!(g.neighbours8(Vector2::new(0usize, 2)).count(), 3);
+    }
+
+    #[test]
+    fn test_transformations() {
+        let g = Grid::from_fn(3, 2, |p: Vector2<i32>| p.y * 3 + p.x);
+        // 0 1 2
+        // 3 4 5
+        assert_eq!(g.transpose().to_string(), "03\n14\n25\n");
+        assert_eq!(g.rotate_cw().to_string(), "30\n41\n52\n");
+        assert_eq!(g.rotate_ccw().to_string(), "25\n14\n03\n");
+        assert_eq!(g.flip_horizontal().to_string(), "210\n543\n");
+        assert_eq!(g.flip_vertical().to_string(), "345\n012\n");
+        assert_eq!(g.rotate_cw().rotate_ccw(), g);
+        assert_eq!(
+            g.rotate_cw().rotate_cw(),
+            g.flip_horizontal().flip_vertical()
+        );
+    }
+}
@@ -0,0 +1,269 @@
+// Export of grids to image files, to look at large maps or make animations of simulations.
+//
+// Images are grids of colors. They can be written as binary PPM, which most viewers understand,
+// or as PNG. The PNG encoder does not compress the pixels (it uses stored deflate blocks), which
+// keeps it short and free of dependencies, at the cost of larger files.
+//
+// To turn a directory of frames into an animation, use e.g.
+// `ffmpeg -i frame_%05d.png animation.mp4`.
+
+use crate::{Grid, Vector2};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const RED: Rgb = Rgb(220, 50, 47);
+    pub const GREEN: Rgb = Rgb(133, 153, 0);
+    pub const BLUE: Rgb = Rgb(38, 139, 210);
+    pub const YELLOW: Rgb = Rgb(181, 137, 0);
+    pub const BROWN: Rgb = Rgb(120, 80, 40);
+    pub const GREY: Rgb = Rgb(128, 128, 128);
+}
+
+pub type Image = Grid<Rgb>;
+
+// Maps each cell of `grid` to a square of `scale` x `scale` pixels.
+pub fn render<T, F>(grid: &Grid<T>, mut palette: F, scale: usize) -> Image
+where
+    F: FnMut(&T) -> Rgb,
+{
+    assert!(scale > 0, "Invalid scale 0");
+    let colors = grid.map(&mut palette);
+    Grid::from_fn(
+        grid.width() * scale,
+        grid.height() * scale,
+        |p: Vector2<usize>| colors[Vector2::new(p.x / scale, p.y / scale)],
+    )
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    Png,
+}
+
+impl Format {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Ppm => "ppm",
+            Format::Png => "png",
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Result<Format, String> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("ppm") => Ok(Format::Ppm),
+            Some("png") => Ok(Format::Png),
+            _ => Err(format!("Unknown image format for {:?}", path)),
+        }
+    }
+}
+
+pub fn write_ppm<W: Write>(image: &Image, mut w: W) -> io::Result<()> {
+    write!(w, "P6\n{} {}\n255\n", image.width(), image.height())?;
+    let bytes: Vec<u8> = image.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]).collect();
+    w.write_all(&bytes)
+}
+
+fn crc32(chunks: &[&[u8]]) -> u32 {
+    let mut table = [0u32; 256];
+    for (n, entry) in table.iter_mut().enumerate() {
+        let mut c = n as u32;
+        for _ in 0..8 {
+            c = if c & 1 != 0 {
+                0xedb88320 ^ (c >> 1)
+            } else {
+                c >> 1
+            }
+        }
+        *entry = c
+    }
+    let mut crc = 0xffffffff;
+    for &b in chunks.iter().flat_map(|c| c.iter()) {
+        crc = table[((crc ^ b as u32) & 0xff) as usize] ^ (crc >> 8)
+    }
+    crc ^ 0xffffffff
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &x in data {
+        a = (a + x as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+fn write_chunk<W: Write>(w: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    w.write_all(&(data.len() as u32).to_be_bytes())?;
+    w.write_all(kind)?;
+    w.write_all(data)?;
+    w.write_all(&crc32(&[kind, data]).to_be_bytes())
+}
+
+pub fn write_png<W: Write>(image: &Image, mut w: W) -> io::Result<()> {
+    w.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+    let mut header = vec![];
+    header.extend((image.width() as u32).to_be_bytes());
+    header.extend((image.height() as u32).to_be_bytes());
+    // 8 bits per channel, RGB, default compression, filtering and no interlacing.
+    header.extend([8, 2, 0, 0, 0]);
+    write_chunk(&mut w, b"IHDR", &header)?;
+
+    // Each row starts with its filter type (0 is none).
+    let mut raw = Vec::with_capacity((3 * image.width() + 1) * image.height());
+    for row in image.rows() {
+        raw.push(0);
+        raw.extend(row.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]));
+    }
+    // A zlib stream made of stored deflate blocks.
+    let mut data = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = raw.chunks(0xffff).collect();
+    if blocks.is_empty() {
+        data.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    for (i, block) in blocks.iter().enumerate() {
+        let last = i + 1 == blocks.len();
+        let len = block.len() as u16;
+        data.push(last as u8);
+        data.extend(len.to_le_bytes());
+        data.extend((!len).to_le_bytes());
+        data.extend(block.iter());
+    }
+    data.extend(adler32(&raw).to_be_bytes());
+    write_chunk(&mut w, b"IDAT", &data)?;
+
+    write_chunk(&mut w, b"IEND", &[])
+}
+
+pub fn write<W: Write>(image: &Image, format: Format, w: W) -> io::Result<()> {
+    match format {
+        Format::Ppm => write_ppm(image, w),
+        Format::Png => write_png(image, w),
+    }
+}
+
+// Writes `image` to `path`, in the format given by its extension.
+pub fn save(image: &Image, path: &Path) -> io::Result<()> {
+    let format =
+        Format::from_path(path).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let mut w = BufWriter::new(File::create(path)?);
+    write(image, format, &mut w)?;
+    w.flush()
+}
+
+// Writes numbered frames `frame_00000.png`, `frame_00001.png`, ... to a directory, typically one
+// per step of a simulation.
+pub struct Frames {
+    dir: PathBuf,
+    format: Format,
+    scale: usize,
+    count: usize,
+}
+
+impl Frames {
+    // Creates `dir` if needed.
+    pub fn new(dir: &Path, format: Format, scale: usize) -> io::Result<Frames> {
+        std::fs::create_dir_all(dir)?;
+        Ok(Frames {
+            dir: dir.to_path_buf(),
+            format,
+            scale,
+            count: 0,
+        })
+    }
+
+    // Frames are only written on demand, when the `AOC_FRAMES` environment variable is set to a
+    // directory. They go to the subdirectory `name` of that directory, as PNG, or as PPM when
+    // `AOC_FRAMES_FORMAT` is `ppm`. Panics if the directory cannot be created.
+    pub fn from_env(name: &str, scale: usize) -> Option<Frames> {
+        let root = std::env::var_os("AOC_FRAMES")?;
+        let format = match std::env::var("AOC_FRAMES_FORMAT").as_deref() {
+            Ok("ppm") => Format::Ppm,
+            _ => Format::Png,
+        };
+        let dir = Path::new(&root).join(name);
+        match Frames::new(&dir, format, scale) {
+            Ok(frames) => Some(frames),
+            Err(e) => panic!("Cannot create {:?}: {}", dir, e),
+        }
+    }
+
+    // Number of frames written so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn write<T, F>(&mut self, grid: &Grid<T>, palette: F) -> io::Result<()>
+    where
+        F: FnMut(&T) -> Rgb,
+    {
+        let image = render(grid, palette, self.scale);
+        let name = format!("frame_{:05}.{}", self.count, self.format.extension());
+        self.count += 1;
+        save(&image, &self.dir.join(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard() -> Image {
+        let grid = Grid::parse("#.\n.#\n", |c| Some(c == '#')).unwrap();
+        render(&grid, |&b| if b { Rgb::WHITE } else { Rgb::BLACK }, 2)
+    }
+
+    #[test]
+    fn test_render() {
+        let image = checkerboard();
+        assert_eq!((image.width(), image.height()), (4, 4));
+        assert_eq!(
+            image.row(1),
+            [Rgb::WHITE, Rgb::WHITE, Rgb::BLACK, Rgb::BLACK]
+        );
+    }
+
+    #[test]
+    fn test_ppm() {
+        let mut bytes = vec![];
+        write_ppm(&checkerboard(), &mut bytes).unwrap();
+        assert!(bytes.starts_with(b"P6\n4 4\n255\n"));
+        assert_eq!(bytes.len(), 11 + 4 * 4 * 3);
+    }
+
+    #[test]
+    fn test_png() {
+        // Checksums from the zlib documentation and the PNG specification.
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+        assert_eq!(crc32(&[b"IEND"]), 0xae426082);
+
+        let mut bytes = vec![];
+        write_png(&checkerboard(), &mut bytes).unwrap();
+        assert!(bytes.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x04\0\0\0\x04"));
+        assert!(bytes.ends_with(b"\0\0\0\0IEND\xae\x42\x60\x82"));
+        // 8 (signature) + 25 (IHDR) + 12 (IEND) + 12 (IDAT) + 2 (zlib header) + 5 (block
+        // header) + 4 * 13 (rows) + 4 (checksum)
+        assert_eq!(bytes.len(), 8 + 25 + 12 + 12 + 2 + 5 + 52 + 4);
+    }
+
+    #[test]
+    fn test_frames() {
+        let dir = std::env::temp_dir().join(format!("geometry_frames_{}", std::process::id()));
+        let grid = Grid::new(3, 2, 0u8);
+        let mut frames = Frames::new(&dir, Format::Ppm, 1).unwrap();
+        for _ in 0..3 {
+            frames.write(&grid, |&c| Rgb(c, c, c)).unwrap();
+        }
+        assert_eq!(frames.count(), 3);
+        assert!(dir.join("frame_00002.ppm").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
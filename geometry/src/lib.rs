@@ -0,0 +1,71 @@
+// Geometry primitives shared by the solutions of all years.
+
+pub mod aabb;
+pub mod automaton;
+pub mod direction;
+pub mod grid;
+pub mod image;
+pub mod ocr;
+pub mod sparse_grid;
+pub mod vector2;
+pub mod vector3;
+
+pub use aabb::Aabb;
+pub use direction::{Direction, Direction8, HexDirection};
+pub use grid::Grid;
+pub use sparse_grid::SparseGrid;
+pub use vector2::Vector2;
+pub use vector3::Vector3;
+
+// Integer types that can be used as coordinates of boxes and grids.
+pub trait Coordinate:
+    Copy + Ord + std::fmt::Debug + std::ops::Add<Output = Self> + std::ops::Sub<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    fn to_i128(self) -> i128;
+    // Panics when `x` does not fit, rather than wrapping around.
+    fn from_i128(x: i128) -> Self;
+}
+
+macro_rules! coordinate {
+    ($($t:ty),*) => {
+        $(impl Coordinate for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            fn to_i128(self) -> i128 {
+                self as i128
+            }
+            fn from_i128(x: i128) -> Self {
+                <$t>::try_from(x).expect("coordinate out of range")
+            }
+        })*
+    };
+}
+
+coordinate!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+// Absolute value for any signed type, without depending on a numeric traits crate.
+fn abs<T>(x: T) -> T
+where
+    T: Ord + Copy + std::ops::Neg<Output = T>,
+{
+    std::cmp::max(x, -x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coordinate() {
+        assert_eq!(u8::from_i128(255), 255);
+        assert_eq!(i8::from_i128(-128).to_i128(), -128);
+    }
+
+    #[test]
+    #[should_panic(expected = "coordinate out of range")]
+    fn test_coordinate_out_of_range() {
+        u8::from_i128(256);
+    }
+}
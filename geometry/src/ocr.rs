@@ -0,0 +1,219 @@
+// Recognition of the block letters that some puzzles draw as their answer.
+//
+// Advent of Code uses two fonts: a small one, 6 pixels high and (mostly) 4 pixels wide, and a
+// large one, 10 pixels high and 6 pixels wide. The text is cropped to its lit pixels, the font
+// is chosen from the resulting height, and letters are read from cells of a fixed width (5 and 8
+// pixels, with the spacing) from the left of the text: the small 'Y' is 5 pixels wide, and
+// touches the next letter. Only the letters that have been seen in puzzles are known.
+
+use crate::{Grid, Vector2};
+
+const SMALL: [(char, [&str; 6]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[rustfmt::skip]
+const LARGE: [(char, [&str; 10]); 15] = [
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#",
+           "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.",
+           "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....",
+           "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.",
+           "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.",
+           "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....",
+           "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######",
+           "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.",
+           "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....",
+           "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....",
+           "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#",
+           "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.",
+           "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.",
+           "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..",
+           "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..",
+           "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+// A glyph, as a list of columns without the blank ones on either side.
+type Glyph = Vec<Vec<bool>>;
+
+fn trim(mut columns: Glyph) -> Glyph {
+    while columns.last().is_some_and(|c| c.iter().all(|&b| !b)) {
+        columns.pop();
+    }
+    let blank = columns.iter().take_while(|c| c.iter().all(|&b| !b)).count();
+    columns.split_off(blank)
+}
+
+fn glyph(rows: &[&str]) -> Glyph {
+    let width = rows[0].len();
+    let columns = (0..width)
+        .map(|x| rows.iter().map(|row| row.as_bytes()[x] == b'#').collect())
+        .collect();
+    trim(columns)
+}
+
+// The letters of the font of the given height, and the width of their cells.
+fn font(height: usize) -> Option<(Vec<(char, Glyph)>, usize)> {
+    match height {
+        6 => Some((SMALL.iter().map(|(c, rows)| (*c, glyph(rows))).collect(), 5)),
+        10 => Some((LARGE.iter().map(|(c, rows)| (*c, glyph(rows))).collect(), 8)),
+        _ => None,
+    }
+}
+
+fn render(columns: &[Vec<bool>]) -> String {
+    let height = columns.first().map_or(0, |c| c.len());
+    (0..height)
+        .map(|y| {
+            let row: String = columns
+                .iter()
+                .map(|c| if c[y] { '#' } else { '.' })
+                .collect();
+            row + "\n"
+        })
+        .collect()
+}
+
+// Reads the text drawn by the `true` cells of `image`.
+pub fn recognize(image: &Grid<bool>) -> Result<String, String> {
+    let lit: Vec<Vector2<usize>> = image
+        .enumerate()
+        .filter(|(_, &b)| b)
+        .map(|(p, _)| p)
+        .collect();
+    let top = lit.iter().map(|p| p.y).min().ok_or("Empty image")?;
+    let bottom = lit.iter().map(|p| p.y).max().unwrap();
+    let height = bottom - top + 1;
+    let (font, width) = font(height).ok_or(format!("No font with letters of height {}", height))?;
+    let left = lit.iter().map(|p| p.x).min().unwrap();
+    let right = lit.iter().map(|p| p.x).max().unwrap();
+
+    let columns: Glyph = image
+        .columns()
+        .skip(left)
+        .take(right - left + 1)
+        .map(|c| c.skip(top).take(height).copied().collect())
+        .collect();
+    let mut result = String::new();
+    for cell in columns.chunks(width) {
+        let letter = trim(cell.to_vec());
+        if letter.is_empty() {
+            continue;
+        }
+        match font.iter().find(|(_, g)| *g == letter) {
+            Some((c, _)) => result.push(*c),
+            None => return Err(format!("Unknown letter:\n{}", render(&letter))),
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Grid<bool> {
+        Grid::parse(s, |c| Some(c == '#')).unwrap()
+    }
+
+    #[test]
+    fn test_small() {
+        let image = parse(
+            "\
+.##..###..#..#.#...#
+#..#.#..#.#..#.#...#
+#..#.###..####..#.#.
+####.#..#.#..#...#..
+#..#.#..#.#..#...#..
+#..#.###..#..#...#..",
+        );
+        assert_eq!(recognize(&image), Ok("ABHY".to_string()));
+    }
+
+    // The 'Y' touches the next letter.
+    #[test]
+    fn test_small_y() {
+        let image = parse(
+            "\
+.##..#...####.
+#..#.#...##..#
+#..#..#.#.###.
+####...#..#..#
+#..#...#..#..#
+#..#...#..###.",
+        );
+        assert_eq!(recognize(&image), Ok("AYB".to_string()));
+    }
+
+    #[test]
+    fn test_large() {
+        // Surrounded by blank rows and columns, which are ignored.
+        let image = parse(
+            "\
+...............
+.#....#..#####.
+.##...#..#....#
+.##...#..#....#
+.#.#..#..#....#
+.#.#..#..#####.
+.#..#.#..#..#..
+.#..#.#..#...#.
+.#...##..#...#.
+.#...##..#....#
+.#....#..#....#
+...............",
+        );
+        assert_eq!(recognize(&image), Ok("NR".to_string()));
+    }
+
+    #[test]
+    fn test_all_letters() {
+        for (c, rows) in SMALL.iter() {
+            assert_eq!(recognize(&parse(&rows.join("\n"))), Ok(c.to_string()));
+        }
+        for (c, rows) in LARGE.iter() {
+            assert_eq!(recognize(&parse(&rows.join("\n"))), Ok(c.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(recognize(&parse("....\n....")).is_err());
+        assert!(recognize(&parse("#\n#\n#")).is_err());
+        let unknown = "####\n#..#\n#..#\n#..#\n#..#\n####";
+        assert_eq!(
+            recognize(&parse(unknown)),
+            Err(format!("Unknown letter:\n{}\n", unknown))
+        );
+    }
+}
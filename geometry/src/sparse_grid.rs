@@ -0,0 +1,242 @@
+// An unbounded 2d grid, storing only the cells that have been set. Other cells have a default
+// value. The bounding box of the stored cells is kept up to date, so that the grid can be
+// rendered or converted to a dense `Grid`.
+
+use crate::{Aabb, Coordinate, Grid, Vector2};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Index, IndexMut};
+
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T, C = i64> {
+    cells: HashMap<Vector2<C>, T>,
+    default: T,
+    bounds: Option<Aabb<C, 2>>,
+}
+
+impl<T, C: Coordinate + Hash> SparseGrid<T, C> {
+    pub fn new(default: T) -> SparseGrid<T, C> {
+        SparseGrid {
+            cells: HashMap::new(),
+            default,
+            bounds: None,
+        }
+    }
+
+    pub fn default_value(&self) -> &T {
+        &self.default
+    }
+
+    // Number of stored cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // Whether the cell at `pos` has been set (possibly to the default value).
+    pub fn contains(&self, pos: Vector2<C>) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn get(&self, pos: Vector2<C>) -> &T {
+        self.cells.get(&pos).unwrap_or(&self.default)
+    }
+
+    // Returns the cell at `pos`, storing the default value there first if it was not set.
+    pub fn entry(&mut self, pos: Vector2<C>) -> &mut T
+    where
+        T: Clone,
+    {
+        self.extend_bounds(pos);
+        let default = &self.default;
+        self.cells.entry(pos).or_insert_with(|| default.clone())
+    }
+
+    pub fn insert(&mut self, pos: Vector2<C>, value: T) -> Option<T> {
+        self.extend_bounds(pos);
+        self.cells.insert(pos, value)
+    }
+
+    // Resets the cell at `pos` to the default value. The bounding box shrinks if needed, which
+    // takes a scan of the grid when `pos` lies on its border.
+    pub fn remove(&mut self, pos: Vector2<C>) -> Option<T> {
+        let result = self.cells.remove(&pos)?;
+        let on_border = self
+            .bounds
+            .is_some_and(|b| (0..2).any(|i| b.low[i] == pos[i] || b.high[i] == pos[i]));
+        if on_border {
+            self.bounds = Aabb::from_points(self.cells.keys().map(|&p| p.into()));
+        }
+        Some(result)
+    }
+
+    fn extend_bounds(&mut self, pos: Vector2<C>) {
+        match &mut self.bounds {
+            None => self.bounds = Some(Aabb::new(pos.into(), pos.into())),
+            Some(b) => b.extend(&pos.into()),
+        }
+    }
+
+    // The smallest box containing all the stored cells, or `None` if there are none.
+    pub fn bounds(&self) -> Option<Aabb<C, 2>> {
+        self.bounds
+    }
+
+    // The smallest box containing the stored cells that satisfy `f`, e.g. to crop a picture to
+    // its non-blank pixels.
+    pub fn bounds_where<F>(&self, mut f: F) -> Option<Aabb<C, 2>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        Aabb::from_points(
+            self.cells
+                .iter()
+                .filter(|(_, t)| f(t))
+                .map(|(&p, _)| p.into()),
+        )
+    }
+
+    // Iterates over the stored cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Vector2<C>, &T)> {
+        self.cells.iter().map(|(&p, t)| (p, t))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.values()
+    }
+
+    pub fn map<U, F>(&self, mut f: F) -> SparseGrid<U, C>
+    where
+        F: FnMut(&T) -> U,
+    {
+        SparseGrid {
+            cells: self.cells.iter().map(|(&p, t)| (p, f(t))).collect(),
+            default: f(&self.default),
+            bounds: self.bounds,
+        }
+    }
+
+    // Copies the cells of `viewport` to a dense grid, whose origin is the low corner of the
+    // viewport.
+    pub fn crop(&self, viewport: &Aabb<C, 2>) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let [width, height] = viewport.size();
+        let origin = Vector2::from(viewport.low);
+        Grid::from_fn(
+            width.to_i128() as usize,
+            height.to_i128() as usize,
+            |p: Vector2<C>| self.get(origin + p).clone(),
+        )
+    }
+
+    // Copies the bounding box of the stored cells to a dense grid. Returns the position of the
+    // origin of the grid along with the grid.
+    pub fn to_grid(&self) -> (Vector2<C>, Grid<T>)
+    where
+        T: Clone,
+    {
+        match self.bounds {
+            None => (
+                Vector2::new(C::ZERO, C::ZERO),
+                Grid::from_rows(vec![]).unwrap(),
+            ),
+            Some(b) => (Vector2::from(b.low), self.crop(&b)),
+        }
+    }
+
+    // Renders the cells of `viewport`, with one line per row.
+    pub fn render_viewport<F>(&self, viewport: &Aabb<C, 2>, mut f: F) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        let mut s = String::new();
+        let [low_x, low_y] = viewport.low;
+        let [high_x, high_y] = viewport.high;
+        let mut y = low_y;
+        while y <= high_y {
+            let mut x = low_x;
+            while x <= high_x {
+                s.push(f(self.get(Vector2::new(x, y))));
+                x = x + C::ONE
+            }
+            s.push('\n');
+            y = y + C::ONE
+        }
+        s
+    }
+
+    // Renders the bounding box of the stored cells.
+    pub fn render<F>(&self, f: F) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        match self.bounds {
+            None => String::new(),
+            Some(b) => self.render_viewport(&b, f),
+        }
+    }
+}
+
+impl<T, C: Coordinate + Hash> Index<Vector2<C>> for SparseGrid<T, C> {
+    type Output = T;
+    fn index(&self, pos: Vector2<C>) -> &T {
+        self.get(pos)
+    }
+}
+
+impl<T: Clone, C: Coordinate + Hash> IndexMut<Vector2<C>> for SparseGrid<T, C> {
+    fn index_mut(&mut self, pos: Vector2<C>) -> &mut T {
+        self.entry(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let mut g: SparseGrid<char> = SparseGrid::new('.');
+        assert_eq!(g.bounds(), None);
+        assert_eq!(g.render(|&c| c), "");
+        g.insert(Vector2::new(2, -1), '#');
+        g.insert(Vector2::new(-1, 1), '#');
+        g[Vector2::new(0, 0)] = 'o';
+        assert_eq!(g.bounds(), Some(Aabb::new([-1, -1], [2, 1])));
+        assert_eq!(g[Vector2::new(5, 5)], '.');
+        assert_eq!(g.len(), 3);
+        assert_eq!(g.render(|&c| c), "...#\n.o..\n#...\n");
+
+        g.remove(Vector2::new(2, -1));
+        assert_eq!(g.bounds(), Some(Aabb::new([-1, 0], [0, 1])));
+        g.remove(Vector2::new(0, 0));
+        g.remove(Vector2::new(-1, 1));
+        assert_eq!(g.bounds(), None);
+    }
+
+    #[test]
+    fn test_viewport() {
+        let mut g: SparseGrid<bool, i32> = SparseGrid::new(false);
+        for x in 0..4 {
+            g.insert(Vector2::new(x, x), true);
+        }
+        g.insert(Vector2::new(10, 10), false);
+        let viewport = g.bounds_where(|&b| b).unwrap();
+        assert_eq!(viewport, Aabb::new([0, 0], [3, 3]));
+        let render = |&b: &bool| if b { '#' } else { '.' };
+        let cropped = Aabb::new([1, 0], [3, 1]);
+        assert_eq!(g.render_viewport(&cropped, render), "...\n#..\n");
+        assert_eq!(g.crop(&cropped).render(render), "...\n#..\n");
+
+        let (origin, grid) = g.to_grid();
+        assert_eq!(origin, Vector2::new(0, 0));
+        assert_eq!((grid.width(), grid.height()), (11, 11));
+        assert_eq!(grid.iter().filter(|&&b| b).count(), 4);
+        assert!(grid[Vector2::new(2, 2)]);
+    }
+}
@@ -0,0 +1,201 @@
+use std::ops::{Add, AddAssign, Index, Mul, Neg, Sub, SubAssign};
+
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq, Ord, PartialOrd)]
+pub struct Vector2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Vector2<T> {
+    pub fn new(x: T, y: T) -> Vector2<T> {
+        Vector2 { x, y }
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vector2<T> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl<T: AddAssign> AddAssign for Vector2<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vector2<T> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Self {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+impl<T: SubAssign> SubAssign for Vector2<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vector2<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+// Scalar multiplication
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vector2<T> {
+    type Output = Self;
+    fn mul(self, k: T) -> Self {
+        Self {
+            x: self.x * k,
+            y: self.y * k,
+        }
+    }
+}
+
+impl<T> From<[T; 2]> for Vector2<T> {
+    fn from(a: [T; 2]) -> Self {
+        let [x, y] = a;
+        Vector2 { x, y }
+    }
+}
+
+impl<T> From<Vector2<T>> for [T; 2] {
+    fn from(v: Vector2<T>) -> Self {
+        [v.x, v.y]
+    }
+}
+
+impl<T> Index<usize> for Vector2<T> {
+    type Output = T;
+    fn index(&self, index: usize) -> &T {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("Invalid index"),
+        }
+    }
+}
+
+impl<T> Vector2<T>
+where
+    T: Ord + Copy + Add<Output = T> + Sub<Output = T> + Neg<Output = T>,
+{
+    // Manhattan norm
+    pub fn norm1(&self) -> T {
+        crate::abs(self.x) + crate::abs(self.y)
+    }
+
+    // Chebyshev norm
+    pub fn norm_inf(&self) -> T {
+        std::cmp::max(crate::abs(self.x), crate::abs(self.y))
+    }
+
+    pub fn manhattan(&self, other: &Self) -> T {
+        (*self - *other).norm1()
+    }
+
+    pub fn chebyshev(&self, other: &Self) -> T {
+        (*self - *other).norm_inf()
+    }
+
+    // Rotations by a quarter turn, counter-clockwise and clockwise, in a frame where the y axis
+    // points up. When the y axis points down (e.g., rows of a grid), the visual sense is
+    // reversed.
+    pub fn rotate_ccw(&self) -> Self {
+        Vector2::new(-self.y, self.x)
+    }
+
+    pub fn rotate_cw(&self) -> Self {
+        Vector2::new(self.y, -self.x)
+    }
+}
+
+use core::borrow::Borrow;
+
+pub fn bounding_box<T, I>(mut it: I) -> Option<(Vector2<T>, Vector2<T>)>
+where
+    I: Iterator,
+    I::Item: Borrow<Vector2<T>>,
+    T: Ord + Copy,
+{
+    match it.next() {
+        None => None,
+        Some(p) => {
+            let mut minx = p.borrow().x;
+            let mut maxx = p.borrow().x;
+            let mut miny = p.borrow().y;
+            let mut maxy = p.borrow().y;
+            for p in it {
+                minx = std::cmp::min(minx, p.borrow().x);
+                miny = std::cmp::min(miny, p.borrow().y);
+                maxx = std::cmp::max(maxx, p.borrow().x);
+                maxy = std::cmp::max(maxy, p.borrow().y);
+            }
+            Some((Vector2::new(minx, miny), Vector2::new(maxx, maxy)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operators() {
+        let mut a = Vector2::new(3, -4);
+        let b = Vector2::new(1, 2);
+        assert_eq!(a + b, Vector2::new(4, -2));
+        assert_eq!(a - b, Vector2::new(2, -6));
+        assert_eq!(-a, Vector2::new(-3, 4));
+        assert_eq!(a * 3, Vector2::new(9, -12));
+        a += b;
+        assert_eq!(a, Vector2::new(4, -2));
+        a -= b * 2;
+        assert_eq!(a, Vector2::new(2, -6));
+        assert_eq!((a[0], a[1]), (2, -6));
+    }
+
+    #[test]
+    fn test_norms() {
+        let a = Vector2::new(3i64, -4);
+        assert_eq!(a.norm1(), 7);
+        assert_eq!(a.norm_inf(), 4);
+        assert_eq!(a.manhattan(&Vector2::new(1, 1)), 7);
+        assert_eq!(a.chebyshev(&Vector2::new(1, 1)), 5);
+    }
+
+    #[test]
+    fn test_rotations() {
+        let a = Vector2::new(1, 0);
+        assert_eq!(a.rotate_ccw(), Vector2::new(0, 1));
+        assert_eq!(a.rotate_cw(), Vector2::new(0, -1));
+        let b = Vector2::new(3, 7);
+        assert_eq!(b.rotate_ccw().rotate_cw(), b);
+        assert_eq!(b.rotate_ccw().rotate_ccw(), -b);
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let points = [Vector2::new(1, 5), Vector2::new(-2, 3), Vector2::new(4, -1)];
+        assert_eq!(
+            bounding_box(points.iter()),
+            Some((Vector2::new(-2, -1), Vector2::new(4, 5)))
+        );
+        assert_eq!(bounding_box(std::iter::empty::<Vector2<i32>>()), None);
+    }
+}
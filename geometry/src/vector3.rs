@@ -0,0 +1,227 @@
+use std::ops::{Add, AddAssign, Index, Mul, Neg, Sub, SubAssign};
+
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq, Ord, PartialOrd)]
+pub struct Vector3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Vector3<T> {
+    pub fn new(x: T, y: T, z: T) -> Vector3<T> {
+        Vector3 { x, y, z }
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vector3<T> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl<T: AddAssign> AddAssign for Vector3<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+        self.z += other.z;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vector3<T> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Self {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+}
+
+impl<T: SubAssign> SubAssign for Vector3<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+        self.z -= other.z;
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vector3<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+// Scalar multiplication
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vector3<T> {
+    type Output = Self;
+    fn mul(self, k: T) -> Self {
+        Self {
+            x: self.x * k,
+            y: self.y * k,
+            z: self.z * k,
+        }
+    }
+}
+
+impl<T> From<[T; 3]> for Vector3<T> {
+    fn from(a: [T; 3]) -> Self {
+        let [x, y, z] = a;
+        Vector3 { x, y, z }
+    }
+}
+
+impl<T> From<Vector3<T>> for [T; 3] {
+    fn from(v: Vector3<T>) -> Self {
+        [v.x, v.y, v.z]
+    }
+}
+
+impl<T> Index<usize> for Vector3<T> {
+    type Output = T;
+    fn index(&self, index: usize) -> &T {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Invalid index"),
+        }
+    }
+}
+
+impl<T> Vector3<T>
+where
+    T: Ord + Copy + Add<Output = T> + Sub<Output = T> + Neg<Output = T>,
+{
+    // Manhattan norm
+    pub fn norm1(&self) -> T {
+        crate::abs(self.x) + crate::abs(self.y) + crate::abs(self.z)
+    }
+
+    // Chebyshev norm
+    pub fn norm_inf(&self) -> T {
+        std::cmp::max(
+            crate::abs(self.x),
+            std::cmp::max(crate::abs(self.y), crate::abs(self.z)),
+        )
+    }
+
+    pub fn manhattan(&self, other: &Self) -> T {
+        (*self - *other).norm1()
+    }
+
+    pub fn chebyshev(&self, other: &Self) -> T {
+        (*self - *other).norm_inf()
+    }
+
+    // Quarter turns around each axis, counter-clockwise when looking from the positive side of
+    // the axis towards the origin (right-hand rule).
+    pub fn rotate_x(&self) -> Self {
+        Vector3::new(self.x, -self.z, self.y)
+    }
+
+    pub fn rotate_y(&self) -> Self {
+        Vector3::new(self.z, self.y, -self.x)
+    }
+
+    pub fn rotate_z(&self) -> Self {
+        Vector3::new(-self.y, self.x, self.z)
+    }
+}
+
+use core::borrow::Borrow;
+
+pub fn bounding_box<T, I>(mut it: I) -> Option<(Vector3<T>, Vector3<T>)>
+where
+    I: Iterator,
+    I::Item: Borrow<Vector3<T>>,
+    T: Ord + Copy,
+{
+    match it.next() {
+        None => None,
+        Some(p) => {
+            let mut minx = p.borrow().x;
+            let mut maxx = p.borrow().x;
+            let mut miny = p.borrow().y;
+            let mut maxy = p.borrow().y;
+            let mut minz = p.borrow().z;
+            let mut maxz = p.borrow().z;
+
+            for p in it {
+                minx = std::cmp::min(minx, p.borrow().x);
+                miny = std::cmp::min(miny, p.borrow().y);
+                minz = std::cmp::min(minz, p.borrow().z);
+
+                maxx = std::cmp::max(maxx, p.borrow().x);
+                maxy = std::cmp::max(maxy, p.borrow().y);
+                maxz = std::cmp::max(maxz, p.borrow().z);
+            }
+            Some((
+                Vector3::new(minx, miny, minz),
+                Vector3::new(maxx, maxy, maxz),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operators() {
+        let mut a = Vector3::new(3, -4, 5);
+        let b = Vector3::new(1, 2, 3);
+        assert_eq!(a + b, Vector3::new(4, -2, 8));
+        assert_eq!(a - b, Vector3::new(2, -6, 2));
+        assert_eq!(-a, Vector3::new(-3, 4, -5));
+        assert_eq!(a * 2, Vector3::new(6, -8, 10));
+        a += b;
+        assert_eq!(a, Vector3::new(4, -2, 8));
+        a -= b;
+        assert_eq!((a[0], a[1], a[2]), (3, -4, 5));
+        assert_eq!(a.norm1(), 12);
+        assert_eq!(a.norm_inf(), 5);
+        let b: [i32; 3] = a.into();
+        assert_eq!(Vector3::from(b), a);
+    }
+
+    #[test]
+    fn test_rotations() {
+        let x = Vector3::new(1, 0, 0);
+        let y = Vector3::new(0, 1, 0);
+        let z = Vector3::new(0, 0, 1);
+        assert_eq!(x.rotate_z(), y);
+        assert_eq!(y.rotate_x(), z);
+        assert_eq!(z.rotate_y(), x);
+        let v = Vector3::new(1, 2, 3);
+        assert_eq!(v.rotate_x().rotate_x().rotate_x().rotate_x(), v);
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let points = [
+            Vector3::new(1, 5, 0),
+            Vector3::new(-2, 3, 7),
+            Vector3::new(4, -1, 2),
+        ];
+        assert_eq!(
+            bounding_box(points.iter()),
+            Some((Vector3::new(-2, -1, 0), Vector3::new(4, 5, 7)))
+        );
+        assert_eq!(bounding_box(std::iter::empty::<Vector3<i32>>()), None);
+        let expected = crate::Aabb::from_points(points.iter().map(|&p| p.into())).unwrap();
+        let (low, high) = bounding_box(points.iter()).unwrap();
+        assert_eq!(crate::Aabb::new(low.into(), high.into()), expected);
+    }
+}
@@ -0,0 +1,200 @@
+// Cycle detection for iterated functions, e.g. simulations that eventually loop.
+//
+// Starting from some state `x0`, iterating `step` gives `x0, x1, x2, ...`. If there are finitely
+// many states, this sequence is eventually periodic: after a prefix of `start` states, the same
+// `period` states repeat forever. This is what makes it possible to get the state after a
+// billion steps without computing them all.
+//
+// There are two ways to find the cycle: keep the history of the states in a hash table (which
+// also gives the states of the cycle for free), or use Brent's algorithm, which keeps only a
+// couple of states at a time but computes the cycle twice.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    // The index of the first state that is repeated.
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    // The index of a state equal to `x_n`, in `0..start + period`.
+    pub fn reduce(&self, n: u64) -> usize {
+        let start = self.start as u64;
+        if n < start {
+            n as usize
+        } else {
+            self.start + ((n - start) % self.period as u64) as usize
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct History<S> {
+    pub cycle: Cycle,
+    // The states up to the first repetition, included: `states[start + period]` is equal to
+    // `states[start]`.
+    pub states: Vec<S>,
+}
+
+impl<S> History<S> {
+    // The state after `n` steps.
+    pub fn state_at(&self, n: u64) -> &S {
+        &self.states[self.cycle.reduce(n)]
+    }
+
+    // The first state that repeats.
+    pub fn first_repeated(&self) -> &S {
+        &self.states[self.cycle.start]
+    }
+}
+
+// Iterates `step` from `init` until a state repeats.
+pub fn find_cycle<S, F>(init: S, step: F) -> History<S>
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    find_cycle_by_key(init, step, |s| s.clone())
+}
+
+// Iterates `step` from `init` until the key of a state repeats. The key must determine the rest
+// of the evolution: it can leave out parts of the state that do not influence the next steps
+// (e.g. a position that drifts, while the shape stays the same).
+pub fn find_cycle_by_key<S, K, F, G>(init: S, mut step: F, mut key: G) -> History<S>
+where
+    K: Hash + Eq,
+    F: FnMut(&S) -> S,
+    G: FnMut(&S) -> K,
+{
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut states = vec![init];
+    loop {
+        let i = states.len() - 1;
+        if let Some(&start) = seen.get(&key(&states[i])) {
+            let cycle = Cycle {
+                start,
+                period: i - start,
+            };
+            return History { cycle, states };
+        }
+        seen.insert(key(&states[i]), i);
+        let next = step(&states[i]);
+        states.push(next)
+    }
+}
+
+// Brent's algorithm: finds the cycle using a constant number of states.
+pub fn brent<S, F>(init: &S, mut step: F) -> Cycle
+where
+    S: Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    // Find the period, by looking for the first repetition of a state within windows of
+    // increasing (power of two) sizes.
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = init.clone();
+    let mut hare = step(init);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    // Then the start of the cycle, with two states `period` steps apart.
+    let mut tortoise = init.clone();
+    let mut hare = init.clone();
+    for _ in 0..period {
+        hare = step(&hare)
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, period }
+}
+
+// The state after `n` steps, computed with `brent`.
+pub fn state_at<S, F>(init: &S, mut step: F, n: u64) -> S
+where
+    S: Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let cycle = brent(init, &mut step);
+    let mut state = init.clone();
+    for _ in 0..cycle.reduce(n) {
+        state = step(&state)
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    // x -> x^2 + 1 mod 255 from 3: 3, 10, 101, 2, 5, 26, 167, 95, 101, ...
+    fn step(x: &u64) -> u64 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let history = find_cycle(3, step);
+        assert_eq!(
+            history.cycle,
+            Cycle {
+                start: 2,
+                period: 6
+            }
+        );
+        assert_eq!(history.states, vec![3, 10, 101, 2, 5, 26, 167, 95, 101]);
+        assert_eq!(*history.first_repeated(), 101);
+        assert_eq!(*history.state_at(1), 10);
+        assert_eq!(*history.state_at(14), 101);
+        assert_eq!(*history.state_at(1_000_000_000_000), 5);
+        assert_eq!(brent(&3, step), history.cycle);
+        assert_eq!(state_at(&3, step, 1_000_000_000_000), 5);
+    }
+
+    #[test]
+    fn test_by_key() {
+        // A counter that drifts, next to a value that cycles.
+        let history = find_cycle_by_key((0, 0), |&(d, x)| (d + 10, (x + 1) % 3), |&(_, x)| x);
+        assert_eq!(
+            history.cycle,
+            Cycle {
+                start: 0,
+                period: 3
+            }
+        );
+        assert_eq!(history.states[3], (30, 0));
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = Rng::new(1);
+        for _ in 0..100 {
+            let seed = rng.next_u64();
+            let modulus = 1 + (seed >> 33) % 1000;
+            let f = |x: &u64| (x * x + seed % 7) % modulus;
+            let init = seed % modulus;
+            let history = find_cycle(init, f);
+            assert_eq!(brent(&init, f), history.cycle);
+            let mut x = init;
+            for n in 0..2000 {
+                assert_eq!(*history.state_at(n), x);
+                x = f(&x)
+            }
+        }
+    }
+}
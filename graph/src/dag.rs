@@ -0,0 +1,277 @@
+// Directed acyclic graphs of tasks and their dependencies: topological orders, and scheduling of
+// the tasks on a pool of workers.
+//
+// Nodes are kept ordered, so that when several tasks are available, the smallest one is picked
+// first. This makes topological orders lexicographically smallest, and schedules deterministic.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
+
+#[derive(Clone, Debug)]
+pub struct Dag<N> {
+    successors: BTreeMap<N, BTreeSet<N>>,
+    predecessors: BTreeMap<N, BTreeSet<N>>,
+}
+
+impl<N: Ord + Clone> Default for Dag<N> {
+    fn default() -> Self {
+        Dag::new()
+    }
+}
+
+impl<N: Ord + Clone> Dag<N> {
+    pub fn new() -> Dag<N> {
+        Dag {
+            successors: BTreeMap::new(),
+            predecessors: BTreeMap::new(),
+        }
+    }
+
+    // Each edge `(a, b)` means that `a` must come before `b`.
+    pub fn from_edges<I>(edges: I) -> Dag<N>
+    where
+        I: IntoIterator<Item = (N, N)>,
+    {
+        let mut dag = Dag::new();
+        for (a, b) in edges {
+            dag.add_edge(a, b)
+        }
+        dag
+    }
+
+    pub fn add_node(&mut self, n: N) {
+        self.successors.entry(n.clone()).or_default();
+        self.predecessors.entry(n).or_default();
+    }
+
+    pub fn add_edge(&mut self, before: N, after: N) {
+        self.add_node(before.clone());
+        self.add_node(after.clone());
+        self.successors
+            .get_mut(&before)
+            .unwrap()
+            .insert(after.clone());
+        self.predecessors.get_mut(&after).unwrap().insert(before);
+    }
+
+    pub fn len(&self) -> usize {
+        self.successors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.successors.is_empty()
+    }
+
+    // All the nodes, in increasing order.
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.successors.keys()
+    }
+
+    pub fn successors(&self, n: &N) -> impl Iterator<Item = &N> {
+        self.successors.get(n).into_iter().flatten()
+    }
+
+    pub fn predecessors(&self, n: &N) -> impl Iterator<Item = &N> {
+        self.predecessors.get(n).into_iter().flatten()
+    }
+
+    // The nodes without predecessors, in increasing order.
+    pub fn roots(&self) -> Vec<N> {
+        self.predecessors
+            .iter()
+            .filter(|(_, p)| p.is_empty())
+            .map(|(n, _)| n.clone())
+            .collect()
+    }
+
+    fn in_degrees(&self) -> BTreeMap<&N, usize> {
+        self.predecessors
+            .iter()
+            .map(|(n, p)| (n, p.len()))
+            .collect()
+    }
+
+    // Returns the lexicographically smallest order in which each node comes after its
+    // predecessors. If there is none, returns a cycle of the graph instead, as a list of nodes
+    // where each one is a predecessor of the next (and the last one of the first).
+    pub fn toposort(&self) -> Result<Vec<N>, Vec<N>> {
+        let mut in_degree = self.in_degrees();
+        let mut ready: BinaryHeap<Reverse<&N>> = in_degree
+            .iter()
+            .filter(|(_, &d)| d == 0)
+            .map(|(&n, _)| Reverse(n))
+            .collect();
+        let mut result = Vec::with_capacity(self.len());
+        while let Some(Reverse(n)) = ready.pop() {
+            result.push(n.clone());
+            for s in self.successors(n) {
+                let d = in_degree.get_mut(s).unwrap();
+                *d -= 1;
+                if *d == 0 {
+                    ready.push(Reverse(s))
+                }
+            }
+        }
+        if result.len() == self.len() {
+            Ok(result)
+        } else {
+            // The remaining nodes all have a remaining predecessor: walking back through them
+            // eventually loops.
+            let remaining = |n: &N| in_degree[n] > 0;
+            let mut walk: Vec<&N> = vec![*in_degree.keys().find(|n| remaining(n)).unwrap()];
+            loop {
+                let last = walk.last().unwrap();
+                let prev = self.predecessors(last).find(|n| remaining(n)).unwrap();
+                if let Some(i) = walk.iter().position(|&n| n == prev) {
+                    let mut cycle: Vec<N> = walk[i..].iter().map(|&n| n.clone()).collect();
+                    cycle.reverse();
+                    return Err(cycle);
+                }
+                walk.push(prev)
+            }
+        }
+    }
+
+    // Runs the tasks on `workers` workers, where task `n` takes `cost(n)` units of time and can
+    // only start once its predecessors are done. Whenever a worker is idle, it starts the
+    // smallest available task. Returns the cycle that prevents it if the tasks cannot all be
+    // run.
+    pub fn schedule<F>(&self, workers: usize, mut cost: F) -> Result<Schedule<N>, Vec<N>>
+    where
+        F: FnMut(&N) -> usize,
+    {
+        assert!(workers > 0, "Cannot schedule tasks without workers");
+        self.toposort()?;
+
+        let mut in_degree = self.in_degrees();
+        let mut ready: BinaryHeap<Reverse<&N>> = in_degree
+            .iter()
+            .filter(|(_, &d)| d == 0)
+            .map(|(&n, _)| Reverse(n))
+            .collect();
+        // The tasks in progress, by end time, and then by worker.
+        let mut running: BinaryHeap<Reverse<(usize, usize, &N)>> = BinaryHeap::new();
+        let mut idle: BTreeSet<usize> = (0..workers).collect();
+        let mut clock = 0;
+        let mut tasks = vec![];
+        loop {
+            while !idle.is_empty() && !ready.is_empty() {
+                let worker = idle.pop_first().unwrap();
+                let Reverse(n) = ready.pop().unwrap();
+                let end = clock + cost(n);
+                tasks.push(Task {
+                    id: n.clone(),
+                    worker,
+                    start: clock,
+                    end,
+                });
+                running.push(Reverse((end, worker, n)));
+            }
+            // Wait for the next tasks to complete.
+            let Some(Reverse((end, _, _))) = running.peek() else {
+                break;
+            };
+            clock = *end;
+            while let Some(Reverse((end, worker, n))) = running.peek().copied() {
+                if end > clock {
+                    break;
+                }
+                running.pop();
+                idle.insert(worker);
+                for s in self.successors(n) {
+                    let d = in_degree.get_mut(s).unwrap();
+                    *d -= 1;
+                    if *d == 0 {
+                        ready.push(Reverse(s))
+                    }
+                }
+            }
+        }
+        Ok(Schedule {
+            duration: clock,
+            tasks,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Task<N> {
+    pub id: N,
+    pub worker: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Schedule<N> {
+    // The time at which the last task completes.
+    pub duration: usize,
+    // The tasks, in the order in which they were started.
+    pub tasks: Vec<Task<N>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Dag<char> {
+        Dag::from_edges(vec![
+            ('C', 'A'),
+            ('C', 'F'),
+            ('A', 'B'),
+            ('A', 'D'),
+            ('B', 'E'),
+            ('D', 'E'),
+            ('F', 'E'),
+        ])
+    }
+
+    #[test]
+    fn test_toposort() {
+        let dag = example();
+        assert_eq!(dag.roots(), vec!['C']);
+        assert_eq!(dag.toposort(), Ok("CABDFE".chars().collect()));
+
+        let mut dag = Dag::from_edges(vec![(3, 1), (2, 1)]);
+        dag.add_node(0);
+        assert_eq!(dag.toposort(), Ok(vec![0, 2, 3, 1]));
+    }
+
+    #[test]
+    fn test_cycle() {
+        let dag = Dag::from_edges(vec![(0, 1), (1, 2), (2, 3), (3, 4), (4, 2), (1, 5)]);
+        let cycle = dag.toposort().unwrap_err();
+        assert_eq!(cycle.len(), 3);
+        for (i, n) in cycle.iter().enumerate() {
+            let next = cycle[(i + 1) % cycle.len()];
+            assert!(dag.successors(n).any(|&s| s == next));
+        }
+        assert_eq!(dag.schedule(2, |_| 1).unwrap_err().len(), 3);
+        assert_eq!(Dag::from_edges(vec![(7, 7)]).toposort(), Err(vec![7]));
+    }
+
+    #[test]
+    fn test_schedule() {
+        let dag = example();
+        let cost = |&c: &char| (c as u8 - b'A' + 1) as usize;
+        let schedule = dag.schedule(2, cost).unwrap();
+        assert_eq!(schedule.duration, 15);
+        let order: String = schedule.tasks.iter().map(|t| t.id).collect();
+        assert_eq!(order, "CAFBDE");
+        assert_eq!(
+            schedule.tasks[2],
+            Task {
+                id: 'F',
+                worker: 1,
+                start: 3,
+                end: 9
+            }
+        );
+
+        // With a single worker, tasks run in topological order.
+        let schedule = dag.schedule(1, cost).unwrap();
+        assert_eq!(schedule.duration, 21);
+        let order: String = schedule.tasks.iter().map(|t| t.id).collect();
+        assert_eq!(order, "CABDFE");
+    }
+}
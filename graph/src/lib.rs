@@ -0,0 +1,13 @@
+// Graph algorithms shared by the solutions of all years.
+
+pub mod cycle;
+pub mod dag;
+#[cfg(any(test, feature = "test-util"))]
+pub mod rng;
+pub mod search;
+pub mod union_find;
+
+pub use cycle::{find_cycle, find_cycle_by_key, Cycle, History};
+pub use dag::{Dag, Schedule, Task};
+pub use search::*;
+pub use union_find::{HashUnionFind, UnionFind};
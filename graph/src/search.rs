@@ -0,0 +1,399 @@
+// Searches in implicit graphs. A graph is described by the `Neighbours` trait: the outgoing
+// edges of a node, each with the node it leads to and a label (e.g. the direction to take, or
+// the key to pick up). Edges have a weight of 1 by default, which weighted graphs override.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+pub trait Neighbours<N, E> {
+    fn neighbours<K>(&self, n: &K) -> Vec<(N, E)>
+    where
+        N: std::borrow::Borrow<K>,
+        K: ToOwned<Owned = N> + Clone;
+
+    // The cost of following `edge`, for `dijkstra` and `astar`.
+    fn weight(&self, _edge: &E) -> u64 {
+        1
+    }
+}
+
+// A graph given by a function from a node to its outgoing edges.
+pub struct FromFn<F>(pub F);
+
+impl<N, E, F> Neighbours<N, E> for FromFn<F>
+where
+    F: Fn(&N) -> Vec<(N, E)>,
+{
+    fn neighbours<K>(&self, n: &K) -> Vec<(N, E)>
+    where
+        N: std::borrow::Borrow<K>,
+        K: ToOwned<Owned = N> + Clone,
+    {
+        (self.0)(&n.to_owned())
+    }
+}
+
+// A graph whose edges are given by a function from a node to its outgoing edges, along with
+// their weights.
+pub struct WeightedFromFn<F>(pub F);
+
+impl<N, F> Neighbours<N, u64> for WeightedFromFn<F>
+where
+    F: Fn(&N) -> Vec<(N, u64)>,
+{
+    fn neighbours<K>(&self, n: &K) -> Vec<(N, u64)>
+    where
+        N: std::borrow::Borrow<K>,
+        K: ToOwned<Owned = N> + Clone,
+    {
+        (self.0)(&n.to_owned())
+    }
+
+    fn weight(&self, edge: &u64) -> u64 {
+        *edge
+    }
+}
+
+// Follows the predecessors recorded by a search, from `tgt` back to `src`, and returns the
+// labels of the edges along the way, in order.
+pub fn reconstruct_path<N, E>(prev: &HashMap<N, (N, E)>, src: &N, tgt: &N) -> Vec<E>
+where
+    N: Eq + Hash,
+    E: Clone,
+{
+    let mut ptr = tgt;
+    let mut acc: Vec<E> = vec![];
+    while ptr != src {
+        let (p, e) = &prev[ptr];
+        acc.push(e.clone());
+        ptr = p;
+    }
+    acc.reverse();
+    acc
+}
+
+// Returns the labels of the edges of a shortest path from `src` to `tgt`, ignoring weights.
+pub fn bfs<T, N, E>(state: &T, src: &N, tgt: &N) -> Option<Vec<E>>
+where
+    N: Eq + Hash + Clone,
+    E: Clone,
+    T: Neighbours<N, E>,
+{
+    let mut visited = HashSet::new();
+    let mut todo: VecDeque<N> = VecDeque::new();
+    let mut prev: HashMap<N, (N, E)> = HashMap::new();
+    todo.push_back(src.clone());
+
+    while let Some(node) = todo.pop_front() {
+        if node == *tgt {
+            return Some(reconstruct_path(&prev, src, tgt));
+        } else if visited.insert(node.clone()) {
+            for (next, edge) in state.neighbours(&node).into_iter() {
+                if next != *src && !prev.contains_key(&next) {
+                    prev.insert(next.clone(), (node.clone(), edge));
+                    todo.push_back(next)
+                }
+            }
+        }
+    }
+    None
+}
+
+// The distance (number of edges) from `src` to each node reachable from it.
+pub fn flood_fill<T, N, E>(state: &T, src: &N) -> HashMap<N, usize>
+where
+    N: Eq + Hash + Clone,
+    T: Neighbours<N, E>,
+{
+    let mut distance = HashMap::new();
+    let mut todo = VecDeque::new();
+    distance.insert(src.clone(), 0);
+    todo.push_back(src.clone());
+    while let Some(node) = todo.pop_front() {
+        let d = distance[&node];
+        for (next, _) in state.neighbours(&node) {
+            if !distance.contains_key(&next) {
+                distance.insert(next.clone(), d + 1);
+                todo.push_back(next)
+            }
+        }
+    }
+    distance
+}
+
+// The set of nodes reachable from `src`, including itself.
+pub fn reachable<T, N, E>(state: &T, src: &N) -> HashSet<N>
+where
+    N: Eq + Hash + Clone,
+    T: Neighbours<N, E>,
+{
+    flood_fill(state, src).into_keys().collect()
+}
+
+// Returns the nodes of a shortest path from `src` to `tgt` (both included), searching from both
+// ends at once. Edges must be symmetric: `b` is a neighbour of `a` iff `a` is a neighbour of
+// `b`.
+pub fn bidirectional_bfs<T, N, E>(state: &T, src: &N, tgt: &N) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    T: Neighbours<N, E>,
+{
+    // For each side, the predecessor of each visited node (towards `src` or `tgt`).
+    let mut prev: [HashMap<N, Option<N>>; 2] = [HashMap::new(), HashMap::new()];
+    prev[0].insert(src.clone(), None);
+    prev[1].insert(tgt.clone(), None);
+    // The nodes discovered last on each side.
+    let mut frontier = [vec![src.clone()], vec![tgt.clone()]];
+
+    let mut meeting = if src == tgt { Some(src.clone()) } else { None };
+    while meeting.is_none() && !frontier[0].is_empty() && !frontier[1].is_empty() {
+        // Expand a whole layer of the smallest frontier. The first node seen from both sides
+        // is on a shortest path.
+        let side = if frontier[0].len() <= frontier[1].len() {
+            0
+        } else {
+            1
+        };
+        let mut next_frontier = vec![];
+        'layer: for node in std::mem::take(&mut frontier[side]) {
+            for (next, _) in state.neighbours(&node) {
+                if prev[side].contains_key(&next) {
+                    continue;
+                }
+                prev[side].insert(next.clone(), Some(node.clone()));
+                if prev[1 - side].contains_key(&next) {
+                    meeting = Some(next);
+                    break 'layer;
+                }
+                next_frontier.push(next)
+            }
+        }
+        frontier[side] = next_frontier;
+    }
+
+    let meeting = meeting?;
+    let walk = |prev: &HashMap<N, Option<N>>| {
+        let mut acc = vec![];
+        let mut ptr = prev[&meeting].clone();
+        while let Some(n) = ptr {
+            ptr = prev[&n].clone();
+            acc.push(n);
+        }
+        acc
+    };
+    let mut path = walk(&prev[0]);
+    path.reverse();
+    path.push(meeting.clone());
+    path.extend(walk(&prev[1]));
+    Some(path)
+}
+
+// A path found by a weighted search.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<N, E> {
+    pub cost: u64,
+    // From the source to the target, both included.
+    pub nodes: Vec<N>,
+    pub edges: Vec<E>,
+}
+
+type Visited<N, E> = (N, u64, Option<(usize, E)>);
+
+// Finds a path of minimum weight from `src` to a node satisfying `is_target`. `heuristic` must
+// not overestimate the remaining cost to a target (and should be consistent, i.e. decrease by
+// at most the weight of each edge, for the search to visit each node once).
+pub fn astar<T, N, E, F, H>(
+    state: &T,
+    src: &N,
+    mut is_target: F,
+    mut heuristic: H,
+) -> Option<Path<N, E>>
+where
+    N: Eq + Hash + Clone,
+    E: Clone,
+    T: Neighbours<N, E>,
+    F: FnMut(&N) -> bool,
+    H: FnMut(&N) -> u64,
+{
+    // Nodes are numbered in order of discovery, so that the heap does not need to compare them.
+    let mut index: HashMap<N, usize> = HashMap::new();
+    // For each node: the node, the best known cost, and the edge to it from its predecessor.
+    let mut nodes: Vec<Visited<N, E>> = vec![];
+    let mut heap = BinaryHeap::new();
+
+    index.insert(src.clone(), 0);
+    nodes.push((src.clone(), 0, None));
+    heap.push(Reverse((heuristic(src), 0, 0)));
+
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        // We have found a better way in the meantime.
+        if cost > nodes[i].1 {
+            continue;
+        }
+        let node = nodes[i].0.clone();
+        if is_target(&node) {
+            let mut path = Path {
+                cost,
+                nodes: vec![],
+                edges: vec![],
+            };
+            let mut ptr = Some(i);
+            while let Some(j) = ptr {
+                let (n, _, prev) = &nodes[j];
+                path.nodes.push(n.clone());
+                ptr = prev.as_ref().map(|(p, e)| {
+                    path.edges.push(e.clone());
+                    *p
+                });
+            }
+            path.nodes.reverse();
+            path.edges.reverse();
+            return Some(path);
+        }
+        for (next, edge) in state.neighbours(&node) {
+            let c = cost + state.weight(&edge);
+            let j = match index.get(&next) {
+                Some(&j) if nodes[j].1 <= c => continue,
+                Some(&j) => {
+                    nodes[j].1 = c;
+                    nodes[j].2 = Some((i, edge));
+                    j
+                }
+                None => {
+                    let j = nodes.len();
+                    index.insert(next.clone(), j);
+                    nodes.push((next, c, Some((i, edge))));
+                    j
+                }
+            };
+            heap.push(Reverse((c + heuristic(&nodes[j].0), c, j)));
+        }
+    }
+    None
+}
+
+// Finds a path of minimum weight from `src` to a node satisfying `is_target`.
+pub fn dijkstra<T, N, E, F>(state: &T, src: &N, is_target: F) -> Option<Path<N, E>>
+where
+    N: Eq + Hash + Clone,
+    E: Clone,
+    T: Neighbours<N, E>,
+    F: FnMut(&N) -> bool,
+{
+    astar(state, src, is_target, |_| 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    // A grid with walls, where moving costs the digit of the destination cell.
+    const MAZE: &str = "\
+1111#
+1#99#
+1#11#
+1#1##
+11111";
+
+    fn maze() -> Vec<Vec<u8>> {
+        MAZE.lines().map(|l| l.bytes().collect()).collect()
+    }
+
+    fn moves(maze: &[Vec<u8>], (x, y): (i32, i32)) -> Vec<((i32, i32), u64)> {
+        [(0, -1), (-1, 0), (1, 0), (0, 1)]
+            .iter()
+            .map(|(dx, dy)| (x + dx, y + dy))
+            .filter_map(|(x, y)| {
+                let c = *maze.get(y as usize)?.get(x as usize)?;
+                if c == b'#' {
+                    None
+                } else {
+                    Some(((x, y), (c - b'0') as u64))
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_unweighted() {
+        let maze = maze();
+        let g = FromFn(|&p: &(i32, i32)| moves(&maze, p));
+        let path = bfs(&g, &(0, 0), &(2, 2)).unwrap();
+        assert_eq!(path.len(), 4);
+        assert_eq!(bfs(&g, &(0, 0), &(4, 0)), None);
+
+        let nodes = bidirectional_bfs(&g, &(0, 0), &(2, 2)).unwrap();
+        assert_eq!(nodes.len(), 5);
+        assert_eq!((nodes[0], nodes[4]), ((0, 0), (2, 2)));
+        assert!(nodes
+            .windows(2)
+            .all(|w| moves(&maze, w[0]).iter().any(|m| m.0 == w[1])));
+        assert_eq!(bidirectional_bfs(&g, &(1, 4), &(1, 4)), Some(vec![(1, 4)]));
+        assert_eq!(bidirectional_bfs(&g, &(0, 0), &(10, 10)), None);
+
+        let distance = flood_fill(&g, &(0, 0));
+        assert_eq!(distance.len(), 17);
+        assert_eq!(distance[&(4, 4)], 8);
+        assert_eq!(reachable(&g, &(0, 0)).len(), 17);
+    }
+
+    #[test]
+    fn test_weighted() {
+        let maze = maze();
+        let g = WeightedFromFn(|&p: &(i32, i32)| moves(&maze, p));
+        // Going through the 9 is shorter, but more expensive.
+        let path = dijkstra(&g, &(0, 0), |&p| p == (3, 2)).unwrap();
+        assert_eq!(path.cost, 9);
+        assert_eq!(path.nodes.len(), 10);
+        assert_eq!(path.edges, vec![1; 9]);
+        let path = dijkstra(&g, &(3, 2), |&p| p == (3, 2)).unwrap();
+        assert_eq!((path.cost, path.nodes), (0, vec![(3, 2)]));
+
+        let manhattan = |&(x, y): &(i32, i32)| ((x - 3).abs() + (y - 2).abs()) as u64;
+        let path = astar(&g, &(0, 0), |&p| p == (3, 2), manhattan).unwrap();
+        assert_eq!(path.cost, 9);
+        assert_eq!(dijkstra(&g, &(0, 0), |&p| p == (4, 0)), None);
+    }
+
+    // Cross-checks the searches on pseudo-random graphs.
+    #[test]
+    fn test_random() {
+        let mut rng = Rng::new(7);
+        for _ in 0..100 {
+            let size = 2 + rng.below(30) as usize;
+            let mut edges = vec![vec![]; size];
+            for _ in 0..rng.below(3 * size as u64) {
+                let (a, b, w) = (
+                    rng.below(size as u64) as usize,
+                    rng.below(size as u64) as usize,
+                    1 + rng.below(4),
+                );
+                edges[a].push((b, w));
+                edges[b].push((a, w));
+            }
+            let weighted = WeightedFromFn(|&n: &usize| edges[n].clone());
+            let unweighted = FromFn(|&n: &usize| edges[n].clone());
+            let distance = flood_fill(&unweighted, &0);
+            for tgt in 0..size {
+                let path = bfs(&unweighted, &0, &tgt);
+                assert_eq!(path.map(|p| p.len()), distance.get(&tgt).copied());
+                let nodes = bidirectional_bfs(&unweighted, &0, &tgt);
+                assert_eq!(nodes.map(|p| p.len() - 1), distance.get(&tgt).copied());
+                let path = dijkstra(&weighted, &0, |&n| n == tgt);
+                assert_eq!(path.is_some(), distance.contains_key(&tgt));
+                if let Some(path) = path {
+                    assert_eq!(path.edges.iter().sum::<u64>(), path.cost);
+                    assert_eq!(path.nodes.len(), path.edges.len() + 1);
+                    // A* with admissible heuristics: the null one, and one that counts the last
+                    // edge, since all the weights are at least 1.
+                    let a = astar(&weighted, &0, |&n| n == tgt, |_| 0);
+                    assert_eq!(a.unwrap().cost, path.cost);
+                    let a = astar(&weighted, &0, |&n| n == tgt, |&n| (n != tgt) as u64);
+                    assert_eq!(a.unwrap().cost, path.cost);
+                }
+            }
+        }
+    }
+}
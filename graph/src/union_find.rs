@@ -0,0 +1,280 @@
+// Disjoint sets (union-find), to compute the connected components of a graph as its edges are
+// discovered. Both path compression and union by rank are used, which makes each operation run
+// in (almost) constant amortized time.
+//
+// `UnionFind` works on the indices `0..n`. `HashUnionFind` works on arbitrary values, which
+// are numbered as they are added.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Clone, Debug, Default)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    // The size of each component, only meaningful for the roots.
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl UnionFind {
+    // `n` singletons.
+    pub fn new(n: usize) -> UnionFind {
+        UnionFind {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            size: vec![1; n],
+            count: n,
+        }
+    }
+
+    // Number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    // Adds a singleton, and returns its index.
+    pub fn add(&mut self) -> usize {
+        let i = self.parent.len();
+        self.parent.push(i);
+        self.rank.push(0);
+        self.size.push(1);
+        self.count += 1;
+        i
+    }
+
+    // The representative of the component of `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root]
+        }
+        let mut x = x;
+        while x != root {
+            let next = self.parent[x];
+            self.parent[x] = root;
+            x = next
+        }
+        root
+    }
+
+    // Merges the components of `x` and `y`. Returns false if they were already the same.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let x = self.find(x);
+        let y = self.find(y);
+        if x == y {
+            return false;
+        }
+        let (x, y) = if self.rank[x] < self.rank[y] {
+            (y, x)
+        } else {
+            (x, y)
+        };
+        self.parent[y] = x;
+        self.size[x] += self.size[y];
+        if self.rank[x] == self.rank[y] {
+            self.rank[x] += 1
+        }
+        self.count -= 1;
+        true
+    }
+
+    pub fn same(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    // The number of elements in the component of `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    // The number of components.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    // The components, each sorted, ordered by their smallest element.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut index: HashMap<usize, usize> = HashMap::new();
+        let mut result: Vec<Vec<usize>> = vec![];
+        for x in 0..self.len() {
+            let root = self.find(x);
+            let i = *index.entry(root).or_insert_with(|| {
+                result.push(vec![]);
+                result.len() - 1
+            });
+            result[i].push(x)
+        }
+        result
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HashUnionFind<T> {
+    index: HashMap<T, usize>,
+    values: Vec<T>,
+    sets: UnionFind,
+}
+
+impl<T: Hash + Eq + Clone> Default for HashUnionFind<T> {
+    fn default() -> Self {
+        HashUnionFind::new()
+    }
+}
+
+impl<T: Hash + Eq + Clone> HashUnionFind<T> {
+    pub fn new() -> HashUnionFind<T> {
+        HashUnionFind {
+            index: HashMap::new(),
+            values: vec![],
+            sets: UnionFind::new(0),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn contains(&self, x: &T) -> bool {
+        self.index.contains_key(x)
+    }
+
+    // Adds `x` as a singleton if it is not there yet, and returns its index.
+    pub fn add(&mut self, x: T) -> usize {
+        if let Some(&i) = self.index.get(&x) {
+            return i;
+        }
+        let i = self.sets.add();
+        self.index.insert(x.clone(), i);
+        self.values.push(x);
+        i
+    }
+
+    // The representative of the component of `x`, or `None` if `x` was never added.
+    pub fn find(&mut self, x: &T) -> Option<&T> {
+        let i = *self.index.get(x)?;
+        let root = self.sets.find(i);
+        Some(&self.values[root])
+    }
+
+    // Merges the components of `x` and `y`, adding them if needed. Returns false if they were
+    // already the same.
+    pub fn union(&mut self, x: T, y: T) -> bool {
+        let x = self.add(x);
+        let y = self.add(y);
+        self.sets.union(x, y)
+    }
+
+    // Values that were never added are only in the same component as themselves.
+    pub fn same(&mut self, x: &T, y: &T) -> bool {
+        match (self.index.get(x), self.index.get(y)) {
+            (Some(&i), Some(&j)) => self.sets.same(i, j),
+            _ => x == y,
+        }
+    }
+
+    // The number of elements in the component of `x` (0 if it was never added).
+    pub fn size(&mut self, x: &T) -> usize {
+        match self.index.get(x) {
+            Some(&i) => self.sets.size(i),
+            None => 0,
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.sets.count()
+    }
+
+    // The components, with their elements in the order in which they were added.
+    pub fn components(&mut self) -> Vec<Vec<T>> {
+        self.sets
+            .components()
+            .into_iter()
+            .map(|c| c.into_iter().map(|i| self.values[i].clone()).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_union_find() {
+        let mut sets = UnionFind::new(6);
+        assert_eq!(sets.count(), 6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(4, 1));
+        assert!(!sets.union(0, 4));
+        assert!(sets.union(2, 3));
+        assert_eq!(sets.count(), 3);
+        assert!(sets.same(1, 4));
+        assert!(!sets.same(1, 2));
+        assert_eq!(sets.size(4), 3);
+        assert_eq!(sets.size(5), 1);
+        let i = sets.add();
+        sets.union(i, 5);
+        assert_eq!(
+            sets.components(),
+            vec![vec![0, 1, 4], vec![2, 3], vec![5, 6]]
+        );
+    }
+
+    #[test]
+    fn test_hash_union_find() {
+        let mut sets = HashUnionFind::new();
+        sets.union("a", "b");
+        sets.union("c", "d");
+        sets.union("b", "e");
+        sets.add("f");
+        assert_eq!(sets.len(), 6);
+        assert_eq!(sets.count(), 3);
+        let root = *sets.find(&"a").unwrap();
+        assert_eq!(sets.find(&"e"), Some(&root));
+        assert_eq!(sets.find(&"z"), None);
+        assert!(sets.same(&"a", &"e"));
+        assert!(!sets.same(&"a", &"z"));
+        assert_eq!(sets.size(&"d"), 2);
+        assert_eq!(
+            sets.components(),
+            vec![vec!["a", "b", "e"], vec!["c", "d"], vec!["f"]]
+        );
+    }
+
+    // Compares the sizes of the components with a naive labelling.
+    #[test]
+    fn test_random() {
+        let mut rng = Rng::new(42);
+        let n = 200;
+        let mut sets = UnionFind::new(n);
+        let mut label: Vec<usize> = (0..n).collect();
+        for _ in 0..150 {
+            let (x, y) = (rng.below(n as u64) as usize, rng.below(n as u64) as usize);
+            sets.union(x, y);
+            let (old, new) = (label[y], label[x]);
+            for l in label.iter_mut().filter(|l| **l == old) {
+                *l = new
+            }
+        }
+        for x in 0..n {
+            assert_eq!(
+                sets.size(x),
+                label.iter().filter(|&&l| l == label[x]).count()
+            );
+        }
+        let mut labels = label.clone();
+        labels.sort();
+        labels.dedup();
+        assert_eq!(sets.count(), labels.len());
+    }
+}